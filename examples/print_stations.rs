@@ -10,9 +10,7 @@ fn main() {
         .unwrap();
 
     let socket = NlSocket::connect().unwrap();
-    let interfaces = socket.list_interfaces().unwrap();
-    for interface in interfaces {
-        let stations = socket.list_stations(interface.interface_index).unwrap();
+    for (_, stations) in socket.list_all_stations().unwrap() {
         for station in stations {
             println!("{station:#?}");
         }
@@ -0,0 +1,142 @@
+use crate::attributes::AclPolicy as NlAclPolicy;
+use crate::attributes::HiddenSsid as NlHiddenSsid;
+use crate::interface::ChannelConfig;
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Access point configuration used when starting an AP.
+pub struct ApConfig {
+    /// Network SSID.
+    pub ssid: String,
+    /// Beacon frame head (everything before the TIM element).
+    pub beacon_head: Vec<u8>,
+    /// Beacon frame tail (everything after the TIM element).
+    pub beacon_tail: Vec<u8>,
+    /// Beacon interval in TUs.
+    pub beacon_interval: u32,
+    /// DTIM period for beaconing.
+    pub dtim_period: u32,
+    /// Whether the SSID is hidden in beacon frames.
+    pub hidden_ssid: HiddenSsid,
+    /// Operating channel. If not set, the driver uses the current channel.
+    pub channel: Option<ChannelConfig>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// SSID hiding mode of an access point.
+pub enum HiddenSsid {
+    /// SSID is broadcast in beacon frames.
+    #[default]
+    NotInUse,
+    /// SSID is hidden by using zero-length SSID element in beacon frames.
+    ZeroLength,
+    /// SSID is hidden by replacing the SSID element contents with zeros.
+    ZeroContents,
+}
+
+impl From<HiddenSsid> for NlHiddenSsid {
+    fn from(value: HiddenSsid) -> Self {
+        match value {
+            HiddenSsid::NotInUse => NlHiddenSsid::NotInUse,
+            HiddenSsid::ZeroLength => NlHiddenSsid::ZeroLen,
+            HiddenSsid::ZeroContents => NlHiddenSsid::ZeroContents,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Beacon and probe response templates of a running access point.
+///
+/// Only the templates that are set are updated.
+pub struct BeaconConfig {
+    /// Beacon frame head (everything before the TIM element).
+    pub beacon_head: Option<Vec<u8>>,
+    /// Beacon frame tail (everything after the TIM element).
+    pub beacon_tail: Option<Vec<u8>>,
+    /// Probe response template for drivers that offload probe responses.
+    pub probe_response: Option<Vec<u8>>,
+    /// Extra information elements added to beacon frames.
+    pub ie: Option<Vec<u8>>,
+    /// Extra information elements added to probe response frames.
+    pub ie_probe_response: Option<Vec<u8>>,
+    /// Extra information elements added to (re)association response frames.
+    pub ie_assoc_response: Option<Vec<u8>>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// MAC address based access control policy of an access point.
+pub enum AclPolicy {
+    /// Deny stations which are listed and accept all other stations.
+    AcceptUnlessListed,
+    /// Accept stations which are listed and deny all other stations.
+    DenyUnlessListed,
+}
+
+impl From<AclPolicy> for NlAclPolicy {
+    fn from(value: AclPolicy) -> Self {
+        match value {
+            AclPolicy::AcceptUnlessListed => NlAclPolicy::AcceptUnlessListed,
+            AclPolicy::DenyUnlessListed => NlAclPolicy::DenyUnlessListed,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// QoS map used to map DSCP values to user priorities as in the QoS Map
+/// element (IEEE 802.11-2016 9.4.2.95).
+pub struct QosMap {
+    /// DSCP values that are mapped to a user priority regardless of the ranges
+    /// (at most 21).
+    pub dscp_exceptions: Vec<DscpException>,
+    /// DSCP range for each user priority 0-7.
+    pub up_ranges: [DscpRange; 8],
+}
+
+impl QosMap {
+    pub(crate) fn payload(&self) -> Vec<u8> {
+        let mut payload = Vec::with_capacity(2 * (self.dscp_exceptions.len() + 8));
+        for exception in &self.dscp_exceptions {
+            payload.push(exception.dscp);
+            payload.push(exception.user_priority);
+        }
+        for range in &self.up_ranges {
+            payload.push(range.low);
+            payload.push(range.high);
+        }
+        payload
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// DSCP value mapped to a user priority.
+pub struct DscpException {
+    /// DSCP value.
+    pub dscp: u8,
+    /// User priority the DSCP value maps to.
+    pub user_priority: u8,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Range of DSCP values mapped to a user priority.
+pub struct DscpRange {
+    /// Lowest DSCP value of the range.
+    pub low: u8,
+    /// Highest DSCP value of the range.
+    pub high: u8,
+}
+
+impl Default for DscpRange {
+    /// Range that is not used (both values 255).
+    fn default() -> Self {
+        Self {
+            low: 255,
+            high: 255,
+        }
+    }
+}
@@ -1,23 +1,45 @@
-use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, VecDeque};
 use std::fmt::Write;
-use std::io::Cursor;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
 
-use log::debug;
+use futures_util::future;
+use futures_util::stream::{self, Stream};
+use log::{debug, log_enabled, Level};
 use neli::consts::nl::Nlmsg;
 use neli::consts::socket::NlFamily;
-use neli::err::RouterError;
+use neli::genl::Genlmsghdr;
 use neli::nl::NlPayload;
 use neli::router::asynchronous::{NlRouter, NlRouterReceiverHandle};
 use neli::utils::Groups;
-use neli::ToBytes;
 
+use crate::ap::{AclPolicy, ApConfig, BeaconConfig, QosMap};
 use crate::attributes::{Attribute, Attrs, MonitorFlags};
-use crate::error::Result;
-use crate::interface::{ChannelWidth, InterfaceType};
-use crate::netlink::{Neli80211Header, Nl80211Request};
-use crate::reg_domain::RegulatoryDomain;
-use crate::station::WirelessStation;
-use crate::wiphy::PhysicalDevice;
+use crate::commands::Command;
+use crate::error::{ErrorKind, NlError, Result};
+use crate::event::{Event, MulticastGroup, RadarEvent};
+use crate::interface::{
+    BitrateMask, ChannelConfig, ChannelWidth, FrameTx, InterfaceType, MacAddress, TxPower,
+};
+use crate::key::{DefaultKeyType, Key, KeyInfo};
+use crate::mesh::{MeshParams, MeshPath, MeshProxyPath};
+use crate::netlink::{
+    split_wiphy_dump, Neli80211Header, Nl80211Request, Response, DUMP_CAPACITY_HINT,
+    NL80211_FAMILY_ID,
+};
+use crate::observer::{Observation, SocketObserver};
+use crate::reg_domain::{RegulatoryDomain, RegulatoryHint};
+use crate::station::{
+    associated_bssid, LazyStation, LinkStatus, StationConfig, StationFields, WirelessStation,
+    WirelessStationRef,
+};
+use crate::survey::SurveyInfo;
+use crate::topology::Topology;
+use crate::wiphy::{
+    parse_coalesce_rules, CoalesceRule, PhysicalDevice, ProtocolFeatures, SarLimit, TxqParams,
+    WiphyParams,
+};
 
 use super::interface::WirelessInterface;
 
@@ -25,36 +47,85 @@ use super::interface::WirelessInterface;
 pub struct AsyncNlSocket {
     socket: NlRouter,
     nl_type: u16,
+    events: NlRouterReceiverHandle<u16, Genlmsghdr<u8, u16>>,
+    dump_retries: Option<u32>,
+    observer: Option<Arc<dyn SocketObserver>>,
+    protocol_features: OnceLock<ProtocolFeatures>,
+    skipped_events: VecDeque<Event>,
 }
 
 impl AsyncNlSocket {
     /// Connect netlink socket.
     pub async fn connect() -> Result<Self> {
-        let (socket, _) = NlRouter::connect(NlFamily::Generic, None, Groups::empty()).await?;
-        let nl_type = socket.resolve_genl_family("nl80211").await?;
-        Ok(Self { socket, nl_type })
+        let (socket, events) = NlRouter::connect(NlFamily::Generic, None, Groups::empty()).await?;
+        let nl_type = match NL80211_FAMILY_ID.get() {
+            Some(id) => *id,
+            None => {
+                let id = socket.resolve_genl_family("nl80211").await?;
+                *NL80211_FAMILY_ID.get_or_init(|| id)
+            }
+        };
+        Ok(Self {
+            socket,
+            nl_type,
+            events,
+            dump_retries: None,
+            observer: None,
+            protocol_features: OnceLock::new(),
+            skipped_events: VecDeque::new(),
+        })
+    }
+
+    /// Verify that all messages of a dump share the same generation number.
+    ///
+    /// The kernel increments the generation whenever the dumped object list
+    /// changes. When enabled, interface, station, physical device and mesh path
+    /// dumps are repeated up to `max_retries` times if the generation changes
+    /// mid-dump, after which an `ErrorKind::InconsistentDump` error is
+    /// returned. `None` disables the check.
+    pub fn set_consistent_dumps(&mut self, max_retries: Option<u32>) {
+        self.dump_retries = max_retries;
+    }
+
+    /// Set an observer called for every request sent by this socket.
+    pub fn set_observer(&mut self, observer: Option<Arc<dyn SocketObserver>>) {
+        self.observer = observer;
     }
 
     pub async fn list_interfaces(&self) -> Result<Vec<WirelessInterface>> {
-        let request = Nl80211Request::list_interfaces();
+        self.handle_consistent_dump(Nl80211Request::list_interfaces, |handle| {
+            Ok(TryInto::<WirelessInterface>::try_into(handle)?)
+        })
+        .await
+    }
+
+    pub async fn get_interface(&self, if_index: u32) -> Result<Option<WirelessInterface>> {
+        let request = Nl80211Request::get_interface(if_index)?;
         let recv = self.send(request).await?;
-        let mut responses = Vec::new();
+
+        let mut result: Option<WirelessInterface> = None;
         Self::handle_dump_response(recv, |handle| {
-            responses.push(TryInto::<WirelessInterface>::try_into(handle)?);
+            let device: WirelessInterface = handle.try_into()?;
+            if device.interface_index == if_index {
+                result = Some(device);
+            }
             Ok(())
         })
         .await?;
-        Ok(responses)
+        Ok(result)
     }
 
-    pub async fn get_interface(&self, if_index: u32) -> Result<Option<WirelessInterface>> {
-        let request = Nl80211Request::get_interface(if_index);
+    /// Get an interface by its wireless device identifier.
+    ///
+    /// Used for interfaces without a network device, e.g. P2P-Device and NAN.
+    pub async fn get_interface_by_wdev(&self, wdev: u64) -> Result<Option<WirelessInterface>> {
+        let request = Nl80211Request::get_interface_by_wdev(wdev)?;
         let recv = self.send(request).await?;
 
         let mut result: Option<WirelessInterface> = None;
         Self::handle_dump_response(recv, |handle| {
             let device: WirelessInterface = handle.try_into()?;
-            if device.interface_index == if_index {
+            if device.wdev == Some(wdev) {
                 result = Some(device);
             }
             Ok(())
@@ -63,56 +134,259 @@ impl AsyncNlSocket {
         Ok(result)
     }
 
+    /// Get an interface by its name, e.g. "wlan0".
+    pub async fn get_interface_by_name(&self, name: &str) -> Result<Option<WirelessInterface>> {
+        let interfaces = self.list_interfaces().await?;
+        Ok(interfaces
+            .into_iter()
+            .find(|interface| interface.name == name))
+    }
+
     pub async fn set_interface(&self, if_index: u32, if_type: InterfaceType) -> Result<()> {
-        let request = Nl80211Request::set_interface(if_index, if_type);
+        let request = Nl80211Request::set_interface(if_index, if_type)?;
         let recv = self.send(request).await?;
         Self::handle_ack_response(recv).await
     }
 
     pub async fn set_monitor_flags(&self, if_index: u32, flags: Vec<MonitorFlags>) -> Result<()> {
-        let request = Nl80211Request::set_monitor_flags(if_index, flags);
+        let request = Nl80211Request::set_monitor_flags(if_index, flags)?;
         let recv = self.send(request).await?;
         Self::handle_ack_response(recv).await
     }
 
     pub async fn set_channel(&self, if_index: u32, freq: u32, width: ChannelWidth) -> Result<()> {
-        let request = Nl80211Request::set_channel(if_index, freq, width);
+        let request = Nl80211Request::set_channel(if_index, freq, width)?;
         let recv = self.send(request).await?;
         Self::handle_ack_response(recv).await
     }
 
-    pub async fn list_stations(&self, if_index: u32) -> Result<Vec<WirelessStation>> {
-        let request = Nl80211Request::list_stations(if_index);
+    /// Set the operating channel of an interface, including the center
+    /// frequencies required by channels wider than 20 MHz.
+    pub async fn set_channel_config(&self, if_index: u32, channel: ChannelConfig) -> Result<()> {
+        channel.validate()?;
+        let request = Nl80211Request::set_channel_config(if_index, channel)?;
         let recv = self.send(request).await?;
+        Self::handle_ack_response(recv).await
+    }
 
-        let mut responses = Vec::new();
+    pub async fn list_stations(&self, if_index: u32) -> Result<Vec<WirelessStation>> {
+        self.handle_consistent_dump(
+            || Nl80211Request::list_stations(if_index),
+            |handle| Ok(TryInto::<WirelessStation>::try_into(handle)?),
+        )
+        .await
+    }
+
+    /// List stations associated to an interface, decoding only the station
+    /// information in `fields`.
+    pub async fn list_stations_with(
+        &self,
+        if_index: u32,
+        fields: StationFields,
+    ) -> Result<Vec<WirelessStation>> {
+        self.handle_consistent_dump(
+            || Nl80211Request::list_stations(if_index),
+            |handle| Ok(WirelessStation::from_attributes(handle, fields)?),
+        )
+        .await
+    }
+
+    /// List stations associated to an interface, deferring decoding of the
+    /// station information until it is accessed.
+    pub async fn list_stations_lazy(&self, if_index: u32) -> Result<Vec<LazyStation>> {
+        self.handle_consistent_dump(
+            || Nl80211Request::list_stations(if_index),
+            |handle| Ok(LazyStation::try_from(handle)?),
+        )
+        .await
+    }
+
+    /// List stations associated to an interface into `stations`.
+    ///
+    /// The vector is cleared before the request is sent and left empty if it
+    /// fails, so its allocation can be reused when polling stations
+    /// repeatedly. The request itself is still built per call: neli takes
+    /// ownership of the message attributes when sending, so there is no
+    /// attribute buffer to hand back for reuse.
+    pub async fn list_stations_into(
+        &self,
+        if_index: u32,
+        stations: &mut Vec<WirelessStation>,
+    ) -> Result<()> {
+        self.handle_consistent_dump_into(
+            || Nl80211Request::list_stations(if_index),
+            |handle| Ok(TryInto::<WirelessStation>::try_into(handle)?),
+            stations,
+        )
+        .await
+    }
+
+    /// List stations of all wireless interfaces. Stations of each interface are
+    /// requested concurrently.
+    pub async fn list_all_stations(
+        &self,
+    ) -> Result<Vec<(WirelessInterface, Vec<WirelessStation>)>> {
+        self.list_all_stations_each()
+            .await?
+            .into_iter()
+            .map(|(interface, stations)| Ok((interface, stations?)))
+            .collect()
+    }
+
+    /// List stations of all wireless interfaces with a result per interface.
+    /// Stations of each interface are requested concurrently.
+    ///
+    /// Unlike `list_all_stations`, a failure on one interface does not affect
+    /// the results of the others.
+    pub async fn list_all_stations_each(
+        &self,
+    ) -> Result<Vec<(WirelessInterface, Result<Vec<WirelessStation>>)>> {
+        let interfaces = self.list_interfaces().await?;
+        let stations = future::join_all(interfaces.iter().map(|interface| async move {
+            match interface.interface_index {
+                0 => Ok(Vec::new()),
+                if_index => self.list_stations(if_index).await,
+            }
+        }))
+        .await;
+        Ok(interfaces.into_iter().zip(stations).collect())
+    }
+
+    /// Poll stations of an interface every `interval`.
+    ///
+    /// With `changes_in`, a station list is only delivered when the stations
+    /// projected onto these fields differ from the previously delivered
+    /// list. Errors are delivered and polling continues. Polling stops when
+    /// the stream is dropped.
+    pub fn watch_stations(
+        &self,
+        if_index: u32,
+        interval: Duration,
+        changes_in: Option<StationFields>,
+    ) -> impl Stream<Item = Result<Vec<WirelessStation>>> + '_ {
+        let state: (Option<Vec<WirelessStation>>, bool) = (None, true);
+        stream::unfold(state, move |(mut previous, mut first)| async move {
+            loop {
+                if !first {
+                    tokio::time::sleep(interval).await;
+                }
+                first = false;
+                let result = self.list_stations(if_index).await;
+                if let (Some(fields), Ok(stations)) = (changes_in, &result) {
+                    let projected: Vec<_> = stations.iter().map(|s| s.project(fields)).collect();
+                    if previous.as_ref() == Some(&projected) {
+                        continue;
+                    }
+                    previous = Some(projected);
+                }
+                return Some((result, (previous, first)));
+            }
+        })
+    }
+
+    /// Get a snapshot of all physical devices with their interfaces and
+    /// stations.
+    pub async fn snapshot(&self) -> Result<Topology> {
+        let (devices, interfaces) =
+            future::try_join(self.list_physical_devices(), self.list_all_stations()).await?;
+        Ok(Topology::new(devices, interfaces))
+    }
+
+    /// Call `f` for each station associated to an interface with counters
+    /// borrowed from the received message.
+    ///
+    /// Intended for frequent polling, where only a few statistics are read.
+    pub async fn for_each_station<F: FnMut(WirelessStationRef<'_>)>(
+        &self,
+        if_index: u32,
+        mut f: F,
+    ) -> Result<()> {
+        let request = Nl80211Request::list_stations(if_index)?;
+        let recv = self.send(request).await?;
         Self::handle_dump_response(recv, |handle| {
-            responses.push(TryInto::<WirelessStation>::try_into(handle)?);
+            f(WirelessStationRef::try_from(handle)?);
             Ok(())
         })
-        .await?;
-        Ok(responses)
+        .await
     }
 
-    pub async fn list_physical_devices(&self) -> Result<Vec<PhysicalDevice>> {
-        let request = Nl80211Request::list_physical_devices();
+    /// List stations associated to an interface, decoding each station lazily
+    /// as it is read from the socket.
+    ///
+    /// Unlike `list_stations`, the dump is not buffered, so consumers can stop
+    /// early by dropping the stream.
+    pub async fn list_stations_iter(
+        &self,
+        if_index: u32,
+    ) -> Result<impl Stream<Item = Result<WirelessStation>>> {
+        let request = Nl80211Request::list_stations(if_index)?;
         let recv = self.send(request).await?;
 
-        let mut responses = HashMap::new();
+        Ok(stream::unfold(recv, |mut recv| async move {
+            let item = recv.next().await?.and_then(|payload| {
+                let handle = payload.attrs().get_attr_handle();
+                Ok(WirelessStation::try_from(&handle)?)
+            });
+            Some((item, recv))
+        }))
+    }
+
+    /// Get the protocol features supported by the kernel. The result is
+    /// cached by the socket.
+    pub async fn get_protocol_features(&self) -> Result<ProtocolFeatures> {
+        if let Some(features) = self.protocol_features.get() {
+            return Ok(*features);
+        }
+        let request = Nl80211Request::get_protocol_features()?;
+        let recv = self.send(request).await?;
+
+        let mut features = ProtocolFeatures::empty();
         Self::handle_dump_response(recv, |handle| {
-            let device: PhysicalDevice = handle.try_into()?;
-            responses
-                .entry(device.wiphy_index)
-                .and_modify(|d: &mut PhysicalDevice| d.merge(&device))
-                .or_insert(device);
+            if let Ok(bits) = handle.get_attr_payload_as::<u32>(Attribute::ProtocolFeatures) {
+                features = ProtocolFeatures::from_bits_retain(bits);
+            }
             Ok(())
         })
         .await?;
-        Ok(responses.values().cloned().collect())
+        Ok(*self.protocol_features.get_or_init(|| features))
     }
 
+    /// List physical devices. Split dumps are requested if the kernel supports
+    /// them, and the parts of each device are merged. Kernels that do not
+    /// support `GetProtocolFeatures` (before 3.10) get an unsplit dump.
+    pub async fn list_physical_devices(&self) -> Result<Vec<PhysicalDevice>> {
+        let request: fn() -> Result<Nl80211Request> =
+            if split_wiphy_dump(self.get_protocol_features().await)? {
+                Nl80211Request::list_physical_devices
+            } else {
+                Nl80211Request::list_physical_devices_unsplit
+            };
+        let devices = self
+            .handle_consistent_dump(request, |handle| {
+                Ok(TryInto::<PhysicalDevice>::try_into(handle)?)
+            })
+            .await?;
+
+        let mut responses: HashMap<u32, PhysicalDevice> = HashMap::new();
+        for device in devices {
+            match responses.entry(device.wiphy_index) {
+                Entry::Occupied(mut entry) => entry.get_mut().merge(device),
+                Entry::Vacant(entry) => {
+                    entry.insert(device);
+                }
+            }
+        }
+        Ok(responses.into_values().collect())
+    }
+
+    /// Get a physical device by its index, following the same split dump
+    /// selection as `list_physical_devices`.
     pub async fn get_physical_device(&self, wiphy_index: u32) -> Result<Option<PhysicalDevice>> {
-        let request = Nl80211Request::get_physical_device(wiphy_index);
+        let request = if split_wiphy_dump(self.get_protocol_features().await)? {
+            Nl80211Request::get_physical_device(wiphy_index)?
+        } else {
+            Nl80211Request::get_physical_device_unsplit(wiphy_index)?
+        };
         let recv = self.send(request).await?;
 
         let mut result: Option<PhysicalDevice> = None;
@@ -120,7 +394,7 @@ impl AsyncNlSocket {
             let device: PhysicalDevice = handle.try_into()?;
             if device.wiphy_index == wiphy_index {
                 if let Some(d) = result.as_mut() {
-                    d.merge(&device);
+                    d.merge(device);
                 } else {
                     result = Some(device);
                 }
@@ -132,7 +406,7 @@ impl AsyncNlSocket {
     }
 
     pub async fn get_regulatory_domain(&self) -> Result<Vec<RegulatoryDomain>> {
-        let request = Nl80211Request::get_regulatory_domain();
+        let request = Nl80211Request::get_regulatory_domain()?;
         let recv = self.send(request).await?;
 
         let mut responses = Vec::new();
@@ -144,72 +418,865 @@ impl AsyncNlSocket {
         Ok(responses)
     }
 
+    /// Get the regulatory domain of a device.
+    ///
+    /// Self-managed devices return their private regulatory domain, other
+    /// devices return the global regulatory domain.
+    pub async fn get_regulatory_domain_for(
+        &self,
+        wiphy_index: u32,
+    ) -> Result<Option<RegulatoryDomain>> {
+        let request = Nl80211Request::get_regulatory_domain_for(wiphy_index)?;
+        let recv = self.send(request).await?;
+
+        let mut result: Option<RegulatoryDomain> = None;
+        Self::handle_dump_response(recv, |handle| {
+            result = Some(handle.try_into()?);
+            Ok(())
+        })
+        .await?;
+        Ok(result)
+    }
+
     pub async fn trigger_scan(&self, if_index: u32) -> Result<()> {
-        let request = Nl80211Request::trigger_scan(if_index);
+        let request = Nl80211Request::trigger_scan(if_index)?;
+        let recv = self.send(request).await?;
+        Self::handle_ack_response(recv).await
+    }
+
+    /// Trigger a new scan on a wireless device without a network interface.
+    pub async fn trigger_scan_by_wdev(&self, wdev: u64) -> Result<()> {
+        let request = Nl80211Request::trigger_scan_by_wdev(wdev)?;
         let recv = self.send(request).await?;
         Self::handle_ack_response(recv).await
     }
 
     pub async fn abort_scan(&self, if_index: u32) -> Result<()> {
-        let request = Nl80211Request::abort_scan(if_index);
+        let request = Nl80211Request::abort_scan(if_index)?;
         let recv = self.send(request).await?;
         Self::handle_ack_response(recv).await
     }
 
-    async fn send(
+    /// Stop an ongoing scan on a wireless device without a network interface.
+    ///
+    /// Returns NlError ENOENT if a scan is not running.
+    pub async fn abort_scan_by_wdev(&self, wdev: u64) -> Result<()> {
+        let request = Nl80211Request::abort_scan_by_wdev(wdev)?;
+        let recv = self.send(request).await?;
+        Self::handle_ack_response(recv).await
+    }
+
+    /// Install a PMK for 4-way handshake offload.
+    ///
+    /// Used with drivers that offload the 4-way handshake (`Want1x4wayHs`) to
+    /// provide the PMK (and PMK-R0 name for FT) for the given authenticator.
+    pub async fn set_pmk(
         &self,
-        request: Nl80211Request,
-    ) -> std::result::Result<
-        NlRouterReceiverHandle<Nlmsg, Neli80211Header>,
-        RouterError<u16, Neli80211Header>,
-    > {
-        if cfg!(debug_assertions) {
-            let mut b: Cursor<Vec<u8>> = Cursor::new(Vec::new());
-            request.nl_payload.to_bytes(&mut b).unwrap();
-            let octets: String = b.get_ref().iter().fold(String::new(), |mut output, b| {
-                let _ = write!(output, "{b:02x} ");
-                output
-            });
-            debug!("[PAYLOAD] {octets}");
+        if_index: u32,
+        mac: MacAddress,
+        pmk: &[u8],
+        pmkr0_name: Option<&[u8]>,
+    ) -> Result<()> {
+        let request = Nl80211Request::set_pmk(if_index, mac, pmk, pmkr0_name)?;
+        let recv = self.send(request).await?;
+        Self::handle_ack_response(recv).await
+    }
+
+    /// Remove a previously installed PMK for the given authenticator.
+    pub async fn del_pmk(&self, if_index: u32, mac: MacAddress) -> Result<()> {
+        let request = Nl80211Request::del_pmk(if_index, mac)?;
+        let recv = self.send(request).await?;
+        Self::handle_ack_response(recv).await
+    }
+
+    /// Send the OWE (Opportunistic Wireless Encryption) processing result for a peer.
+    ///
+    /// Used in AP mode when the driver requested OWE handling from userspace.
+    /// `dh_ie` contains the Diffie-Hellman parameter element that is sent to the peer
+    /// in the association response.
+    pub async fn update_owe_info(
+        &self,
+        if_index: u32,
+        mac: MacAddress,
+        status_code: u16,
+        dh_ie: &[u8],
+    ) -> Result<()> {
+        let request = Nl80211Request::update_owe_info(if_index, mac, status_code, dh_ie)?;
+        let recv = self.send(request).await?;
+        Self::handle_ack_response(recv).await
+    }
+
+    /// Get current association information of a station mode interface.
+    ///
+    /// Combines the interface information with the station entry of the
+    /// associated access point, similar to `iw dev <ifname> link`. The access
+    /// point is the BSS marked as associated in the scan results. Returns `None`
+    /// if the interface does not exist, is not a station or P2P client
+    /// interface, or is not associated.
+    pub async fn get_link(&self, if_index: u32) -> Result<Option<LinkStatus>> {
+        let Some(interface) = self.get_interface(if_index).await? else {
+            return Ok(None);
+        };
+        if !matches!(
+            interface.interface_type,
+            Some(InterfaceType::Station | InterfaceType::P2pClient)
+        ) {
+            return Ok(None);
         }
+
+        let request = Nl80211Request::get_scan(if_index)?;
+        let recv = self.send(request).await?;
+        let mut bssid = None;
+        Self::handle_dump_response(recv, |handle| {
+            if let Some(associated) = associated_bssid(handle)? {
+                bssid = Some(associated);
+            }
+            Ok(())
+        })
+        .await?;
+        let Some(bssid) = bssid else {
+            return Ok(None);
+        };
+
+        let station = self
+            .list_stations(if_index)
+            .await?
+            .into_iter()
+            .find(|station| station.mac == bssid);
+        Ok(station.map(|station| LinkStatus::new(interface, station)))
+    }
+
+    /// Start operating as an access point on the given interface.
+    ///
+    /// The interface must already be in access point mode.
+    pub async fn start_ap(&self, if_index: u32, config: ApConfig) -> Result<()> {
+        if let Some(channel) = &config.channel {
+            channel.validate()?;
+        }
+        let request = Nl80211Request::start_ap(if_index, config)?;
+        let recv = self.send(request).await?;
+        Self::handle_ack_response(recv).await
+    }
+
+    /// Stop access point operation on the given interface.
+    pub async fn stop_ap(&self, if_index: u32) -> Result<()> {
+        let request = Nl80211Request::stop_ap(if_index)?;
+        let recv = self.send(request).await?;
+        Self::handle_ack_response(recv).await
+    }
+
+    /// Update beacon and probe response templates of a running access point.
+    pub async fn set_beacon(&self, if_index: u32, config: BeaconConfig) -> Result<()> {
+        let request = Nl80211Request::set_beacon(if_index, config)?;
+        let recv = self.send(request).await?;
+        Self::handle_ack_response(recv).await
+    }
+
+    /// Add a new station to an access point interface.
+    pub async fn new_station(
+        &self,
+        if_index: u32,
+        mac: MacAddress,
+        config: StationConfig,
+    ) -> Result<()> {
+        let request = Nl80211Request::new_station(if_index, mac, config)?;
+        let recv = self.send(request).await?;
+        Self::handle_ack_response(recv).await
+    }
+
+    /// Modify parameters of an existing station.
+    pub async fn set_station(
+        &self,
+        if_index: u32,
+        mac: MacAddress,
+        config: StationConfig,
+    ) -> Result<()> {
+        let request = Nl80211Request::set_station(if_index, mac, config)?;
+        let recv = self.send(request).await?;
+        Self::handle_ack_response(recv).await
+    }
+
+    /// Remove a station from an access point interface.
+    pub async fn del_station(&self, if_index: u32, mac: MacAddress) -> Result<()> {
+        let request = Nl80211Request::del_station(if_index, mac)?;
+        let recv = self.send(request).await?;
+        Self::handle_ack_response(recv).await
+    }
+
+    /// Set MAC address based access control list of an access point.
+    ///
+    /// The maximum number of addresses supported by the device is reported in
+    /// `PhysicalDevice::max_acl_mac_addresses`.
+    pub async fn set_mac_acl(
+        &self,
+        if_index: u32,
+        policy: AclPolicy,
+        mac_addresses: Vec<MacAddress>,
+    ) -> Result<()> {
+        let request = Nl80211Request::set_mac_acl(if_index, policy, mac_addresses)?;
+        let recv = self.send(request).await?;
+        Self::handle_ack_response(recv).await
+    }
+
+    /// Probe if a client associated to an access point is still reachable.
+    ///
+    /// Returns a cookie identifying the probe. The result is delivered as an
+    /// `Event::ProbeClient` with the same cookie to sockets subscribed to
+    /// `MulticastGroup::Mlme`.
+    pub async fn probe_client(&self, if_index: u32, mac: MacAddress) -> Result<u64> {
+        let request = Nl80211Request::probe_client(if_index, mac)?;
+        let recv = self.send(request).await?;
+
+        let mut cookie = None;
+        Self::handle_dump_response(recv, |handle| {
+            cookie = Some(handle.get_attr_payload_as::<u64>(Attribute::Cookie)?);
+            Ok(())
+        })
+        .await?;
+        cookie.ok_or_else(|| NlError::new("Probe client response did not contain a cookie"))
+    }
+
+    /// Subscribe to a nl80211 multicast group to receive its events with
+    /// `next_event`.
+    pub async fn subscribe(&self, group: MulticastGroup) -> Result<()> {
+        let id = self
+            .socket
+            .resolve_nl_mcast_group("nl80211", group.name())
+            .await?;
         self.socket
+            .add_mcast_membership(Groups::new_groups(&[id]))?;
+        Ok(())
+    }
+
+    /// Wait for the next event delivered to this socket.
+    ///
+    /// Returns `None` if the socket was closed.
+    pub async fn next_event(&mut self) -> Result<Option<Event>> {
+        if let Some(event) = self.skipped_events.pop_front() {
+            return Ok(Some(event));
+        }
+        self.receive_event().await
+    }
+
+    /// Receive the next event from the socket, bypassing skipped events.
+    async fn receive_event(&mut self) -> Result<Option<Event>> {
+        while let Some(response) = self.events.next::<Nlmsg, Neli80211Header>().await {
+            let response = response?;
+            if let NlPayload::Payload(payload) = response.nl_payload() {
+                let handle = payload.attrs().get_attr_handle();
+                return Ok(Some(Event::from_message(*payload.cmd(), &handle)?));
+            }
+        }
+        Ok(None)
+    }
+
+    /// Register this socket to receive all beacon frames seen by the wiphy.
+    ///
+    /// The beacons are delivered as `Event::Frame` events to this socket with
+    /// `next_event`. The registration is removed when the socket is closed.
+    pub async fn register_beacons(&self, wiphy_index: u32) -> Result<()> {
+        let request = Nl80211Request::register_beacons(wiphy_index)?;
+        let recv = self.send(request).await?;
+        Self::handle_ack_response(recv).await
+    }
+
+    /// Get mesh configuration parameters of a mesh point interface.
+    pub async fn get_mesh_config(&self, if_index: u32) -> Result<MeshParams> {
+        let request = Nl80211Request::get_mesh_config(if_index)?;
+        let recv = self.send(request).await?;
+
+        let mut params = MeshParams::default();
+        Self::handle_dump_response(recv, |handle| {
+            params = handle.try_into()?;
+            Ok(())
+        })
+        .await?;
+        Ok(params)
+    }
+
+    /// Set mesh configuration parameters of a mesh point interface.
+    pub async fn set_mesh_config(&self, if_index: u32, params: MeshParams) -> Result<()> {
+        let request = Nl80211Request::set_mesh_config(if_index, params)?;
+        let recv = self.send(request).await?;
+        Self::handle_ack_response(recv).await
+    }
+
+    /// List mesh paths of a mesh point interface.
+    pub async fn list_mesh_paths(&self, if_index: u32) -> Result<Vec<MeshPath>> {
+        self.handle_consistent_dump(
+            || Nl80211Request::list_mesh_paths(if_index),
+            |handle| Ok(TryInto::<MeshPath>::try_into(handle)?),
+        )
+        .await
+    }
+
+    /// Get the mesh path to a destination.
+    pub async fn get_mesh_path(
+        &self,
+        if_index: u32,
+        destination: MacAddress,
+    ) -> Result<Option<MeshPath>> {
+        let request = Nl80211Request::get_mesh_path(if_index, destination)?;
+        let recv = self.send(request).await?;
+
+        let mut result: Option<MeshPath> = None;
+        Self::handle_dump_response(recv, |handle| {
+            result = Some(handle.try_into()?);
+            Ok(())
+        })
+        .await?;
+        Ok(result)
+    }
+
+    /// List mesh proxy paths of a mesh point interface.
+    pub async fn list_mesh_proxy_paths(&self, if_index: u32) -> Result<Vec<MeshProxyPath>> {
+        let request = Nl80211Request::list_mesh_proxy_paths(if_index)?;
+        let recv = self.send(request).await?;
+
+        let mut responses = Vec::new();
+        Self::handle_dump_response(recv, |handle| {
+            responses.push(TryInto::<MeshProxyPath>::try_into(handle)?);
+            Ok(())
+        })
+        .await?;
+        Ok(responses)
+    }
+
+    /// Send a directed frame to a mesh peer to verify that the peer link is alive.
+    ///
+    /// `frame` is an Ethernet frame (starting with the destination address) that is
+    /// sent to the peer bypassing the mesh path selection.
+    pub async fn probe_mesh_link(
+        &self,
+        if_index: u32,
+        mac: MacAddress,
+        frame: &[u8],
+    ) -> Result<()> {
+        let request = Nl80211Request::probe_mesh_link(if_index, mac, frame)?;
+        let recv = self.send(request).await?;
+        Self::handle_ack_response(recv).await
+    }
+
+    /// Install an encryption key.
+    pub async fn add_key(&self, if_index: u32, key: Key) -> Result<()> {
+        let request = Nl80211Request::add_key(if_index, key)?;
+        let recv = self.send(request).await?;
+        Self::handle_ack_response(recv).await
+    }
+
+    /// Remove an encryption key.
+    ///
+    /// `mac` is used to select a pairwise key.
+    pub async fn del_key(&self, if_index: u32, index: u8, mac: Option<MacAddress>) -> Result<()> {
+        let request = Nl80211Request::del_key(if_index, index, mac)?;
+        let recv = self.send(request).await?;
+        Self::handle_ack_response(recv).await
+    }
+
+    /// Get information about an encryption key.
+    ///
+    /// `mac` is used to select a pairwise key.
+    pub async fn get_key(
+        &self,
+        if_index: u32,
+        index: u8,
+        mac: Option<MacAddress>,
+    ) -> Result<Option<KeyInfo>> {
+        let request = Nl80211Request::get_key(if_index, index, mac)?;
+        let recv = self.send(request).await?;
+
+        let mut result: Option<KeyInfo> = None;
+        Self::handle_dump_response(recv, |handle| {
+            result = Some(handle.try_into()?);
+            Ok(())
+        })
+        .await?;
+        Ok(result)
+    }
+
+    /// Select the default key.
+    pub async fn set_key(
+        &self,
+        if_index: u32,
+        index: u8,
+        default_type: DefaultKeyType,
+    ) -> Result<()> {
+        let request = Nl80211Request::set_key(if_index, index, default_type)?;
+        let recv = self.send(request).await?;
+        Self::handle_ack_response(recv).await
+    }
+
+    /// Set the antennas used for transmitting and receiving.
+    ///
+    /// Each bit in the masks enables one antenna.
+    pub async fn set_antenna(&self, wiphy_index: u32, tx_mask: u32, rx_mask: u32) -> Result<()> {
+        let request = Nl80211Request::set_antenna(wiphy_index, tx_mask, rx_mask)?;
+        let recv = self.send(request).await?;
+        Self::handle_ack_response(recv).await
+    }
+
+    /// Set SAR (specific absorption rate) power limits.
+    ///
+    /// Supported frequency ranges are listed in `PhysicalDevice::sar_capabilities`.
+    pub async fn set_sar_limits(&self, wiphy_index: u32, limits: Vec<SarLimit>) -> Result<()> {
+        let request = Nl80211Request::set_sar_limits(wiphy_index, limits)?;
+        let recv = self.send(request).await?;
+        Self::handle_ack_response(recv).await
+    }
+
+    /// Set transmit queue parameters. Parameters set to `None` are left unchanged.
+    pub async fn set_txq_params(&self, wiphy_index: u32, params: TxqParams) -> Result<()> {
+        let request = Nl80211Request::set_txq_params(wiphy_index, params)?;
+        let recv = self.send(request).await?;
+        Self::handle_ack_response(recv).await
+    }
+
+    /// Set the airtime weight of a station used by the airtime fairness scheduler.
+    pub async fn set_station_airtime_weight(
+        &self,
+        if_index: u32,
+        mac: MacAddress,
+        weight: u16,
+    ) -> Result<()> {
+        let request = Nl80211Request::set_station_airtime_weight(if_index, mac, weight)?;
+        let recv = self.send(request).await?;
+        Self::handle_ack_response(recv).await
+    }
+
+    /// Restrict the bitrates used for transmitting frames.
+    ///
+    /// Equivalent to `iw dev <devname> set bitrates`.
+    pub async fn set_tx_bitrate_mask(&self, if_index: u32, mask: BitrateMask) -> Result<()> {
+        let request = Nl80211Request::set_tx_bitrate_mask(if_index, mask)?;
+        let recv = self.send(request).await?;
+        Self::handle_ack_response(recv).await
+    }
+
+    /// Enable or disable 4-address (WDS) frames on an interface.
+    pub async fn set_4addr(&self, if_index: u32, enabled: bool) -> Result<()> {
+        let request = Nl80211Request::set_4addr(if_index, enabled)?;
+        let recv = self.send(request).await?;
+        Self::handle_ack_response(recv).await
+    }
+
+    /// Configure connection quality monitor RSSI thresholds in dBm.
+    ///
+    /// An `Event::ConnectionQuality` is sent to the MLME multicast group when the
+    /// RSSI crosses a threshold by more than `hysteresis` dBm. Beacon loss events
+    /// are reported once any threshold is configured. An empty threshold list
+    /// disables RSSI monitoring.
+    pub async fn set_cqm_rssi(
+        &self,
+        if_index: u32,
+        thresholds: Vec<i32>,
+        hysteresis: u32,
+    ) -> Result<()> {
+        let request = Nl80211Request::set_cqm_rssi(if_index, thresholds, hysteresis)?;
+        let recv = self.send(request).await?;
+        Self::handle_ack_response(recv).await
+    }
+
+    /// Configure connection quality monitor TX error reporting.
+    ///
+    /// An `Event::ConnectionQuality` is sent when at least `rate` percent of
+    /// `packets` transmitted packets fail within `interval` seconds.
+    pub async fn set_cqm_txe(
+        &self,
+        if_index: u32,
+        rate: u32,
+        packets: u32,
+        interval: u32,
+    ) -> Result<()> {
+        let request = Nl80211Request::set_cqm_txe(if_index, rate, packets, interval)?;
+        let recv = self.send(request).await?;
+        Self::handle_ack_response(recv).await
+    }
+
+    /// Set the transmit power of an interface.
+    pub async fn set_tx_power(&self, if_index: u32, tx_power: TxPower) -> Result<()> {
+        let request = Nl80211Request::set_tx_power(if_index, tx_power)?;
+        let recv = self.send(request).await?;
+        Self::handle_ack_response(recv).await
+    }
+
+    /// Set retry limits, fragmentation and RTS thresholds and coverage class.
+    pub async fn set_wiphy_params(&self, wiphy_index: u32, params: WiphyParams) -> Result<()> {
+        let request = Nl80211Request::set_wiphy_params(wiphy_index, params)?;
+        let recv = self.send(request).await?;
+        Self::handle_ack_response(recv).await
+    }
+
+    /// Set the QoS map used to map DSCP values to user priorities.
+    pub async fn set_qos_map(&self, if_index: u32, qos_map: QosMap) -> Result<()> {
+        let request = Nl80211Request::set_qos_map(if_index, qos_map)?;
+        let recv = self.send(request).await?;
+        Self::handle_ack_response(recv).await
+    }
+
+    /// Request a regulatory domain change to the given ISO/IEC 3166-1 alpha2
+    /// country code, e.g. "US". Equivalent to `iw reg set`.
+    pub async fn set_regulatory_domain(
+        &self,
+        alpha2: &str,
+        hint: Option<RegulatoryHint>,
+    ) -> Result<()> {
+        let request = Nl80211Request::set_regulatory_domain(alpha2, hint)?;
+        let recv = self.send(request).await?;
+        Self::handle_ack_response(recv).await
+    }
+
+    /// Start DFS radar detection (channel availability check) on a channel.
+    ///
+    /// Waits until the check finishes, is aborted or a radar is detected and
+    /// returns the final radar event. There is no CAC time parameter: the
+    /// kernel derives the CAC time from the DFS CAC time of the channels
+    /// covered by `channel` in the current regulatory domain.
+    ///
+    /// This socket is subscribed to the MLME multicast group to receive the
+    /// result and stays subscribed afterwards. Other events received while
+    /// waiting are kept and returned by later `next_event` calls.
+    pub async fn start_radar_detection(
+        &mut self,
+        if_index: u32,
+        channel: ChannelConfig,
+    ) -> Result<RadarEvent> {
+        channel.validate()?;
+        self.subscribe(MulticastGroup::Mlme).await?;
+        let request = Nl80211Request::start_radar_detection(if_index, channel)?;
+        let recv = self.send(request).await?;
+        Self::handle_ack_response(recv).await?;
+
+        while let Some(event) = self.receive_event().await? {
+            if let Event::Radar {
+                interface_index: Some(interface_index),
+                event:
+                    radar_event @ (RadarEvent::RadarDetected
+                    | RadarEvent::CacFinished
+                    | RadarEvent::CacAborted),
+                ..
+            } = event
+            {
+                if interface_index == if_index {
+                    return Ok(radar_event);
+                }
+            }
+            self.skipped_events.push_back(event);
+        }
+        Err(NlError::new(
+            "Socket closed before radar detection finished",
+        ))
+    }
+
+    /// Get channel survey information of an interface.
+    ///
+    /// If `radio_stats` is set, drivers that support it also report cumulative
+    /// radio statistics as an entry without a frequency.
+    pub async fn get_survey(&self, if_index: u32, radio_stats: bool) -> Result<Vec<SurveyInfo>> {
+        let request = Nl80211Request::get_survey(if_index, radio_stats)?;
+        let recv = self.send(request).await?;
+
+        let mut responses = Vec::new();
+        Self::handle_dump_response(recv, |handle| {
+            responses.push(TryInto::<SurveyInfo>::try_into(handle)?);
+            Ok(())
+        })
+        .await?;
+        Ok(responses)
+    }
+
+    /// Remain on a channel for the given duration, e.g. to exchange action
+    /// frames off-channel.
+    ///
+    /// Returns a cookie identifying the request. `Event::RemainOnChannel` is
+    /// delivered to the MLME multicast group when the device is on the channel.
+    pub async fn remain_on_channel(
+        &self,
+        if_index: u32,
+        frequency: u32,
+        duration: Duration,
+    ) -> Result<u64> {
+        let request = Nl80211Request::remain_on_channel(if_index, frequency, duration)?;
+        let recv = self.send(request).await?;
+
+        let mut cookie = None;
+        Self::handle_dump_response(recv, |handle| {
+            cookie = Some(handle.get_attr_payload_as::<u64>(Attribute::Cookie)?);
+            Ok(())
+        })
+        .await?;
+        cookie.ok_or_else(|| NlError::new("Remain on channel response did not contain a cookie"))
+    }
+
+    /// Remain on a channel for the given duration, e.g. to exchange action
+    /// frames off-channel.
+    ///
+    /// Returns a cookie identifying the request. `Event::RemainOnChannel` is
+    /// delivered to the MLME multicast group when the device is on the channel.
+    pub async fn remain_on_channel_by_wdev(
+        &self,
+        wdev: u64,
+        frequency: u32,
+        duration: Duration,
+    ) -> Result<u64> {
+        let request = Nl80211Request::remain_on_channel_by_wdev(wdev, frequency, duration)?;
+        let recv = self.send(request).await?;
+
+        let mut cookie = None;
+        Self::handle_dump_response(recv, |handle| {
+            cookie = Some(handle.get_attr_payload_as::<u64>(Attribute::Cookie)?);
+            Ok(())
+        })
+        .await?;
+        cookie.ok_or_else(|| NlError::new("Remain on channel response did not contain a cookie"))
+    }
+
+    /// Cancel a remain on channel request identified by its cookie.
+    pub async fn cancel_remain_on_channel(&self, if_index: u32, cookie: u64) -> Result<()> {
+        let request = Nl80211Request::cancel_remain_on_channel(if_index, cookie)?;
+        let recv = self.send(request).await?;
+        Self::handle_ack_response(recv).await
+    }
+
+    /// Cancel a remain on channel request identified by its cookie.
+    pub async fn cancel_remain_on_channel_by_wdev(&self, wdev: u64, cookie: u64) -> Result<()> {
+        let request = Nl80211Request::cancel_remain_on_channel_by_wdev(wdev, cookie)?;
+        let recv = self.send(request).await?;
+        Self::handle_ack_response(recv).await
+    }
+
+    /// Transmit a management frame.
+    ///
+    /// Returns a cookie identifying the transmission unless the frame is sent
+    /// without waiting for an acknowledgement. The result is delivered as
+    /// `Event::FrameTxStatus` to the MLME multicast group.
+    pub async fn send_frame(&self, if_index: u32, frame: FrameTx) -> Result<Option<u64>> {
+        let request = Nl80211Request::send_frame(if_index, frame)?;
+        let recv = self.send(request).await?;
+
+        let mut cookie = None;
+        Self::handle_dump_response(recv, |handle| {
+            cookie = Some(handle.get_attr_payload_as::<u64>(Attribute::Cookie)?);
+            Ok(())
+        })
+        .await?;
+        Ok(cookie)
+    }
+
+    /// Transmit a management frame.
+    ///
+    /// Returns a cookie identifying the transmission unless the frame is sent
+    /// without waiting for an acknowledgement. The result is delivered as
+    /// `Event::FrameTxStatus` to the MLME multicast group.
+    pub async fn send_frame_by_wdev(&self, wdev: u64, frame: FrameTx) -> Result<Option<u64>> {
+        let request = Nl80211Request::send_frame_by_wdev(wdev, frame)?;
+        let recv = self.send(request).await?;
+
+        let mut cookie = None;
+        Self::handle_dump_response(recv, |handle| {
+            cookie = Some(handle.get_attr_payload_as::<u64>(Attribute::Cookie)?);
+            Ok(())
+        })
+        .await?;
+        Ok(cookie)
+    }
+
+    /// Start a P2P device identified by its wireless device identifier.
+    pub async fn start_p2p_device(&self, wdev: u64) -> Result<()> {
+        let request = Nl80211Request::start_p2p_device(wdev)?;
+        let recv = self.send(request).await?;
+        Self::handle_ack_response(recv).await
+    }
+
+    /// Stop a P2P device identified by its wireless device identifier.
+    pub async fn stop_p2p_device(&self, wdev: u64) -> Result<()> {
+        let request = Nl80211Request::stop_p2p_device(wdev)?;
+        let recv = self.send(request).await?;
+        Self::handle_ack_response(recv).await
+    }
+
+    /// Get packet coalescing rules of a device.
+    pub async fn get_coalesce(&self, wiphy_index: u32) -> Result<Vec<CoalesceRule>> {
+        let request = Nl80211Request::get_coalesce(wiphy_index)?;
+        let recv = self.send(request).await?;
+
+        let mut rules = Vec::new();
+        Self::handle_dump_response(recv, |handle| {
+            rules.extend(parse_coalesce_rules(handle)?);
+            Ok(())
+        })
+        .await?;
+        Ok(rules)
+    }
+
+    /// Set packet coalescing rules of a device.
+    ///
+    /// An empty list of rules disables packet coalescing.
+    pub async fn set_coalesce(&self, wiphy_index: u32, rules: Vec<CoalesceRule>) -> Result<()> {
+        let request = Nl80211Request::set_coalesce(wiphy_index, rules)?;
+        let recv = self.send(request).await?;
+        Self::handle_ack_response(recv).await
+    }
+
+    /// Update fast transition (802.11r) IEs of the current connection.
+    ///
+    /// `mdid` is the mobility domain identifier and `ies` contains the FT IEs
+    /// for the driver to use in the next reassociation.
+    pub async fn update_ft_ies(&self, if_index: u32, mdid: u16, ies: Vec<u8>) -> Result<()> {
+        let request = Nl80211Request::update_ft_ies(if_index, mdid, ies)?;
+        let recv = self.send(request).await?;
+        Self::handle_ack_response(recv).await
+    }
+
+    /// Enable or disable dynamic ACK timeout estimation of a device.
+    ///
+    /// Dynamic ACK replaces the static coverage class. Disabling it resets the
+    /// coverage class to 0; set `WiphyParams::coverage_class` afterwards for
+    /// a fixed ACK timeout. Requires `WiphyFeatures::ACKTO_ESTIMATION`.
+    pub async fn set_wiphy_dyn_ack(&self, wiphy_index: u32, enabled: bool) -> Result<()> {
+        let request = Nl80211Request::set_wiphy_dyn_ack(wiphy_index, enabled)?;
+        let recv = self.send(request).await?;
+        Self::handle_ack_response(recv).await
+    }
+
+    async fn send(&self, request: Nl80211Request) -> Result<Pending> {
+        let mut observation = self
+            .observer
+            .as_ref()
+            .map(|observer| Observation::start(observer, request.command()));
+        if cfg!(debug_assertions) && log_enabled!(Level::Debug) && !request.has_key_material() {
+            if let Ok(payload) = request.to_bytes() {
+                let octets = payload.iter().fold(String::new(), |mut output, b| {
+                    let _ = write!(output, "{b:02x} ");
+                    output
+                });
+                debug!("[PAYLOAD] {octets}");
+            }
+        }
+        let command = request.command();
+        let recv = self
+            .socket
             .send(self.nl_type, request.nl_flags, request.nl_payload)
             .await
+            .map_err(|err| NlError::from(err).with_command(command));
+        if let Some(observation) = observation.as_mut() {
+            observation.record(&recv);
+        }
+        Ok(Pending {
+            recv: recv?,
+            command,
+            observation,
+        })
+    }
+
+    async fn handle_consistent_dump<T, F: FnMut(&Attrs<'_, Attribute>) -> Result<T>>(
+        &self,
+        request: impl Fn() -> Result<Nl80211Request>,
+        parse: F,
+    ) -> Result<Vec<T>> {
+        let mut responses = Vec::with_capacity(DUMP_CAPACITY_HINT);
+        self.handle_consistent_dump_into(request, parse, &mut responses)
+            .await?;
+        Ok(responses)
+    }
+
+    /// Collect a dump into `responses`, reusing its allocation. Dumps are
+    /// retried on generation changes when consistent dumps are enabled.
+    /// `responses` is left empty if the dump fails.
+    async fn handle_consistent_dump_into<T, F: FnMut(&Attrs<'_, Attribute>) -> Result<T>>(
+        &self,
+        request: impl Fn() -> Result<Nl80211Request>,
+        mut parse: F,
+        responses: &mut Vec<T>,
+    ) -> Result<()> {
+        let mut retries = 0;
+        let mut generations = HashMap::new();
+        loop {
+            // Cleared before sending so that a failed request leaves no
+            // results of an earlier dump behind.
+            responses.clear();
+            generations.clear();
+            let mut consistent = true;
+            let result = async {
+                let recv = self.send(request()?).await?;
+                Self::handle_dump_response(recv, |handle| {
+                    if let Ok(generation) = handle.get_attr_payload_as::<u32>(Attribute::Generation)
+                    {
+                        // Generation counters are tracked per wiphy by the kernel.
+                        let wiphy = handle.get_attr_payload_as::<u32>(Attribute::Wiphy).ok();
+                        consistent &= *generations.entry(wiphy).or_insert(generation) == generation;
+                    }
+                    responses.push(parse(handle)?);
+                    Ok(())
+                })
+                .await
+            }
+            .await;
+            if let Err(err) = result {
+                responses.clear();
+                return Err(err);
+            }
+
+            match self.dump_retries {
+                Some(max_retries) if !consistent => {
+                    if retries >= max_retries {
+                        responses.clear();
+                        return Err(NlError::with_kind(
+                            ErrorKind::InconsistentDump,
+                            format!("Dump was inconsistent after {retries} retries"),
+                        ));
+                    }
+                    debug!("Dump was interrupted by a generation change, retrying");
+                    retries += 1;
+                }
+                _ => return Ok(()),
+            }
+        }
     }
 
     async fn handle_dump_response<F: FnMut(&Attrs<'_, Attribute>) -> Result<()>>(
-        mut recv: NlRouterReceiverHandle<Nlmsg, Neli80211Header>,
+        mut recv: Pending,
         mut f: F,
     ) -> Result<()> {
-        while let Some(Ok(response)) = recv.next::<Nlmsg, Neli80211Header>().await {
-            match response.nl_payload() {
-                NlPayload::Err(err) => {
-                    debug!("Error when reading dump response: {err}");
-                    return Err(err.clone().into());
-                }
-                NlPayload::Payload(payload) => {
-                    let handle = payload.attrs().get_attr_handle();
-                    f(&handle)?
-                }
-                NlPayload::Empty | NlPayload::Ack(_) => (),
-            };
+        while let Some(payload) = recv.next().await {
+            let payload = payload?;
+            let handle = payload.attrs().get_attr_handle();
+            f(&handle)?
         }
         Ok(())
     }
 
-    async fn handle_ack_response(
-        mut recv: NlRouterReceiverHandle<Nlmsg, Neli80211Header>,
-    ) -> Result<()> {
-        while let Some(response) = recv.next::<Nlmsg, Neli80211Header>().await {
-            let response = response?;
-            match response.nl_payload() {
-                NlPayload::Err(err) => {
-                    debug!("Error when reading ack response: {err}");
-                    return Err(err.clone().into());
-                }
-                NlPayload::Payload(_) | NlPayload::Empty | NlPayload::Ack(_) => (),
-            };
+    async fn handle_ack_response(mut recv: Pending) -> Result<()> {
+        while let Some(response) = recv.next().await {
+            response?;
         }
         Ok(())
     }
 }
+
+/// Responses of a sent request.
+struct Pending {
+    recv: NlRouterReceiverHandle<Nlmsg, Neli80211Header>,
+    command: Command,
+    observation: Option<Observation>,
+}
+
+impl Pending {
+    /// Receive the payload of the next response message. Error responses are
+    /// returned as errors, acknowledgements are skipped.
+    async fn next(&mut self) -> Option<Result<Response>> {
+        while let Some(response) = self.recv.next::<Nlmsg, Neli80211Header>().await {
+            let payload = match response {
+                Ok(message) => match Response::from_message(message, self.command) {
+                    Some(payload) => payload,
+                    None => continue,
+                },
+                Err(err) => Err(NlError::from(err).with_command(self.command)),
+            };
+            if let Some(observation) = self.observation.as_mut() {
+                observation.record(&payload);
+            }
+            return Some(payload);
+        }
+        None
+    }
+}
@@ -11,6 +11,33 @@ use neli_proc_macros::neli_enum;
 
 pub type Attrs<'a, T> = AttrHandle<'a, GenlBuffer<T, Buffer>, Nlattr<T, Buffer>>;
 
+const NLA_HEADER_LEN: usize = 4;
+const NLA_TYPE_MASK: u16 = 0x3fff;
+
+/// Iterate over the type and payload of the attributes nested in `payload`
+/// without copying them. Iteration stops at the first truncated attribute.
+pub(crate) fn nested_attributes(payload: &[u8]) -> impl Iterator<Item = (u16, &[u8])> {
+    let mut rest = payload;
+    std::iter::from_fn(move || {
+        let len = u16::from_ne_bytes(rest.get(0..2)?.try_into().ok()?) as usize;
+        let nla_type = u16::from_ne_bytes(rest.get(2..4)?.try_into().ok()?) & NLA_TYPE_MASK;
+        let attr_payload = rest.get(NLA_HEADER_LEN..len)?;
+        rest = rest.get((len + 3) & !3..).unwrap_or_default();
+        Some((nla_type, attr_payload))
+    })
+}
+
+/// Append a netlink attribute with the given type and payload to `buffer`,
+/// including the header and padding.
+#[cfg(any(test, feature = "sync"))]
+pub(crate) fn encode_attribute(buffer: &mut Vec<u8>, attr_type: u16, payload: &[u8]) {
+    let len = NLA_HEADER_LEN + payload.len();
+    buffer.extend((len as u16).to_ne_bytes());
+    buffer.extend(attr_type.to_ne_bytes());
+    buffer.extend(payload);
+    buffer.resize(buffer.len() + len.next_multiple_of(4) - len, 0);
+}
+
 /// Nl80211 netlink attributes.
 ///
 /// nl80211_attrs enum from:
@@ -644,6 +671,57 @@ pub(crate) enum BssParam {
 
 impl NlAttrType for BssParam {}
 
+/// Nl80211 BSS information of a scan result.
+///
+/// These attribute types are used with `Attribute.Bss`.
+///
+/// nl80211_bss enum from:
+/// https://github.com/torvalds/linux/blob/master/include/uapi/linux/nl80211.h
+#[neli_enum(serialized_type = "u16")]
+pub(crate) enum BssAttr {
+    /// Attribute number 0 is reserved.
+    Invalid = 0,
+    /// BSSID of the BSS (6 octets).
+    Bssid = 1,
+    /// Frequency in MHz (u32).
+    Frequency = 2,
+    /// TSF of the received probe response/beacon (u64).
+    Tsf = 3,
+    /// Beacon interval of the (I)BSS (u16).
+    BeaconInterval = 4,
+    /// Capability field (CPU order, u16).
+    Capability = 5,
+    /// Binary attribute containing the raw information elements.
+    InformationElements = 6,
+    /// Signal strength of probe response/beacon in mBm (s32).
+    SignalMbm = 7,
+    /// Signal strength of the probe response/beacon in unspecified units,
+    /// scaled to 0..100 (u8).
+    SignalUnspec = 8,
+    /// Status, if this BSS is "used" (u32, see `BssStatus`).
+    Status = 9,
+    /// Age of this BSS entry in ms (u32).
+    SeenMsAgo = 10,
+}
+
+impl NlAttrType for BssAttr {}
+
+/// Nl80211 BSS status.
+///
+/// These values are used with `BssAttr.Status`.
+///
+/// nl80211_bss_status enum from:
+/// https://github.com/torvalds/linux/blob/master/include/uapi/linux/nl80211.h
+#[neli_enum(serialized_type = "u32")]
+pub(crate) enum BssStatus {
+    /// Authenticated with this BSS.
+    Authenticated = 0,
+    /// Associated with this BSS.
+    Associated = 1,
+    /// Joined to this IBSS.
+    IbssJoined = 2,
+}
+
 /// Nl80211 HE guard interval.
 ///
 /// These attribute types are used with `RateInfo.HeGuardInterval`
@@ -959,3 +1037,611 @@ pub(crate) enum ChannelWidth {
     Width16 = 12,
     Width320 = 13,
 }
+
+/// Values for `Attribute.HiddenSsid`.
+///
+/// nl80211_hidden_ssid enum from:
+/// https://github.com/torvalds/linux/blob/master/include/uapi/linux/nl80211.h
+#[neli_enum(serialized_type = "u32")]
+pub(crate) enum HiddenSsid {
+    /// Do not hide SSID (i.e., broadcast it in Beacon frames).
+    NotInUse = 0,
+    /// Hide SSID by using zero-length SSID element in Beacon frames.
+    ZeroLen = 1,
+    /// Hide SSID by using correct length of SSID element in Beacon frames but
+    /// with the actual SSID contents replaced with zeros.
+    ZeroContents = 2,
+}
+
+bitflags! {
+    /// Station flags.
+    ///
+    /// Bit positions from nl80211_sta_flags enum, used in the mask and set
+    /// fields of struct nl80211_sta_flag_update:
+    /// https://github.com/torvalds/linux/blob/master/include/uapi/linux/nl80211.h
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub struct StationFlags: u32 {
+        const AUTHORIZED     = 1<<1;
+        const SHORT_PREAMBLE = 1<<2;
+        const WME            = 1<<3;
+        const MFP            = 1<<4;
+        const AUTHENTICATED  = 1<<5;
+        const TDLS_PEER      = 1<<6;
+        const ASSOCIATED     = 1<<7;
+    }
+}
+
+impl fmt::Debug for StationFlags {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+/// Access control policy.
+///
+/// These values are used with the `Attribute.AclPolicy` attribute.
+///
+/// nl80211_acl_policy enum from:
+/// https://github.com/torvalds/linux/blob/master/include/uapi/linux/nl80211.h
+#[neli_enum(serialized_type = "u32")]
+pub(crate) enum AclPolicy {
+    /// Deny stations which are listed in ACL, i.e. allow all the stations
+    /// which are not listed in ACL to authenticate.
+    AcceptUnlessListed = 0,
+    /// Allow the stations which are listed in ACL, i.e. deny all the stations
+    /// which are not listed in ACL.
+    DenyUnlessListed = 1,
+}
+
+/// Mesh configuration parameters.
+///
+/// These attribute types are used with `Attribute.MeshConfig`.
+///
+/// nl80211_meshconf_params enum from:
+/// https://github.com/torvalds/linux/blob/master/include/uapi/linux/nl80211.h
+#[neli_enum(serialized_type = "u16")]
+pub(crate) enum MeshConfigAttr {
+    /// Attribute number 0 is reserved.
+    Invalid = 0,
+    /// Specifies the initial retry timeout in millisecond units, used by the
+    /// Peer Link Open message (u16).
+    RetryTimeout = 1,
+    /// Specifies the initial confirm timeout, in millisecond units, used by
+    /// the peer link management to close a peer link (u16).
+    ConfirmTimeout = 2,
+    /// Specifies the holding timeout, in millisecond units, used by the peer
+    /// link management to close a peer link (u16).
+    HoldingTimeout = 3,
+    /// Maximum number of peer links allowed on this mesh interface (u16).
+    MaxPeerLinks = 4,
+    /// Specifies the maximum number of peer link open retries that can be
+    /// sent to establish a new peer link instance in a mesh (u8).
+    MaxRetries = 5,
+    /// Specifies the value of TTL field set at a source mesh point (u8).
+    Ttl = 6,
+    /// Whether we should automatically open peer links when we detect
+    /// compatible mesh peers (u8, 0 or 1).
+    AutoOpenPlinks = 7,
+    /// The number of action frames containing a PREQ that an MP can send to
+    /// a particular destination (path target) (u8).
+    HwmpMaxPreqRetries = 8,
+    /// How frequently to refresh mesh paths (in milliseconds) (u32).
+    PathRefreshTime = 9,
+    /// The minimum length of time to wait until giving up on a path
+    /// discovery (in milliseconds) (u16).
+    MinDiscoveryTimeout = 10,
+    /// The time (in TUs) for which mesh points receiving a PREQ shall
+    /// consider the forwarding information from the root to be valid (u32).
+    HwmpActivePathTimeout = 11,
+    /// The minimum interval of time (in TUs) that a mesh point can wait
+    /// between sending two consecutive PREQs (u16).
+    HwmpPreqMinInterval = 12,
+    /// The interval of time (in TUs) that it takes for an HWMP information
+    /// element to propagate across the mesh (u16).
+    HwmpNetDiamTrvsTime = 13,
+    /// Whether root mode is enabled or not (u8).
+    HwmpRootmode = 14,
+    /// Specifies the value of TTL field set at a mesh point for path
+    /// selection elements (u8).
+    ElementTtl = 15,
+    /// The interval of time (in TUs) between root announcements are
+    /// transmitted (u16).
+    HwmpRannInterval = 16,
+    /// Advertise that this mesh station has access to a broader network
+    /// beyond the MBSS (u8, 0 or 1).
+    GateAnnouncements = 17,
+    /// The minimum interval of time (in TUs) that a mesh point can wait
+    /// between sending two consecutive PERRs (u16).
+    HwmpPerrMinInterval = 18,
+    /// Set Mesh STA as forwarding or non-forwarding (u8, 0 or 1).
+    Forwarding = 19,
+    /// RSSI threshold in dBm. This specifies the threshold for average
+    /// signal strength of candidate station to establish a peer link (s32).
+    RssiThreshold = 20,
+    /// Maximum number of neighbors to synchronize to (u32).
+    SyncOffsetMaxNeighbor = 21,
+    /// Set mesh HT protection mode (u16).
+    HtOpmode = 22,
+    /// The time (in TUs) for which mesh points receiving a proactive PREQ
+    /// shall consider the forwarding information to the root mesh STA to be
+    /// valid (u32).
+    HwmpPathToRootTimeout = 23,
+    /// The interval of time (in TUs) between proactive PREQs are
+    /// transmitted (u16).
+    HwmpRootInterval = 24,
+    /// The minimum interval of time (in TUs) during which a mesh STA can
+    /// send only one Action frame containing a PREQ element for root path
+    /// confirmation (u16).
+    HwmpConfirmationInterval = 25,
+    /// Default mesh power mode for new peer links (u32, see enum
+    /// `MeshPowerMode`).
+    PowerMode = 26,
+    /// Awake window duration (in TUs) (u16).
+    AwakeWindow = 27,
+    /// If no tx activity is seen from a STA we've established peering with
+    /// for longer than this time (in seconds), then remove it from the
+    /// STA's list of peers (u32).
+    PlinkTimeout = 28,
+    /// If set to true then this mesh STA will advertise in the mesh
+    /// configuration element that it is connected to a mesh gate (u8, 0 or 1).
+    ConnectedToGate = 29,
+    /// Try to avoid multi-hop path discovery if the destination is a direct
+    /// neighbor (u8, 0 or 1).
+    Nolearn = 30,
+    /// If set to true then this mesh STA will advertise in the mesh
+    /// configuration element that it is connected to an authentication
+    /// server (u8, 0 or 1).
+    ConnectedToAs = 31,
+}
+
+impl NlAttrType for MeshConfigAttr {}
+
+/// Mesh power save modes.
+///
+/// nl80211_mesh_power_mode enum from:
+/// https://github.com/torvalds/linux/blob/master/include/uapi/linux/nl80211.h
+#[neli_enum(serialized_type = "u32")]
+pub(crate) enum MeshPowerMode {
+    /// The mesh power mode of a mesh STA is not known or not set.
+    Unknown = 0,
+    /// Active mesh power mode. The mesh STA is always awake.
+    Active = 1,
+    /// Light sleep mode. The mesh STA will alternate between Active and Doze
+    /// states, but will wake up for neighbor's beacons.
+    LightSleep = 2,
+    /// Deep sleep mode. The mesh STA will alternate between Active and Doze
+    /// states, but may not wake up for neighbor's beacons.
+    DeepSleep = 3,
+}
+
+/// Mesh path information.
+///
+/// These attribute types are used with `Attribute.MpathInfo` when getting
+/// information about a mesh path.
+///
+/// nl80211_mpath_info enum from:
+/// https://github.com/torvalds/linux/blob/master/include/uapi/linux/nl80211.h
+#[neli_enum(serialized_type = "u16")]
+pub(crate) enum MpathInfo {
+    /// Attribute number 0 is reserved.
+    Invalid = 0,
+    /// Number of queued frames for this destination (u32).
+    FrameQlen = 1,
+    /// Destination sequence number (u32).
+    Sn = 2,
+    /// Metric (cost) of this mesh path (u32).
+    Metric = 3,
+    /// Expiration time for the path, in msec from now (u32).
+    Exptime = 4,
+    /// Mesh path flags, enumerated in `MpathFlags` (u8).
+    Flags = 5,
+    /// Total path discovery timeout, in msec (u32).
+    DiscoveryTimeout = 6,
+    /// Mesh path discovery retries (u8).
+    DiscoveryRetries = 7,
+    /// Hop count to destination (u8).
+    HopCount = 8,
+    /// Total number of path changes to destination (u32).
+    PathChange = 9,
+}
+
+impl NlAttrType for MpathInfo {}
+
+bitflags! {
+    /// Mesh path flags.
+    ///
+    /// nl80211_mpath_flags enum from:
+    /// https://github.com/torvalds/linux/blob/master/include/uapi/linux/nl80211.h
+    pub struct MpathFlags: u8 {
+        const ACTIVE    = 1<<0;
+        const RESOLVING = 1<<1;
+        const SN_VALID  = 1<<2;
+        const FIXED     = 1<<3;
+        const RESOLVED  = 1<<4;
+    }
+}
+
+impl fmt::Debug for MpathFlags {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+/// State of a mesh peer link finite state machine.
+///
+/// These values are used with `StationInfo.PlinkState`.
+///
+/// nl80211_plink_state enum from:
+/// https://github.com/torvalds/linux/blob/master/include/uapi/linux/nl80211.h
+#[neli_enum(serialized_type = "u8")]
+pub(crate) enum PlinkState {
+    /// Initial state, considered the implicit state of non existent mesh peer links.
+    Listen = 0,
+    /// Mesh plink open frame has been sent to this mesh peer.
+    OpnSnt = 1,
+    /// Mesh plink open frame has been received from this mesh peer.
+    OpnRcvd = 2,
+    /// Mesh plink confirm frame has been received from this mesh peer.
+    CnfRcvd = 3,
+    /// Mesh peer link is established.
+    Estab = 4,
+    /// Mesh peer link is being closed or cancelled.
+    Holding = 5,
+    /// All frames transmitted from this mesh plink are discarded.
+    Blocked = 6,
+}
+
+/// Key type.
+///
+/// These values are used with the `Attribute.KeyType` attribute.
+///
+/// nl80211_key_type enum from:
+/// https://github.com/torvalds/linux/blob/master/include/uapi/linux/nl80211.h
+#[neli_enum(serialized_type = "u32")]
+pub(crate) enum KeyType {
+    /// Group (broadcast/multicast) key.
+    Group = 0,
+    /// Pairwise (unicast/individual) key.
+    Pairwise = 1,
+    /// Peer key (DLS).
+    Peerkey = 2,
+}
+
+/// Interface type AKM suite attributes.
+///
+/// These attribute types are used with the nested entries of
+/// `Attribute.IfTypeAkmSuites`.
+///
+/// nl80211_iftype_akm_attributes enum from:
+/// https://github.com/torvalds/linux/blob/master/include/uapi/linux/nl80211.h
+#[neli_enum(serialized_type = "u16")]
+pub(crate) enum IftypeAkmAttr {
+    /// Attribute number 0 is reserved.
+    Invalid = 0,
+    /// Nested flag attribute listing the interface types (`InterfaceType`)
+    /// the AKM suites apply to.
+    Iftypes = 1,
+    /// Array of AKM suite selectors (u32) supported by the interface types.
+    Suites = 2,
+}
+
+impl NlAttrType for IftypeAkmAttr {}
+
+/// DFS states for channels.
+///
+/// These values are used with `FrequencyAttr.DfsState`.
+///
+/// nl80211_dfs_state enum from:
+/// https://github.com/torvalds/linux/blob/master/include/uapi/linux/nl80211.h
+#[neli_enum(serialized_type = "u32")]
+pub(crate) enum DfsState {
+    /// The channel can be used, but channel availability check (CAC) must be
+    /// performed before using it for AP or IBSS.
+    Usable = 0,
+    /// A radar has been detected on this channel, it is therefore marked as
+    /// not available.
+    Unavailable = 1,
+    /// The channel has been CAC checked and is available.
+    Available = 2,
+}
+
+/// SAR attributes.
+///
+/// These attribute types are used with `Attribute.SarSpec`.
+///
+/// nl80211_sar_attrs enum from:
+/// https://github.com/torvalds/linux/blob/master/include/uapi/linux/nl80211.h
+#[neli_enum(serialized_type = "u16")]
+pub(crate) enum SarAttr {
+    /// Attribute number 0 is reserved.
+    Invalid = 0,
+    /// Type of the SAR specs (u32, see enum `SarType`).
+    Type = 1,
+    /// Nested array of SAR specs, each using attributes from `SarSpecsAttr`.
+    Specs = 2,
+}
+
+impl NlAttrType for SarAttr {}
+
+/// SAR specs attributes.
+///
+/// nl80211_sar_specs_attrs enum from:
+/// https://github.com/torvalds/linux/blob/master/include/uapi/linux/nl80211.h
+#[neli_enum(serialized_type = "u16")]
+pub(crate) enum SarSpecsAttr {
+    /// Attribute number 0 is reserved.
+    Invalid = 0,
+    /// Power limit (s32) in units of 0.25 dBm.
+    Power = 1,
+    /// Index of the frequency range the power limit applies to (u32).
+    RangeIndex = 2,
+    /// Start frequency of the range in kHz (u32).
+    StartFreq = 3,
+    /// End frequency of the range in kHz (u32).
+    EndFreq = 4,
+}
+
+impl NlAttrType for SarSpecsAttr {}
+
+/// SAR types.
+///
+/// nl80211_sar_type enum from:
+/// https://github.com/torvalds/linux/blob/master/include/uapi/linux/nl80211.h
+#[neli_enum(serialized_type = "u32")]
+pub(crate) enum SarType {
+    /// Power limitation specified in 0.25 dBm units.
+    Power = 0,
+}
+
+/// TX power adjustment settings.
+///
+/// These values are used with `Attribute.WiphyTxPowerSetting` and
+/// `Attribute.StaTxPowerSetting`.
+///
+/// nl80211_tx_power_setting enum from:
+/// https://github.com/torvalds/linux/blob/master/include/uapi/linux/nl80211.h
+#[neli_enum(serialized_type = "u32")]
+pub(crate) enum TxPowerSetting {
+    /// Automatically determine transmit power.
+    Automatic = 0,
+    /// Limit TX power by the given power level.
+    Limited = 1,
+    /// Fix TX power to the given power level.
+    Fixed = 2,
+}
+
+/// TX rate mask attributes.
+///
+/// These attribute types are used with the per-band entries of
+/// `Attribute.TxRates`.
+///
+/// nl80211_tx_rate_attributes enum from:
+/// https://github.com/torvalds/linux/blob/master/include/uapi/linux/nl80211.h
+#[neli_enum(serialized_type = "u16")]
+pub(crate) enum TxRateAttr {
+    /// Attribute number 0 is reserved.
+    Invalid = 0,
+    /// Legacy (non-MCS) rates allowed for TX rate selection in an array of
+    /// rates as defined in IEEE 802.11 7.3.2.2 (u8 values with 1 = 500 kbps).
+    Legacy = 1,
+    /// HT (MCS) rates allowed for TX rate selection in an array of MCS numbers.
+    Ht = 2,
+    /// VHT rates allowed for TX rate selection (struct nl80211_txrate_vht).
+    Vht = 3,
+    /// Configure GI (enum `TxRateGi`).
+    Gi = 4,
+    /// HE rates allowed for TX rate selection (struct nl80211_txrate_he).
+    He = 5,
+    /// Configure HE GI (enum `HeGuardInterval`).
+    HeGi = 6,
+    /// Configure HE LTF.
+    HeLtf = 7,
+}
+
+impl NlAttrType for TxRateAttr {}
+
+/// TX rate guard interval settings.
+///
+/// nl80211_txrate_gi enum from:
+/// https://github.com/torvalds/linux/blob/master/include/uapi/linux/nl80211.h
+#[neli_enum(serialized_type = "u8")]
+pub(crate) enum TxRateGi {
+    /// Use the default guard interval.
+    Default = 0,
+    /// Force short guard interval.
+    ForceSgi = 1,
+    /// Force long guard interval.
+    ForceLgi = 2,
+}
+
+/// Connection quality monitor attributes.
+///
+/// These attribute types are used with `Attribute.Cqm`.
+///
+/// nl80211_attr_cqm enum from:
+/// https://github.com/torvalds/linux/blob/master/include/uapi/linux/nl80211.h
+#[neli_enum(serialized_type = "u16")]
+pub(crate) enum CqmAttr {
+    /// Attribute number 0 is reserved.
+    Invalid = 0,
+    /// RSSI threshold in dBm (s32). Can be an array of thresholds.
+    RssiThold = 1,
+    /// RSSI hysteresis in dBm (u32).
+    RssiHyst = 2,
+    /// RSSI threshold event (u32, see enum `CqmRssiThresholdEvent`).
+    RssiThresholdEvent = 3,
+    /// Number of lost packets to a station (u32).
+    PktLossEvent = 4,
+    /// TX error rate in percent (u32).
+    TxeRate = 5,
+    /// Number of packets to observe before reporting TX errors (u32).
+    TxePkts = 6,
+    /// Interval in seconds for reporting TX errors (u32).
+    TxeIntvl = 7,
+    /// Beacon loss detected (flag).
+    BeaconLossEvent = 8,
+    /// RSSI level that triggered the event in dBm (s32).
+    RssiLevel = 9,
+}
+
+impl NlAttrType for CqmAttr {}
+
+/// RSSI threshold events.
+///
+/// nl80211_cqm_rssi_threshold_event enum from:
+/// https://github.com/torvalds/linux/blob/master/include/uapi/linux/nl80211.h
+#[neli_enum(serialized_type = "u32")]
+pub(crate) enum CqmRssiThresholdEvent {
+    /// The RSSI level is lower than the configured threshold.
+    Low = 0,
+    /// The RSSI level is higher than the configured threshold.
+    High = 1,
+    /// Beacon loss (deprecated, use `CqmAttr.BeaconLossEvent`).
+    BeaconLoss = 2,
+}
+
+/// Type of regulatory hint passed from userspace.
+///
+/// These values are used with `Attribute.UserRegHintType`.
+///
+/// nl80211_user_reg_hint_type enum from:
+/// https://github.com/torvalds/linux/blob/master/include/uapi/linux/nl80211.h
+#[neli_enum(serialized_type = "u32")]
+pub(crate) enum UserRegHintType {
+    /// A user sent the hint.
+    User = 0,
+    /// The hint comes from a cellular base station.
+    CellBase = 1,
+    /// The hint indicates that the device is operating in an indoor
+    /// environment.
+    Indoor = 2,
+}
+
+/// Type of radar event for DFS operation.
+///
+/// These values are used with `Attribute.RadarEvent`.
+///
+/// nl80211_radar_event enum from:
+/// https://github.com/torvalds/linux/blob/master/include/uapi/linux/nl80211.h
+#[neli_enum(serialized_type = "u32")]
+pub(crate) enum RadarEvent {
+    /// A radar pattern has been detected.
+    Detected = 0,
+    /// Channel Availability Check has been finished.
+    CacFinished = 1,
+    /// Channel Availability Check has been aborted.
+    CacAborted = 2,
+    /// The Non-Occupancy Period for this channel is over.
+    NopFinished = 3,
+    /// The channel availability check done on a non-operating channel has
+    /// expired.
+    PreCacExpired = 4,
+    /// Channel Availability Check has been started.
+    CacStarted = 5,
+}
+
+/// Regulatory WMM rule attributes.
+///
+/// These attribute types are nested per access category in
+/// `FrequencyAttr.Wmm`.
+///
+/// nl80211_wmm_rule enum from:
+/// https://github.com/torvalds/linux/blob/master/include/uapi/linux/nl80211.h
+#[neli_enum(serialized_type = "u16")]
+pub(crate) enum WmmRuleAttr {
+    Invalid = 0,
+    /// Minimum contention window slot.
+    CwMin = 1,
+    /// Maximum contention window slot.
+    CwMax = 2,
+    /// Arbitration inter frame space.
+    Aifsn = 3,
+    /// Maximum allowed TX operation time.
+    Txop = 4,
+}
+impl NlAttrType for WmmRuleAttr {}
+
+/// Survey information attributes.
+///
+/// These attribute types are used with `Attribute.SurveyInfo`.
+///
+/// nl80211_survey_info enum from:
+/// https://github.com/torvalds/linux/blob/master/include/uapi/linux/nl80211.h
+#[neli_enum(serialized_type = "u16")]
+pub(crate) enum SurveyInfoAttr {
+    Invalid = 0,
+    /// Center frequency of channel.
+    Frequency = 1,
+    /// Noise level of channel (u8, dBm).
+    Noise = 2,
+    /// Channel is currently being used.
+    InUse = 3,
+    /// Amount of time (in ms) that the radio was turned on.
+    Time = 4,
+    /// Amount of time the primary channel was sensed busy.
+    TimeBusy = 5,
+    /// Amount of time the extension channel was sensed busy.
+    TimeExtBusy = 6,
+    /// Amount of time the radio spent receiving data.
+    TimeRx = 7,
+    /// Amount of time the radio spent transmitting data.
+    TimeTx = 8,
+    /// Time the radio spent for scan.
+    TimeScan = 9,
+    /// Attribute used for padding for 64-bit alignment.
+    Pad = 10,
+    /// Amount of time the radio spent receiving data on a local BSS.
+    TimeBssRx = 11,
+    /// Center frequency offset in kHz.
+    FrequencyOffset = 12,
+}
+impl NlAttrType for SurveyInfoAttr {}
+
+/// Coalesce rule attributes.
+///
+/// These attribute types are nested per rule in `Attribute.CoalesceRule`.
+///
+/// nl80211_attr_coalesce_rule enum from:
+/// https://github.com/torvalds/linux/blob/master/include/uapi/linux/nl80211.h
+#[neli_enum(serialized_type = "u16")]
+pub(crate) enum CoalesceRuleAttr {
+    Invalid = 0,
+    /// Delay in msecs used for packet coalescing.
+    Delay = 1,
+    /// Condition for packet coalescence, see `CoalesceCondition`.
+    Condition = 2,
+    /// Packet offset, pattern is matched after these many bytes of received
+    /// packet (nested attribute, see enum `PacketPatternAttr`).
+    PktPattern = 3,
+}
+impl NlAttrType for CoalesceRuleAttr {}
+
+/// Coalesce rule conditions.
+///
+/// nl80211_coalesce_condition enum from:
+/// https://github.com/torvalds/linux/blob/master/include/uapi/linux/nl80211.h
+#[neli_enum(serialized_type = "u32")]
+pub(crate) enum CoalesceCondition {
+    /// Coalesce the packet if the pattern is matched.
+    Match = 0,
+    /// Coalesce the packet if the pattern is not matched.
+    NoMatch = 1,
+}
+
+/// Packet pattern attributes.
+///
+/// nl80211_packet_pattern_attr enum from:
+/// https://github.com/torvalds/linux/blob/master/include/uapi/linux/nl80211.h
+#[neli_enum(serialized_type = "u16")]
+pub(crate) enum PacketPatternAttr {
+    Invalid = 0,
+    /// Pattern mask, must be long enough to have a bit for every octet in the
+    /// pattern.
+    Mask = 1,
+    /// The pattern.
+    Pattern = 2,
+    /// Packet offset, pattern is matched after these many bytes of received
+    /// packet.
+    Offset = 3,
+}
+impl NlAttrType for PacketPatternAttr {}
@@ -0,0 +1,77 @@
+//! Pipelined nl80211 requests.
+//!
+//! ```rust,no_run
+//! use netlink_wi::{Nl80211Request, NlSocket};
+//!
+//! let socket = NlSocket::connect().unwrap();
+//! let mut batch = socket.batch();
+//! for if_index in [3, 4] {
+//!     batch
+//!         .push(Nl80211Request::get_interface(if_index).unwrap())
+//!         .push(Nl80211Request::list_stations(if_index).unwrap());
+//! }
+//! for responses in batch.send() {
+//!     println!("{:#?}", responses);
+//! }
+//! ```
+use crate::decode::{parse_payload, ParsedNl80211};
+use crate::error::Result;
+use crate::netlink::Nl80211Request;
+use crate::synchronous::NlSocket;
+
+/// Requests that are sent back-to-back before any of their responses are
+/// received, created with [`NlSocket::batch`].
+///
+/// Responses of each request are routed to it by sequence number, so they can
+/// be received in request order without waiting a round trip per request.
+pub struct Batch<'a> {
+    socket: &'a NlSocket,
+    requests: Vec<Nl80211Request>,
+}
+
+impl<'a> Batch<'a> {
+    pub(crate) fn new(socket: &'a NlSocket) -> Self {
+        Self {
+            socket,
+            requests: Vec::new(),
+        }
+    }
+
+    /// Queue a request.
+    pub fn push(&mut self, request: Nl80211Request) -> &mut Self {
+        self.requests.push(request);
+        self
+    }
+
+    /// Number of queued requests.
+    pub fn len(&self) -> usize {
+        self.requests.len()
+    }
+
+    /// Returns true if no requests are queued.
+    pub fn is_empty(&self) -> bool {
+        self.requests.is_empty()
+    }
+
+    /// Send all queued requests, then receive and decode their responses.
+    ///
+    /// Results are returned in the order the requests were queued. A request
+    /// that is only acknowledged has no responses. A failed request does not
+    /// affect the other requests.
+    pub fn send(self) -> Vec<Result<Vec<ParsedNl80211>>> {
+        let pending: Vec<_> = self
+            .requests
+            .into_iter()
+            .map(|request| self.socket.send(request))
+            .collect();
+
+        pending
+            .into_iter()
+            .map(|responses| -> Result<Vec<ParsedNl80211>> {
+                responses?
+                    .map(|payload| parse_payload(&*payload?))
+                    .collect()
+            })
+            .collect()
+    }
+}
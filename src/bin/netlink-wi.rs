@@ -0,0 +1,280 @@
+//! Command line tool for showing and configuring wireless devices, with
+//! commands and output modeled after `iw`.
+use std::env;
+use std::error::Error;
+use std::process::ExitCode;
+
+use netlink_wi::channels::{center_frequency, freq_to_channel};
+use netlink_wi::interface::{ChannelConfig, ChannelWidth, WirelessInterface};
+use netlink_wi::reg_domain::RegulatoryHint;
+use netlink_wi::station::RateInfo;
+use netlink_wi::units::{Dbm, Mhz};
+use netlink_wi::wiphy::WifiBand;
+use netlink_wi::NlSocket;
+
+const USAGE: &str = "\
+Usage: netlink-wi <command>
+
+Commands:
+    dev                                      List wireless interfaces
+    dev <devname> station dump               List stations of an interface
+    dev <devname> scan                       Trigger a scan
+    dev <devname> set channel <freq> [width [center1 [center2]]]
+                                             Set channel by frequency in MHz,
+                                             width is one of noht, 20, 40, 80,
+                                             80+80, 160 or 320. Center
+                                             frequencies are derived for 5 and
+                                             6 GHz channels if not given, 80+80
+                                             requires both
+    phy                                      List physical devices
+    reg get                                  Show regulatory domains
+    reg set <alpha2>                         Set regulatory domain";
+
+type CliResult = Result<(), Box<dyn Error>>;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: &[&str]) -> CliResult {
+    if matches!(args, [] | ["help" | "-h" | "--help"]) {
+        println!("{USAGE}");
+        return Ok(());
+    }
+
+    let socket = NlSocket::connect()?;
+    match args {
+        ["dev"] => list_interfaces(&socket),
+        ["dev", name, "station", "dump"] => station_dump(&socket, name),
+        ["dev", name, "scan"] => {
+            let interface = interface_by_name(&socket, name)?;
+            socket.trigger_scan(interface.interface_index)?;
+            println!("Scan triggered on {name}");
+            Ok(())
+        }
+        ["dev", name, "set", "channel", freq, args @ ..] => {
+            let interface = interface_by_name(&socket, name)?;
+            let channel = parse_channel(freq, args)?;
+            socket.set_channel_config(interface.interface_index, channel)?;
+            Ok(())
+        }
+        ["phy"] => list_physical_devices(&socket),
+        ["reg", "get"] => regulatory_domain(&socket),
+        ["reg", "set", alpha2] => {
+            socket.set_regulatory_domain(alpha2, Some(RegulatoryHint::User))?;
+            Ok(())
+        }
+        _ => Err(USAGE.into()),
+    }
+}
+
+fn interface_by_name(socket: &NlSocket, name: &str) -> Result<WirelessInterface, Box<dyn Error>> {
+    socket
+        .get_interface_by_name(name)?
+        .ok_or_else(|| format!("No such device: {name}").into())
+}
+
+fn parse_channel(freq: &str, args: &[&str]) -> Result<ChannelConfig, Box<dyn Error>> {
+    let frequency = Mhz(freq.parse()?);
+    let (width, centers) = match args {
+        [] => (ChannelWidth::Width20NoHT, args),
+        [width, centers @ ..] if centers.len() <= 2 => (parse_width(width)?, centers),
+        _ => return Err(USAGE.into()),
+    };
+    let mut channel = ChannelConfig::new(frequency, width);
+    channel.center_frequency1 = match centers.first() {
+        Some(center) => Some(Mhz(center.parse()?)),
+        None => center_frequency(frequency.0, width).map(Mhz),
+    };
+    channel.center_frequency2 = centers
+        .get(1)
+        .map(|center| center.parse().map(Mhz))
+        .transpose()?;
+    channel.validate()?;
+    Ok(channel)
+}
+
+fn parse_width(width: &str) -> Result<ChannelWidth, Box<dyn Error>> {
+    Ok(match width {
+        "noht" => ChannelWidth::Width20NoHT,
+        "20" => ChannelWidth::Width20,
+        "40" => ChannelWidth::Width40,
+        "80" => ChannelWidth::Width80,
+        "80+80" => ChannelWidth::Width80P80,
+        "160" => ChannelWidth::Width160,
+        "320" => ChannelWidth::Width320,
+        _ => return Err(format!("Invalid channel width: {width}").into()),
+    })
+}
+
+fn list_interfaces(socket: &NlSocket) -> CliResult {
+    let mut interfaces = socket.list_interfaces()?;
+    interfaces.sort_by_key(|i| (i.wiphy_index, i.interface_index));
+    let mut wiphy = None;
+    for interface in interfaces {
+        if wiphy != Some(interface.wiphy_index) {
+            println!("phy#{}", interface.wiphy_index);
+            wiphy = Some(interface.wiphy_index);
+        }
+        println!("\tInterface {}", interface.name);
+        println!("\t\tifindex {}", interface.interface_index);
+        if let Some(wdev) = interface.wdev {
+            println!("\t\twdev 0x{wdev:x}");
+        }
+        println!("\t\taddr {}", interface.mac);
+        if let Some(ssid) = &interface.ssid {
+            println!("\t\tssid {ssid}");
+        }
+        if let Some(interface_type) = &interface.interface_type {
+            println!("\t\ttype {interface_type}");
+        }
+        if let Some(frequency) = interface.frequency {
+            match interface.channel_number() {
+                Some(channel) => print!("\t\tchannel {channel} ({frequency})"),
+                None => print!("\t\tfrequency {frequency}"),
+            }
+            println!(", width: {}", interface.channel_width);
+        }
+        if let Some(tx_power) = interface.tx_power {
+            println!("\t\ttxpower {}", Dbm::from(tx_power));
+        }
+    }
+    Ok(())
+}
+
+fn station_dump(socket: &NlSocket, name: &str) -> CliResult {
+    let interface = interface_by_name(socket, name)?;
+    for station in socket.list_stations(interface.interface_index)? {
+        println!("Station {} (on {name})", station.mac);
+        if let Some(inactive_time) = station.inactive_time {
+            println!("\tinactive time:\t{} ms", inactive_time.as_millis());
+        }
+        if let Some(rx_bytes) = station.rx_bytes64.or(station.rx_bytes.map(u64::from)) {
+            println!("\trx bytes:\t{rx_bytes}");
+        }
+        if let Some(rx_packets) = station.rx_packets {
+            println!("\trx packets:\t{rx_packets}");
+        }
+        if let Some(tx_bytes) = station.tx_bytes64.or(station.tx_bytes.map(u64::from)) {
+            println!("\ttx bytes:\t{tx_bytes}");
+        }
+        if let Some(tx_packets) = station.tx_packets {
+            println!("\ttx packets:\t{tx_packets}");
+        }
+        if let Some(tx_retries) = station.tx_retries {
+            println!("\ttx retries:\t{tx_retries}");
+        }
+        if let Some(tx_failed) = station.tx_failed {
+            println!("\ttx failed:\t{tx_failed}");
+        }
+        if let Some(signal) = station.signal {
+            println!("\tsignal:  \t{} dBm", signal as i8);
+        }
+        if let Some(signal) = station.average_signal {
+            println!("\tsignal avg:\t{} dBm", signal as i8);
+        }
+        if let Some(rate) = &station.tx_bitrate {
+            println!("\ttx bitrate:\t{}", format_rate(rate));
+        }
+        if let Some(rate) = &station.rx_bitrate {
+            println!("\trx bitrate:\t{}", format_rate(rate));
+        }
+        if let Some(connected_time) = station.connected_time {
+            println!("\tconnected time:\t{} seconds", connected_time.as_secs());
+        }
+    }
+    Ok(())
+}
+
+fn format_rate(rate: &RateInfo) -> String {
+    format!(
+        "{}.{} MBit/s MCS {} {} {}",
+        rate.bitrate / 10,
+        rate.bitrate % 10,
+        rate.mcs,
+        rate.connection_type,
+        rate.channel_width
+    )
+}
+
+fn list_physical_devices(socket: &NlSocket) -> CliResult {
+    let mut devices = socket.list_physical_devices()?;
+    devices.sort_by_key(|d| d.wiphy_index);
+    for device in devices {
+        println!("Wiphy {}", device.name);
+        let bands = [
+            ("2.4 GHz", &device.band_2ghz),
+            ("5 GHz", &device.band_5ghz),
+            ("6 GHz", &device.band_6ghz),
+            ("60 GHz", &device.band_60ghz),
+            ("S1G", &device.band_s1ghz),
+            ("LC", &device.band_lc),
+        ];
+        for (name, band) in bands {
+            if let Some(band) = band {
+                print_band(name, band);
+            }
+        }
+        println!("\tSupported interface modes:");
+        for interface_type in &device.supported_interface_types {
+            println!("\t\t * {interface_type}");
+        }
+    }
+    Ok(())
+}
+
+fn print_band(name: &str, band: &WifiBand) {
+    println!("\tBand {name}:");
+    println!("\t\tFrequencies:");
+    for frequency in &band.frequencies {
+        let channel = freq_to_channel(frequency.frequency.0)
+            .map(|(_, channel)| format!(" [{channel}]"))
+            .unwrap_or_default();
+        let mut flags = Vec::new();
+        if frequency.disabled {
+            flags.push("disabled".to_string());
+        } else {
+            flags.push(Dbm::from(frequency.max_tx_power).to_string());
+        }
+        if frequency.no_ir {
+            flags.push("no IR".to_string());
+        }
+        if frequency.radar_detection {
+            flags.push("radar detection".to_string());
+        }
+        println!(
+            "\t\t\t* {}{channel} ({})",
+            frequency.frequency,
+            flags.join(", ")
+        );
+    }
+}
+
+fn regulatory_domain(socket: &NlSocket) -> CliResult {
+    for domain in socket.get_regulatory_domain()? {
+        match domain.wiphy_index {
+            Some(wiphy_index) => println!("phy#{wiphy_index} (self-managed)"),
+            None => println!("global"),
+        }
+        println!("country {}: {:?}", domain.country_code, domain.dfs_region);
+        for rule in &domain.rules {
+            println!(
+                "\t({} - {} @ {}), ({})",
+                rule.freq_range_start,
+                rule.freq_range_end,
+                rule.max_bandwidth,
+                Dbm::from(rule.max_eirp)
+            );
+        }
+        println!();
+    }
+    Ok(())
+}
@@ -0,0 +1,196 @@
+use crate::interface::ChannelWidth;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Wi-Fi frequency band.
+pub enum Band {
+    /// 2.4 GHz band.
+    Band2GHz,
+    /// 5 GHz band, including the 4.9 GHz public safety channels.
+    Band5GHz,
+    /// 6 GHz band.
+    Band6GHz,
+    /// 60 GHz band.
+    Band60GHz,
+}
+
+/// Convert a frequency in MHz to a band and channel number.
+///
+/// Returns `None` if the frequency does not map to a channel, including
+/// frequencies between the channel centers of a band.
+pub fn freq_to_channel(frequency: u32) -> Option<(Band, u8)> {
+    let (band, offset, spacing) = match frequency {
+        2484 => return Some((Band::Band2GHz, 14)),
+        5935 => return Some((Band::Band6GHz, 2)),
+        2412..=2472 => (Band::Band2GHz, 2407, 5),
+        4910..=4980 => (Band::Band5GHz, 4000, 5),
+        5005..=5905 => (Band::Band5GHz, 5000, 5),
+        5955..=7115 => (Band::Band6GHz, 5950, 5),
+        58320..=70200 => (Band::Band60GHz, 56160, 2160),
+        _ => return None,
+    };
+    let offset = frequency - offset;
+    if !offset.is_multiple_of(spacing) {
+        return None;
+    }
+    Some((band, (offset / spacing) as u8))
+}
+
+/// Convert a band and channel number to a frequency in MHz.
+///
+/// Returns `None` if the channel number is not valid on the band.
+pub fn channel_to_freq(band: Band, channel: u8) -> Option<u32> {
+    let channel = channel as u32;
+    match band {
+        Band::Band2GHz => match channel {
+            14 => Some(2484),
+            1..=13 => Some(2407 + channel * 5),
+            _ => None,
+        },
+        Band::Band5GHz => match channel {
+            1..=181 => Some(5000 + channel * 5),
+            182..=196 => Some(4000 + channel * 5),
+            _ => None,
+        },
+        Band::Band6GHz => match channel {
+            2 => Some(5935),
+            1 | 3..=233 => Some(5950 + channel * 5),
+            _ => None,
+        },
+        Band::Band60GHz => match channel {
+            1..=6 => Some(56160 + channel * 2160),
+            _ => None,
+        },
+    }
+}
+
+/// First 20 MHz channels of the 80 and 160 MHz channel blocks of the 5 GHz
+/// band, in MHz.
+const BLOCKS_5GHZ_80: [u32; 7] = [5180, 5260, 5500, 5580, 5660, 5745, 5825];
+const BLOCKS_5GHZ_160: [u32; 3] = [5180, 5500, 5745];
+
+/// Derive the center frequency of a 40, 80, 160 or 320 MHz channel in MHz
+/// from its control channel frequency, following the channel plans of the 5
+/// and 6 GHz bands. 320 MHz channels use the 320-1 channelization.
+///
+/// Returns `None` for other widths, and for frequencies where the channel is
+/// ambiguous or not defined, e.g. 40 MHz channels in the 2.4 GHz band which
+/// extend either above or below the control channel.
+pub fn center_frequency(frequency: u32, width: ChannelWidth) -> Option<u32> {
+    let width = match width {
+        ChannelWidth::Width40 => 40,
+        ChannelWidth::Width80 => 80,
+        ChannelWidth::Width160 => 160,
+        ChannelWidth::Width320 => 320,
+        _ => return None,
+    };
+    // First 20 MHz channel of the channel.
+    let start = match freq_to_channel(frequency)? {
+        (Band::Band5GHz, _) => {
+            let (blocks, block_width): (&[u32], u32) = match width {
+                40 | 80 => (&BLOCKS_5GHZ_80, 80),
+                160 => (&BLOCKS_5GHZ_160, 160),
+                _ => return None,
+            };
+            let block = blocks
+                .iter()
+                .find(|block| (**block..**block + block_width).contains(&frequency))?;
+            block + (frequency - block) / width * width
+        }
+        (Band::Band6GHz, _) if frequency >= 5955 => {
+            let start = 5955 + (frequency - 5955) / width * width;
+            if start + width - 20 > 7115 {
+                return None;
+            }
+            start
+        }
+        _ => return None,
+    };
+    Some(start + width / 2 - 10)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_freq_to_channel() {
+        assert_eq!(freq_to_channel(2412), Some((Band::Band2GHz, 1)));
+        assert_eq!(freq_to_channel(2472), Some((Band::Band2GHz, 13)));
+        assert_eq!(freq_to_channel(2484), Some((Band::Band2GHz, 14)));
+        assert_eq!(freq_to_channel(4920), Some((Band::Band5GHz, 184)));
+        assert_eq!(freq_to_channel(5180), Some((Band::Band5GHz, 36)));
+        assert_eq!(freq_to_channel(5825), Some((Band::Band5GHz, 165)));
+        assert_eq!(freq_to_channel(5935), Some((Band::Band6GHz, 2)));
+        assert_eq!(freq_to_channel(5955), Some((Band::Band6GHz, 1)));
+        assert_eq!(freq_to_channel(7115), Some((Band::Band6GHz, 233)));
+        assert_eq!(freq_to_channel(58320), Some((Band::Band60GHz, 1)));
+        assert_eq!(freq_to_channel(69120), Some((Band::Band60GHz, 6)));
+    }
+
+    #[test]
+    fn test_freq_to_channel_invalid() {
+        assert_eq!(freq_to_channel(0), None);
+        assert_eq!(freq_to_channel(2407), None);
+        assert_eq!(freq_to_channel(2413), None);
+        assert_eq!(freq_to_channel(2480), None);
+        assert_eq!(freq_to_channel(5182), None);
+        assert_eq!(freq_to_channel(5940), None);
+        assert_eq!(freq_to_channel(58321), None);
+        assert_eq!(freq_to_channel(70200), None);
+    }
+
+    #[test]
+    fn test_channel_to_freq() {
+        assert_eq!(channel_to_freq(Band::Band2GHz, 1), Some(2412));
+        assert_eq!(channel_to_freq(Band::Band2GHz, 14), Some(2484));
+        assert_eq!(channel_to_freq(Band::Band2GHz, 15), None);
+        assert_eq!(channel_to_freq(Band::Band5GHz, 36), Some(5180));
+        assert_eq!(channel_to_freq(Band::Band5GHz, 182), Some(4910));
+        assert_eq!(channel_to_freq(Band::Band5GHz, 196), Some(4980));
+        assert_eq!(channel_to_freq(Band::Band5GHz, 0), None);
+        assert_eq!(channel_to_freq(Band::Band6GHz, 2), Some(5935));
+        assert_eq!(channel_to_freq(Band::Band6GHz, 233), Some(7115));
+        assert_eq!(channel_to_freq(Band::Band6GHz, 234), None);
+        assert_eq!(channel_to_freq(Band::Band60GHz, 1), Some(58320));
+        assert_eq!(channel_to_freq(Band::Band60GHz, 7), None);
+    }
+
+    #[test]
+    fn test_center_frequency() {
+        assert_eq!(center_frequency(5180, ChannelWidth::Width20), None);
+        assert_eq!(center_frequency(5180, ChannelWidth::Width40), Some(5190));
+        assert_eq!(center_frequency(5200, ChannelWidth::Width40), Some(5190));
+        assert_eq!(center_frequency(5220, ChannelWidth::Width40), Some(5230));
+        assert_eq!(center_frequency(5745, ChannelWidth::Width40), Some(5755));
+        assert_eq!(center_frequency(5240, ChannelWidth::Width80), Some(5210));
+        assert_eq!(center_frequency(5500, ChannelWidth::Width80), Some(5530));
+        assert_eq!(center_frequency(5320, ChannelWidth::Width80), Some(5290));
+        assert_eq!(center_frequency(5340, ChannelWidth::Width80), None);
+        assert_eq!(center_frequency(5320, ChannelWidth::Width160), Some(5250));
+        assert_eq!(center_frequency(5640, ChannelWidth::Width160), Some(5570));
+        assert_eq!(center_frequency(5660, ChannelWidth::Width160), None);
+        assert_eq!(center_frequency(5180, ChannelWidth::Width320), None);
+        assert_eq!(center_frequency(5955, ChannelWidth::Width80), Some(5985));
+        assert_eq!(center_frequency(6115, ChannelWidth::Width160), Some(6185));
+        assert_eq!(center_frequency(6255, ChannelWidth::Width320), Some(6105));
+        assert_eq!(center_frequency(7115, ChannelWidth::Width80), None);
+        assert_eq!(center_frequency(5935, ChannelWidth::Width40), None);
+        assert_eq!(center_frequency(2412, ChannelWidth::Width40), None);
+    }
+
+    #[test]
+    fn test_channel_round_trip() {
+        for (band, channels) in [
+            (Band::Band2GHz, 1..=14),
+            (Band::Band5GHz, 1..=196),
+            (Band::Band6GHz, 1..=233),
+            (Band::Band60GHz, 1..=6),
+        ] {
+            for channel in channels {
+                let frequency = channel_to_freq(band, channel).unwrap();
+                assert_eq!(freq_to_channel(frequency), Some((band, channel)));
+            }
+        }
+    }
+}
@@ -0,0 +1,319 @@
+//! Traits exposing the common method set of [`NlSocket`](crate::NlSocket) and
+//! [`AsyncNlSocket`](crate::AsyncNlSocket).
+//!
+//! Code written against these traits can be generic over the socket type and
+//! tested with a mock implementation instead of a real netlink connection.
+
+#[cfg(feature = "async")]
+use std::future::Future;
+use std::time::Duration;
+
+use crate::ap::{AclPolicy, ApConfig, BeaconConfig, QosMap};
+use crate::attributes::MonitorFlags;
+use crate::error::Result;
+use crate::event::{Event, MulticastGroup, RadarEvent};
+use crate::interface::{
+    BitrateMask, ChannelConfig, ChannelWidth, FrameTx, InterfaceType, MacAddress, TxPower,
+    WirelessInterface,
+};
+use crate::key::{DefaultKeyType, Key, KeyInfo};
+use crate::mesh::{MeshParams, MeshPath, MeshProxyPath};
+use crate::reg_domain::{RegulatoryDomain, RegulatoryHint};
+use crate::station::{LinkStatus, StationConfig, WirelessStation};
+use crate::survey::SurveyInfo;
+use crate::topology::Topology;
+use crate::wiphy::{
+    CoalesceRule, PhysicalDevice, ProtocolFeatures, SarLimit, TxqParams, WiphyParams,
+};
+
+macro_rules! nl80211_client {
+    (
+        shared {
+            $(
+                $(#[$shared_meta:meta])*
+                fn $shared:ident(&self $(, $shared_arg:ident: $shared_ty:ty)* $(,)?)
+                    -> $shared_ret:ty;
+            )*
+        }
+        exclusive {
+            $(
+                $(#[$excl_meta:meta])*
+                fn $excl:ident(&mut self $(, $excl_arg:ident: $excl_ty:ty)* $(,)?)
+                    -> $excl_ret:ty;
+            )*
+        }
+    ) => {
+        /// Common method set of the synchronous netlink socket.
+        ///
+        /// See [`NlSocket`](crate::NlSocket) for details of each request.
+        #[cfg(feature = "sync")]
+        pub trait Nl80211Client {
+            $(
+                $(#[$shared_meta])*
+                fn $shared(&self $(, $shared_arg: $shared_ty)*) -> $shared_ret;
+            )*
+            $(
+                $(#[$excl_meta])*
+                fn $excl(&mut self $(, $excl_arg: $excl_ty)*) -> $excl_ret;
+            )*
+        }
+
+        #[cfg(feature = "sync")]
+        impl Nl80211Client for crate::NlSocket {
+            $(
+                fn $shared(&self $(, $shared_arg: $shared_ty)*) -> $shared_ret {
+                    crate::NlSocket::$shared(self $(, $shared_arg)*)
+                }
+            )*
+            $(
+                fn $excl(&mut self $(, $excl_arg: $excl_ty)*) -> $excl_ret {
+                    crate::NlSocket::$excl(self $(, $excl_arg)*)
+                }
+            )*
+        }
+
+        /// Common method set of the asynchronous netlink socket.
+        ///
+        /// See [`AsyncNlSocket`](crate::AsyncNlSocket) for details of each request.
+        #[cfg(feature = "async")]
+        pub trait AsyncNl80211Client {
+            $(
+                $(#[$shared_meta])*
+                fn $shared(
+                    &self
+                    $(, $shared_arg: $shared_ty)*
+                ) -> impl Future<Output = $shared_ret>;
+            )*
+            $(
+                $(#[$excl_meta])*
+                fn $excl(
+                    &mut self
+                    $(, $excl_arg: $excl_ty)*
+                ) -> impl Future<Output = $excl_ret>;
+            )*
+        }
+
+        #[cfg(feature = "async")]
+        impl AsyncNl80211Client for crate::AsyncNlSocket {
+            $(
+                fn $shared(
+                    &self
+                    $(, $shared_arg: $shared_ty)*
+                ) -> impl Future<Output = $shared_ret> {
+                    crate::AsyncNlSocket::$shared(self $(, $shared_arg)*)
+                }
+            )*
+            $(
+                fn $excl(
+                    &mut self
+                    $(, $excl_arg: $excl_ty)*
+                ) -> impl Future<Output = $excl_ret> {
+                    crate::AsyncNlSocket::$excl(self $(, $excl_arg)*)
+                }
+            )*
+        }
+    };
+}
+
+nl80211_client! {
+    shared {
+        /// List wireless interfaces.
+        fn list_interfaces(&self) -> Result<Vec<WirelessInterface>>;
+        /// Get an interface by its index.
+        fn get_interface(&self, if_index: u32) -> Result<Option<WirelessInterface>>;
+        /// Get an interface by its wireless device identifier.
+        fn get_interface_by_wdev(&self, wdev: u64) -> Result<Option<WirelessInterface>>;
+        /// Get an interface by its name, e.g. "wlan0".
+        fn get_interface_by_name(&self, name: &str) -> Result<Option<WirelessInterface>>;
+        /// Change the type of an interface.
+        fn set_interface(&self, if_index: u32, if_type: InterfaceType) -> Result<()>;
+        /// Set monitor mode flags of an interface.
+        fn set_monitor_flags(&self, if_index: u32, flags: Vec<MonitorFlags>) -> Result<()>;
+        /// Set the operating channel of an interface.
+        fn set_channel(&self, if_index: u32, freq: u32, width: ChannelWidth) -> Result<()>;
+        /// Set the operating channel of an interface, including the center
+        /// frequencies required by channels wider than 20 MHz.
+        fn set_channel_config(&self, if_index: u32, channel: ChannelConfig) -> Result<()>;
+        /// List stations associated to an interface.
+        fn list_stations(&self, if_index: u32) -> Result<Vec<WirelessStation>>;
+        /// List stations of all wireless interfaces.
+        fn list_all_stations(&self) -> Result<Vec<(WirelessInterface, Vec<WirelessStation>)>>;
+        /// Get a snapshot of all physical devices with their interfaces and
+        /// stations.
+        fn snapshot(&self) -> Result<Topology>;
+        /// Get the protocol features supported by the kernel.
+        fn get_protocol_features(&self) -> Result<ProtocolFeatures>;
+        /// List physical wireless devices.
+        fn list_physical_devices(&self) -> Result<Vec<PhysicalDevice>>;
+        /// Get a physical wireless device by its index.
+        fn get_physical_device(&self, wiphy_index: u32) -> Result<Option<PhysicalDevice>>;
+        /// Get regulatory domains.
+        fn get_regulatory_domain(&self) -> Result<Vec<RegulatoryDomain>>;
+        /// Get the regulatory domain of a device.
+        fn get_regulatory_domain_for(&self, wiphy_index: u32) -> Result<Option<RegulatoryDomain>>;
+        /// Trigger a new scan on an interface.
+        fn trigger_scan(&self, if_index: u32) -> Result<()>;
+        /// Trigger a new scan on a wireless device without a network interface.
+        fn trigger_scan_by_wdev(&self, wdev: u64) -> Result<()>;
+        /// Stop an ongoing scan on an interface.
+        fn abort_scan(&self, if_index: u32) -> Result<()>;
+        /// Stop an ongoing scan on a wireless device without a network
+        /// interface.
+        fn abort_scan_by_wdev(&self, wdev: u64) -> Result<()>;
+        /// Install a PMK for 4-way handshake offload.
+        fn set_pmk(
+            &self,
+            if_index: u32,
+            mac: MacAddress,
+            pmk: &[u8],
+            pmkr0_name: Option<&[u8]>,
+        ) -> Result<()>;
+        /// Remove a previously installed PMK for the given authenticator.
+        fn del_pmk(&self, if_index: u32, mac: MacAddress) -> Result<()>;
+        /// Send the OWE (Opportunistic Wireless Encryption) processing result
+        /// for a peer.
+        fn update_owe_info(
+            &self,
+            if_index: u32,
+            mac: MacAddress,
+            status_code: u16,
+            dh_ie: &[u8],
+        ) -> Result<()>;
+        /// Get current association information of a station mode interface.
+        fn get_link(&self, if_index: u32) -> Result<Option<LinkStatus>>;
+        /// Start operating as an access point on the given interface.
+        fn start_ap(&self, if_index: u32, config: ApConfig) -> Result<()>;
+        /// Stop access point operation on the given interface.
+        fn stop_ap(&self, if_index: u32) -> Result<()>;
+        /// Update beacon and probe response templates of a running access
+        /// point.
+        fn set_beacon(&self, if_index: u32, config: BeaconConfig) -> Result<()>;
+        /// Add a new station to an access point interface.
+        fn new_station(&self, if_index: u32, mac: MacAddress, config: StationConfig) -> Result<()>;
+        /// Modify parameters of an existing station.
+        fn set_station(&self, if_index: u32, mac: MacAddress, config: StationConfig) -> Result<()>;
+        /// Remove a station from an access point interface.
+        fn del_station(&self, if_index: u32, mac: MacAddress) -> Result<()>;
+        /// Set MAC address based access control list of an access point.
+        fn set_mac_acl(
+            &self,
+            if_index: u32,
+            policy: AclPolicy,
+            mac_addresses: Vec<MacAddress>,
+        ) -> Result<()>;
+        /// Probe if a client associated to an access point is still reachable.
+        fn probe_client(&self, if_index: u32, mac: MacAddress) -> Result<u64>;
+        /// Subscribe to a nl80211 multicast group to receive its events with
+        /// `next_event`.
+        fn subscribe(&self, group: MulticastGroup) -> Result<()>;
+        /// Register this socket to receive all beacon frames seen by the wiphy.
+        fn register_beacons(&self, wiphy_index: u32) -> Result<()>;
+        /// Get mesh configuration parameters of a mesh point interface.
+        fn get_mesh_config(&self, if_index: u32) -> Result<MeshParams>;
+        /// Set mesh configuration parameters of a mesh point interface.
+        fn set_mesh_config(&self, if_index: u32, params: MeshParams) -> Result<()>;
+        /// List mesh paths of a mesh point interface.
+        fn list_mesh_paths(&self, if_index: u32) -> Result<Vec<MeshPath>>;
+        /// Get the mesh path to a destination.
+        fn get_mesh_path(&self, if_index: u32, destination: MacAddress) -> Result<Option<MeshPath>>;
+        /// List mesh proxy paths of a mesh point interface.
+        fn list_mesh_proxy_paths(&self, if_index: u32) -> Result<Vec<MeshProxyPath>>;
+        /// Send a directed frame to a mesh peer to verify that the peer link is
+        /// alive.
+        fn probe_mesh_link(&self, if_index: u32, mac: MacAddress, frame: &[u8]) -> Result<()>;
+        /// Install an encryption key.
+        fn add_key(&self, if_index: u32, key: Key) -> Result<()>;
+        /// Remove an encryption key.
+        fn del_key(&self, if_index: u32, index: u8, mac: Option<MacAddress>) -> Result<()>;
+        /// Get information about an encryption key.
+        fn get_key(
+            &self,
+            if_index: u32,
+            index: u8,
+            mac: Option<MacAddress>,
+        ) -> Result<Option<KeyInfo>>;
+        /// Select the default key.
+        fn set_key(&self, if_index: u32, index: u8, default_type: DefaultKeyType) -> Result<()>;
+        /// Set the antennas used for transmitting and receiving.
+        fn set_antenna(&self, wiphy_index: u32, tx_mask: u32, rx_mask: u32) -> Result<()>;
+        /// Set SAR (specific absorption rate) power limits.
+        fn set_sar_limits(&self, wiphy_index: u32, limits: Vec<SarLimit>) -> Result<()>;
+        /// Set transmit queue parameters. Parameters set to `None` are left
+        /// unchanged.
+        fn set_txq_params(&self, wiphy_index: u32, params: TxqParams) -> Result<()>;
+        /// Set the airtime weight of a station used by the airtime fairness
+        /// scheduler.
+        fn set_station_airtime_weight(
+            &self,
+            if_index: u32,
+            mac: MacAddress,
+            weight: u16,
+        ) -> Result<()>;
+        /// Restrict the bitrates used for transmitting frames.
+        fn set_tx_bitrate_mask(&self, if_index: u32, mask: BitrateMask) -> Result<()>;
+        /// Enable or disable 4-address (WDS) frames on an interface.
+        fn set_4addr(&self, if_index: u32, enabled: bool) -> Result<()>;
+        /// Configure connection quality monitor RSSI thresholds in dBm.
+        fn set_cqm_rssi(&self, if_index: u32, thresholds: Vec<i32>, hysteresis: u32) -> Result<()>;
+        /// Configure connection quality monitor TX error reporting.
+        fn set_cqm_txe(&self, if_index: u32, rate: u32, packets: u32, interval: u32) -> Result<()>;
+        /// Set the transmit power of an interface.
+        fn set_tx_power(&self, if_index: u32, tx_power: TxPower) -> Result<()>;
+        /// Set retry limits, fragmentation and RTS thresholds and coverage
+        /// class.
+        fn set_wiphy_params(&self, wiphy_index: u32, params: WiphyParams) -> Result<()>;
+        /// Set the QoS map used to map DSCP values to user priorities.
+        fn set_qos_map(&self, if_index: u32, qos_map: QosMap) -> Result<()>;
+        /// Request a regulatory domain change to the given ISO/IEC 3166-1
+        /// alpha2 country code, e.g. "US". Equivalent to `iw reg set`.
+        fn set_regulatory_domain(&self, alpha2: &str, hint: Option<RegulatoryHint>) -> Result<()>;
+        /// Get channel survey information of an interface.
+        fn get_survey(&self, if_index: u32, radio_stats: bool) -> Result<Vec<SurveyInfo>>;
+        /// Remain on a channel for the given duration, e.g. to exchange action
+        /// frames off-channel.
+        fn remain_on_channel(
+            &self,
+            if_index: u32,
+            frequency: u32,
+            duration: Duration,
+        ) -> Result<u64>;
+        /// Remain on a channel for the given duration, e.g. to exchange action
+        /// frames off-channel.
+        fn remain_on_channel_by_wdev(
+            &self,
+            wdev: u64,
+            frequency: u32,
+            duration: Duration,
+        ) -> Result<u64>;
+        /// Cancel a remain on channel request identified by its cookie.
+        fn cancel_remain_on_channel(&self, if_index: u32, cookie: u64) -> Result<()>;
+        /// Cancel a remain on channel request identified by its cookie.
+        fn cancel_remain_on_channel_by_wdev(&self, wdev: u64, cookie: u64) -> Result<()>;
+        /// Transmit a management frame.
+        fn send_frame(&self, if_index: u32, frame: FrameTx) -> Result<Option<u64>>;
+        /// Transmit a management frame.
+        fn send_frame_by_wdev(&self, wdev: u64, frame: FrameTx) -> Result<Option<u64>>;
+        /// Start a P2P device identified by its wireless device identifier.
+        fn start_p2p_device(&self, wdev: u64) -> Result<()>;
+        /// Stop a P2P device identified by its wireless device identifier.
+        fn stop_p2p_device(&self, wdev: u64) -> Result<()>;
+        /// Get packet coalescing rules of a device.
+        fn get_coalesce(&self, wiphy_index: u32) -> Result<Vec<CoalesceRule>>;
+        /// Set packet coalescing rules of a device.
+        fn set_coalesce(&self, wiphy_index: u32, rules: Vec<CoalesceRule>) -> Result<()>;
+        /// Update fast transition (802.11r) IEs of the current connection.
+        fn update_ft_ies(&self, if_index: u32, mdid: u16, ies: Vec<u8>) -> Result<()>;
+        /// Enable or disable dynamic ACK timeout estimation of a device.
+        fn set_wiphy_dyn_ack(&self, wiphy_index: u32, enabled: bool) -> Result<()>;
+    }
+    exclusive {
+        /// Wait for the next event delivered to this socket.
+        fn next_event(&mut self) -> Result<Option<Event>>;
+        /// Start DFS radar detection (channel availability check) on a channel.
+        fn start_radar_detection(
+            &mut self,
+            if_index: u32,
+            channel: ChannelConfig,
+        ) -> Result<RadarEvent>;
+    }
+}
@@ -5,7 +5,7 @@ use neli_proc_macros::neli_enum;
 ///
 /// nl80211_commands enum from https://github.com/torvalds/linux/blob/master/include/uapi/linux/nl80211.h
 #[neli_enum(serialized_type = "u8")]
-pub(crate) enum Command {
+pub enum Command {
     Unspec = 0,
 
     /// Request information about a wiphy (physical wireless device) or dump
@@ -255,6 +255,27 @@ pub(crate) enum Command {
     UnprotBeacon = 138,
 
     ControlPortFrameTxStatus = 139,
+
+    /// Set SAR (specific absorption rate) power limits.
+    SetSarSpecs = 140,
 }
 
 impl Cmd for Command {}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Command {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&format_args!("{self:?}"))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Command {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = String::deserialize(deserializer)?;
+        (0..=u8::MAX)
+            .map(Command::from)
+            .find(|command| format!("{command:?}") == name)
+            .ok_or_else(|| serde::de::Error::custom(format!("Unknown command '{name}'")))
+    }
+}
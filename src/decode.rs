@@ -0,0 +1,256 @@
+//! Offline decoding of captured nl80211 messages.
+//!
+//! Messages captured with e.g. `nlmon` can be decoded into the same types
+//! returned by the sockets without a connection to the kernel.
+use nix::errno::Errno;
+
+use crate::commands::Command;
+use crate::error::{ErrorKind, NlError};
+use crate::event::Event;
+use crate::interface::WirelessInterface;
+use crate::key::KeyInfo;
+use crate::mesh::MeshPath;
+use crate::netlink::{message_from_attributes, Neli80211Header};
+use crate::reg_domain::RegulatoryDomain;
+use crate::station::WirelessStation;
+use crate::survey::SurveyInfo;
+use crate::wiphy::PhysicalDevice;
+
+const NLMSG_HEADER_LEN: usize = 16;
+const GENL_HEADER_LEN: usize = 4;
+const NLMSG_ERROR: u16 = 2;
+const NLMSG_DONE: u16 = 3;
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Decoded nl80211 message.
+pub enum ParsedNl80211 {
+    /// Interface information (`Command::NewInterface`).
+    Interface(WirelessInterface),
+    /// Station information (`Command::NewStation`).
+    Station(Box<WirelessStation>),
+    /// Physical device information (`Command::NewWiphy`). Devices dumped in
+    /// several messages are not merged.
+    PhysicalDevice(Box<PhysicalDevice>),
+    /// Regulatory domain (`Command::GetReg`).
+    RegulatoryDomain(RegulatoryDomain),
+    /// Channel survey information (`Command::NewSurveyResults`).
+    Survey(SurveyInfo),
+    /// Mesh path information (`Command::NewMpath`).
+    MeshPath(MeshPath),
+    /// Encryption key information (`Command::NewKey`).
+    Key(KeyInfo),
+    /// Any other command, decoded as an event. Commands that are not events
+    /// are returned as `Event::Unknown`.
+    Event(Event),
+}
+
+/// Decode a raw generic netlink nl80211 message, including the netlink
+/// header.
+///
+/// The netlink message type is not checked against the nl80211 family id,
+/// because the id is assigned dynamically by the kernel. Netlink error
+/// messages are returned as errors.
+pub fn parse_message(message: &[u8]) -> Result<ParsedNl80211, NlError> {
+    if message.len() < NLMSG_HEADER_LEN {
+        return Err(NlError::with_kind(
+            ErrorKind::Deserialize,
+            "Message is shorter than the netlink header",
+        ));
+    }
+    let len = u32::from_ne_bytes(message[0..4].try_into().unwrap()) as usize;
+    let nlmsg_type = u16::from_ne_bytes(message[4..6].try_into().unwrap());
+    if len < NLMSG_HEADER_LEN || len > message.len() {
+        return Err(NlError::with_kind(
+            ErrorKind::Deserialize,
+            format!("Invalid netlink message length {len}"),
+        ));
+    }
+    let payload = &message[NLMSG_HEADER_LEN..len];
+
+    match nlmsg_type {
+        NLMSG_ERROR if payload.len() >= 4 => {
+            let error = i32::from_ne_bytes(payload[0..4].try_into().unwrap());
+            let err = NlError::from_errno(Errno::from_raw(-error));
+            // The error is followed by the netlink header of the request.
+            return Err(match payload.get(4 + NLMSG_HEADER_LEN) {
+                Some(command) => err.with_command(Command::from(*command)),
+                None => err,
+            });
+        }
+        NLMSG_ERROR | NLMSG_DONE => {
+            return Err(NlError::with_kind(
+                ErrorKind::Deserialize,
+                format!("Netlink control message type {nlmsg_type} has no nl80211 payload"),
+            ));
+        }
+        _ => (),
+    }
+    if payload.len() < GENL_HEADER_LEN {
+        return Err(NlError::with_kind(
+            ErrorKind::Deserialize,
+            "Message is shorter than the generic netlink header",
+        ));
+    }
+
+    let command = Command::from(payload[0]);
+    parse_payload(&message_from_attributes(
+        command,
+        &payload[GENL_HEADER_LEN..],
+    )?)
+}
+
+/// Decode the generic netlink payload of a received nl80211 message.
+pub(crate) fn parse_payload(message: &Neli80211Header) -> Result<ParsedNl80211, NlError> {
+    let command = *message.cmd();
+    let handle = message.attrs().get_attr_handle();
+    let parsed = match command {
+        Command::NewInterface => ParsedNl80211::Interface((&handle).try_into()?),
+        Command::NewStation => ParsedNl80211::Station(Box::new((&handle).try_into()?)),
+        Command::NewWiphy => ParsedNl80211::PhysicalDevice(Box::new((&handle).try_into()?)),
+        Command::GetReg => ParsedNl80211::RegulatoryDomain((&handle).try_into()?),
+        Command::NewSurveyResults => ParsedNl80211::Survey((&handle).try_into()?),
+        Command::NewMpath => ParsedNl80211::MeshPath((&handle).try_into()?),
+        Command::NewKey => ParsedNl80211::Key((&handle).try_into()?),
+        command => ParsedNl80211::Event(Event::from_message(command, &handle)?),
+    };
+    Ok(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attributes::{encode_attribute, Attribute};
+
+    const FAMILY_ID: u16 = 0x1c;
+
+    fn netlink_message(nlmsg_type: u16, payload: &[u8]) -> Vec<u8> {
+        let len = NLMSG_HEADER_LEN + payload.len();
+        let mut message = Vec::with_capacity(len);
+        message.extend((len as u32).to_ne_bytes());
+        message.extend(nlmsg_type.to_ne_bytes());
+        // Flags, sequence number and port id.
+        message.extend([0; 10]);
+        message.extend(payload);
+        message
+    }
+
+    fn nl80211_message(command: Command, attributes: &[u8]) -> Vec<u8> {
+        let mut payload = vec![u8::from(command), 1, 0, 0];
+        payload.extend(attributes);
+        netlink_message(FAMILY_ID, &payload)
+    }
+
+    fn interface_attributes() -> Vec<u8> {
+        let mut attributes = Vec::new();
+        encode_attribute(
+            &mut attributes,
+            u16::from(Attribute::Wiphy),
+            &1u32.to_ne_bytes(),
+        );
+        encode_attribute(
+            &mut attributes,
+            u16::from(Attribute::Ifindex),
+            &3u32.to_ne_bytes(),
+        );
+        encode_attribute(&mut attributes, u16::from(Attribute::Ifname), b"wlan0\0");
+        attributes
+    }
+
+    #[test]
+    fn test_parse_interface() {
+        let message = nl80211_message(Command::NewInterface, &interface_attributes());
+        let ParsedNl80211::Interface(interface) = parse_message(&message).unwrap() else {
+            panic!("Expected an interface");
+        };
+        assert_eq!(interface.wiphy_index, 1);
+        assert_eq!(interface.interface_index, 3);
+        assert_eq!(interface.name, "wlan0");
+    }
+
+    #[test]
+    fn test_parse_trailing_bytes() {
+        // Bytes after the length in the netlink header belong to the next
+        // message and are ignored.
+        let mut message = nl80211_message(Command::NewInterface, &interface_attributes());
+        message.extend(message.clone());
+        assert!(matches!(
+            parse_message(&message).unwrap(),
+            ParsedNl80211::Interface(_)
+        ));
+    }
+
+    #[test]
+    fn test_parse_event() {
+        let message = nl80211_message(Command::Unspec, &[]);
+        assert!(matches!(
+            parse_message(&message).unwrap(),
+            ParsedNl80211::Event(_)
+        ));
+    }
+
+    #[test]
+    fn test_parse_short_headers() {
+        let message = nl80211_message(Command::NewInterface, &interface_attributes());
+        for len in [0, 4, NLMSG_HEADER_LEN - 1] {
+            let err = parse_message(&message[..len]).unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::Deserialize, "{len}");
+        }
+
+        // Netlink header without a complete generic netlink header.
+        for len in 0..GENL_HEADER_LEN {
+            let payload = &[u8::from(Command::NewInterface), 1, 0, 0][..len];
+            let err = parse_message(&netlink_message(FAMILY_ID, payload)).unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::Deserialize, "{len}");
+        }
+    }
+
+    #[test]
+    fn test_parse_invalid_length() {
+        let mut message = nl80211_message(Command::NewInterface, &interface_attributes());
+        for len in [0, NLMSG_HEADER_LEN as u32 - 1, message.len() as u32 + 1] {
+            message[0..4].copy_from_slice(&len.to_ne_bytes());
+            let err = parse_message(&message).unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::Deserialize, "{len}");
+        }
+    }
+
+    #[test]
+    fn test_parse_malformed_attributes() {
+        let mut attributes = interface_attributes();
+        // Length of the last attribute exceeds the message.
+        let last = attributes.len() - 12;
+        attributes[last..last + 2].copy_from_slice(&64u16.to_ne_bytes());
+        let message = nl80211_message(Command::NewInterface, &attributes);
+        let err = parse_message(&message).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Deserialize);
+
+        // Payload too short for the attribute type.
+        let mut attributes = Vec::new();
+        encode_attribute(&mut attributes, u16::from(Attribute::Ifindex), &[3]);
+        let message = nl80211_message(Command::NewInterface, &attributes);
+        let err = parse_message(&message).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Deserialize);
+    }
+
+    #[test]
+    fn test_parse_error_message() {
+        let request = nl80211_message(Command::GetInterface, &[]);
+        let mut payload = (-(Errno::ENODEV as i32)).to_ne_bytes().to_vec();
+        payload.extend(&request);
+        let err = parse_message(&netlink_message(NLMSG_ERROR, &payload)).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NoDevice);
+        assert_eq!(err.errno(), Some(Errno::ENODEV as i32));
+        assert_eq!(err.command(), Some(Command::GetInterface));
+
+        // Error without the header of the request.
+        let err = parse_message(&netlink_message(NLMSG_ERROR, &payload[..4])).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::NoDevice);
+        assert_eq!(err.command(), None);
+
+        let err = parse_message(&netlink_message(NLMSG_ERROR, &payload[..3])).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Deserialize);
+        let err = parse_message(&netlink_message(NLMSG_DONE, &[0; 4])).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Deserialize);
+    }
+}
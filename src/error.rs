@@ -2,8 +2,8 @@ use std::{fmt::Display, result};
 
 use neli::{
     consts::nl::Nlmsg,
-    err::{DeError, Nlmsgerr, NlmsghdrErr, RouterError},
-    genl::Genlmsghdr,
+    err::{DeError, Nlmsgerr, NlmsghdrErr, RouterError, SerError},
+    genl::{AttrTypeBuilderError, Genlmsghdr, GenlmsghdrBuilderError, NlattrBuilderError},
 };
 use nix::errno::Errno;
 
@@ -11,15 +11,99 @@ use crate::{attributes::Attribute, commands::Command};
 
 pub type Result<T> = result::Result<T, NlError>;
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+/// Category of a netlink error.
+///
+/// More kinds may be added as errors are classified further, so matches on it
+/// need a wildcard arm.
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// Operation is not permitted (EPERM, EACCES).
+    NotPermitted,
+    /// Device or interface does not exist (ENODEV).
+    NoDevice,
+    /// Operation is not supported by the device or driver (EOPNOTSUPP).
+    NotSupported,
+    /// Device is busy or the operation is already in progress (EBUSY,
+    /// EALREADY, EINPROGRESS).
+    Busy,
+    /// Invalid argument (EINVAL, ERANGE).
+    InvalidArgument,
+    /// Failed to deserialize a netlink message.
+    Deserialize,
+    /// Failed to communicate over the netlink socket.
+    Socket,
+    /// Operation timed out (ETIMEDOUT).
+    Timeout,
+    /// Dumped objects changed during the dump and retries were exhausted.
+    InconsistentDump,
+    /// Any other error.
+    Other,
+}
+
+impl From<Errno> for ErrorKind {
+    fn from(value: Errno) -> Self {
+        match value {
+            Errno::EPERM | Errno::EACCES => ErrorKind::NotPermitted,
+            Errno::ENODEV => ErrorKind::NoDevice,
+            Errno::EOPNOTSUPP => ErrorKind::NotSupported,
+            Errno::EBUSY | Errno::EALREADY | Errno::EINPROGRESS => ErrorKind::Busy,
+            Errno::EINVAL | Errno::ERANGE => ErrorKind::InvalidArgument,
+            Errno::ETIMEDOUT => ErrorKind::Timeout,
+            _ => ErrorKind::Other,
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct NlError {
     pub msg: String,
+    kind: ErrorKind,
+    errno: Option<i32>,
+    command: Option<Command>,
 }
 
 impl NlError {
     pub fn new<T: Display>(msg: T) -> NlError {
+        NlError::with_kind(ErrorKind::Other, msg)
+    }
+
+    pub fn with_kind<T: Display>(kind: ErrorKind, msg: T) -> NlError {
         NlError {
             msg: msg.to_string(),
+            kind,
+            errno: None,
+            command: None,
+        }
+    }
+
+    /// Category of the error.
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// Raw errno returned by the kernel, if the error is a netlink error
+    /// response.
+    pub fn errno(&self) -> Option<i32> {
+        self.errno
+    }
+
+    /// Command of the request that caused the error, if known.
+    pub fn command(&self) -> Option<Command> {
+        self.command
+    }
+
+    /// Set the command of the request that caused the error, unless it is
+    /// already known from the error response.
+    pub(crate) fn with_command(mut self, command: Command) -> NlError {
+        self.command.get_or_insert(command);
+        self
+    }
+
+    pub(crate) fn from_errno(errno: Errno) -> NlError {
+        NlError {
+            errno: Some(errno as i32),
+            ..NlError::with_kind(errno.into(), errno)
         }
     }
 }
@@ -39,15 +123,40 @@ where
 {
     fn from(value: RouterError<T, P>) -> Self {
         match &value {
-            RouterError::Nlmsgerr(err) => NlError::new(Errno::from_raw(-*err.error())),
-            _ => NlError::new(value),
+            RouterError::Nlmsgerr(err) => NlError::from_errno(Errno::from_raw(-*err.error())),
+            RouterError::De(_) => NlError::with_kind(ErrorKind::Deserialize, value),
+            _ => NlError::with_kind(ErrorKind::Socket, value),
         }
     }
 }
 
 impl From<DeError> for NlError {
     fn from(value: DeError) -> Self {
-        NlError::new(value)
+        NlError::with_kind(ErrorKind::Deserialize, value)
+    }
+}
+
+impl From<SerError> for NlError {
+    fn from(value: SerError) -> Self {
+        NlError::with_kind(ErrorKind::InvalidArgument, value)
+    }
+}
+
+impl From<AttrTypeBuilderError> for NlError {
+    fn from(value: AttrTypeBuilderError) -> Self {
+        NlError::with_kind(ErrorKind::InvalidArgument, value)
+    }
+}
+
+impl From<NlattrBuilderError> for NlError {
+    fn from(value: NlattrBuilderError) -> Self {
+        NlError::with_kind(ErrorKind::InvalidArgument, value)
+    }
+}
+
+impl From<GenlmsghdrBuilderError> for NlError {
+    fn from(value: GenlmsghdrBuilderError) -> Self {
+        NlError::with_kind(ErrorKind::InvalidArgument, value)
     }
 }
 
@@ -55,6 +164,9 @@ type Nl80211Msgerr = Nlmsgerr<NlmsghdrErr<Nlmsg, Genlmsghdr<Command, Attribute>>
 
 impl From<Nl80211Msgerr> for NlError {
     fn from(value: Nl80211Msgerr) -> Self {
-        NlError::new(Errno::from_raw(-value.error()))
+        NlError {
+            command: Some(*value.nlmsg().nl_payload().cmd()),
+            ..NlError::from_errno(Errno::from_raw(-value.error()))
+        }
     }
 }
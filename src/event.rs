@@ -0,0 +1,470 @@
+use std::time::Duration;
+
+use log::debug;
+use neli::attr::Attribute as NeliAttribute;
+use neli::err::DeError;
+
+use crate::attributes::RadarEvent as NlRadarEvent;
+use crate::attributes::{Attribute, Attrs, CqmAttr, CqmRssiThresholdEvent};
+use crate::commands::Command;
+use crate::interface::MacAddress;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Nl80211 multicast groups.
+pub enum MulticastGroup {
+    /// Configuration changes.
+    Config,
+    /// Scan events.
+    Scan,
+    /// Regulatory domain changes.
+    Regulatory,
+    /// MLME (MAC sublayer management entity) events.
+    Mlme,
+    /// Vendor specific events.
+    Vendor,
+    /// NAN (neighbor awareness networking) events.
+    Nan,
+    /// Test mode events.
+    TestMode,
+}
+
+impl MulticastGroup {
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            MulticastGroup::Config => "config",
+            MulticastGroup::Scan => "scan",
+            MulticastGroup::Regulatory => "regulatory",
+            MulticastGroup::Mlme => "mlme",
+            MulticastGroup::Vendor => "vendor",
+            MulticastGroup::Nan => "nan",
+            MulticastGroup::TestMode => "testmode",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Event received from the kernel.
+///
+/// Events are delivered either to subscribed multicast groups or directly to
+/// the socket that registered for them.
+pub enum Event {
+    /// Result of a client probe started with `probe_client`.
+    ProbeClient {
+        /// Network interface index.
+        interface_index: u32,
+        /// MAC address of the probed client.
+        mac: MacAddress,
+        /// Cookie returned when the probe was started.
+        cookie: u64,
+        /// Indicates if the client acknowledged the probe.
+        acked: bool,
+    },
+    /// Received management frame, e.g. a beacon registered with `register_beacons`.
+    Frame {
+        /// Index of the wiphy that received the frame.
+        wiphy_index: Option<u32>,
+        /// Network interface index.
+        interface_index: Option<u32>,
+        /// Frequency in MHz on which the frame was received.
+        frequency: Option<u32>,
+        /// Signal strength of the received frame in dBm.
+        signal: Option<i32>,
+        /// Raw 802.11 frame starting with the frame header.
+        frame: Vec<u8>,
+    },
+    /// Connection quality monitor event configured with `set_cqm_rssi` or
+    /// `set_cqm_txe`.
+    ConnectionQuality {
+        /// Network interface index.
+        interface_index: u32,
+        /// MAC address of the peer the event relates to.
+        mac: Option<MacAddress>,
+        /// Connection quality event.
+        event: CqmEvent,
+    },
+    /// DFS radar detection event.
+    Radar {
+        /// Index of the wiphy the event relates to.
+        wiphy_index: Option<u32>,
+        /// Network interface index.
+        interface_index: Option<u32>,
+        /// Control channel frequency in MHz.
+        frequency: Option<u32>,
+        /// Radar event.
+        event: RadarEvent,
+    },
+    /// Device is on the channel requested with `remain_on_channel`.
+    RemainOnChannel {
+        /// Network interface index.
+        interface_index: Option<u32>,
+        /// Wireless device identifier.
+        wdev: Option<u64>,
+        /// Cookie returned when the request was made.
+        cookie: u64,
+        /// Frequency of the channel in MHz.
+        frequency: Option<u32>,
+        /// Duration the device remains on the channel.
+        duration: Option<Duration>,
+    },
+    /// Remain on channel period has ended or was cancelled.
+    CancelRemainOnChannel {
+        /// Network interface index.
+        interface_index: Option<u32>,
+        /// Wireless device identifier.
+        wdev: Option<u64>,
+        /// Cookie returned when the request was made.
+        cookie: u64,
+        /// Frequency of the channel in MHz.
+        frequency: Option<u32>,
+    },
+    /// Transmit status of a frame sent with `send_frame`.
+    FrameTxStatus {
+        /// Network interface index.
+        interface_index: Option<u32>,
+        /// Wireless device identifier.
+        wdev: Option<u64>,
+        /// Cookie returned when the frame was sent.
+        cookie: u64,
+        /// Indicates if the frame was acknowledged by the receiver.
+        acked: bool,
+        /// Raw 802.11 frame that was transmitted.
+        frame: Vec<u8>,
+    },
+    /// Fast transition (802.11r) IEs received from a target AP, to be updated
+    /// with `update_ft_ies`.
+    FastTransition {
+        /// Network interface index.
+        interface_index: Option<u32>,
+        /// MAC address of the target AP.
+        target_ap: Option<MacAddress>,
+        /// FT IEs received from the target AP.
+        ies: Vec<u8>,
+        /// Resource information container (RIC) IEs.
+        ric_ies: Vec<u8>,
+    },
+    /// Event that is not decoded by this library.
+    Unknown {
+        /// Raw nl80211 command number of the event.
+        command: u8,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Connection quality monitor event.
+pub enum CqmEvent {
+    /// RSSI dropped below a configured threshold.
+    RssiLow {
+        /// RSSI level that triggered the event in dBm.
+        level: Option<i32>,
+    },
+    /// RSSI rose above a configured threshold.
+    RssiHigh {
+        /// RSSI level that triggered the event in dBm.
+        level: Option<i32>,
+    },
+    /// Beacons from the connected AP are no longer received.
+    BeaconLoss,
+    /// Packets to a peer have been lost.
+    PacketLoss {
+        /// Number of lost packets.
+        packets: u32,
+    },
+    /// TX error rate exceeded the configured threshold.
+    TxErrors {
+        /// Number of packets that failed to transmit.
+        packets: Option<u32>,
+        /// TX error rate in percent.
+        rate: Option<u32>,
+        /// Interval in seconds the errors were observed in.
+        interval: Option<u32>,
+    },
+    /// Connection quality event that is not decoded by this library.
+    Unknown,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// DFS radar detection event.
+pub enum RadarEvent {
+    /// A radar pattern has been detected.
+    RadarDetected,
+    /// Channel availability check (CAC) has finished and the channel is
+    /// available.
+    CacFinished,
+    /// Channel availability check (CAC) has been aborted.
+    CacAborted,
+    /// The non-occupancy period of the channel is over.
+    NopFinished,
+    /// Channel availability check done on a non-operating channel has expired.
+    PreCacExpired,
+    /// Channel availability check (CAC) has started.
+    CacStarted,
+    /// Kernel returned an unknown radar event.
+    Unknown,
+}
+
+impl From<NlRadarEvent> for RadarEvent {
+    fn from(value: NlRadarEvent) -> Self {
+        match value {
+            NlRadarEvent::Detected => RadarEvent::RadarDetected,
+            NlRadarEvent::CacFinished => RadarEvent::CacFinished,
+            NlRadarEvent::CacAborted => RadarEvent::CacAborted,
+            NlRadarEvent::NopFinished => RadarEvent::NopFinished,
+            NlRadarEvent::PreCacExpired => RadarEvent::PreCacExpired,
+            NlRadarEvent::CacStarted => RadarEvent::CacStarted,
+            NlRadarEvent::UnrecognizedConst(_) => RadarEvent::Unknown,
+        }
+    }
+}
+
+impl TryFrom<Attrs<'_, CqmAttr>> for CqmEvent {
+    type Error = DeError;
+
+    fn try_from(handle: Attrs<'_, CqmAttr>) -> Result<Self, Self::Error> {
+        let mut threshold_event = None;
+        let mut level = None;
+        let mut lost_packets = None;
+        let mut beacon_loss = false;
+        let mut txe_packets = None;
+        let mut txe_rate = None;
+        let mut txe_interval = None;
+        for attr in handle.iter() {
+            match attr.nla_type().nla_type() {
+                CqmAttr::RssiThresholdEvent => {
+                    let event: CqmRssiThresholdEvent = attr.get_payload_as()?;
+                    threshold_event = Some(event);
+                }
+                CqmAttr::RssiLevel => level = Some(attr.get_payload_as()?),
+                CqmAttr::PktLossEvent => lost_packets = Some(attr.get_payload_as()?),
+                CqmAttr::BeaconLossEvent => beacon_loss = true,
+                CqmAttr::TxePkts => txe_packets = Some(attr.get_payload_as()?),
+                CqmAttr::TxeRate => txe_rate = Some(attr.get_payload_as()?),
+                CqmAttr::TxeIntvl => txe_interval = Some(attr.get_payload_as()?),
+                unhandled => debug!("Unhandled CQM attribute 'CqmAttr::{unhandled:?}'"),
+            }
+        }
+        let event = match threshold_event {
+            Some(CqmRssiThresholdEvent::Low) => CqmEvent::RssiLow { level },
+            Some(CqmRssiThresholdEvent::High) => CqmEvent::RssiHigh { level },
+            Some(CqmRssiThresholdEvent::BeaconLoss) => CqmEvent::BeaconLoss,
+            _ if beacon_loss => CqmEvent::BeaconLoss,
+            _ => match lost_packets {
+                Some(packets) => CqmEvent::PacketLoss { packets },
+                None if txe_packets.is_some() || txe_rate.is_some() => CqmEvent::TxErrors {
+                    packets: txe_packets,
+                    rate: txe_rate,
+                    interval: txe_interval,
+                },
+                None => CqmEvent::Unknown,
+            },
+        };
+        Ok(event)
+    }
+}
+
+impl Event {
+    pub(crate) fn from_message(
+        command: Command,
+        handle: &Attrs<'_, Attribute>,
+    ) -> Result<Self, DeError> {
+        match command {
+            Command::ProbeClient => {
+                let mut interface_index = 0;
+                let mut mac = MacAddress::default();
+                let mut cookie = 0;
+                let mut acked = false;
+                for attr in handle.iter() {
+                    match attr.nla_type().nla_type() {
+                        Attribute::Ifindex => interface_index = attr.get_payload_as()?,
+                        Attribute::Mac => mac = attr.get_payload_as()?,
+                        Attribute::Cookie => cookie = attr.get_payload_as()?,
+                        Attribute::Ack => acked = true,
+                        unhandled => {
+                            debug!(
+                                "Unhandled probe client event attribute 'Attribute::{unhandled:?}'"
+                            )
+                        }
+                    }
+                }
+                Ok(Event::ProbeClient {
+                    interface_index,
+                    mac,
+                    cookie,
+                    acked,
+                })
+            }
+            Command::Frame => {
+                let mut wiphy_index = None;
+                let mut interface_index = None;
+                let mut frequency = None;
+                let mut signal = None;
+                let mut frame = Vec::new();
+                for attr in handle.iter() {
+                    match attr.nla_type().nla_type() {
+                        Attribute::Wiphy => wiphy_index = Some(attr.get_payload_as()?),
+                        Attribute::Ifindex => interface_index = Some(attr.get_payload_as()?),
+                        Attribute::WiphyFreq => frequency = Some(attr.get_payload_as()?),
+                        Attribute::RxSignalDbm => signal = Some(attr.get_payload_as()?),
+                        Attribute::Frame => frame = attr.payload().as_ref().to_vec(),
+                        unhandled => {
+                            debug!("Unhandled frame event attribute 'Attribute::{unhandled:?}'")
+                        }
+                    }
+                }
+                Ok(Event::Frame {
+                    wiphy_index,
+                    interface_index,
+                    frequency,
+                    signal,
+                    frame,
+                })
+            }
+            Command::NotifyCqm => {
+                let mut interface_index = 0;
+                let mut mac = None;
+                let mut event = CqmEvent::Unknown;
+                for attr in handle.iter() {
+                    match attr.nla_type().nla_type() {
+                        Attribute::Ifindex => interface_index = attr.get_payload_as()?,
+                        Attribute::Mac => mac = Some(attr.get_payload_as()?),
+                        Attribute::Cqm => {
+                            let sub_handle: Attrs<'_, CqmAttr> = attr.get_attr_handle()?;
+                            event = sub_handle.try_into()?;
+                        }
+                        unhandled => {
+                            debug!("Unhandled CQM event attribute 'Attribute::{unhandled:?}'")
+                        }
+                    }
+                }
+                Ok(Event::ConnectionQuality {
+                    interface_index,
+                    mac,
+                    event,
+                })
+            }
+            Command::RadarDetect => {
+                let mut wiphy_index = None;
+                let mut interface_index = None;
+                let mut frequency = None;
+                let mut event = RadarEvent::Unknown;
+                for attr in handle.iter() {
+                    match attr.nla_type().nla_type() {
+                        Attribute::Wiphy => wiphy_index = Some(attr.get_payload_as()?),
+                        Attribute::Ifindex => interface_index = Some(attr.get_payload_as()?),
+                        Attribute::WiphyFreq => frequency = Some(attr.get_payload_as()?),
+                        Attribute::RadarEvent => {
+                            let radar_event: NlRadarEvent = attr.get_payload_as()?;
+                            event = radar_event.into();
+                        }
+                        unhandled => {
+                            debug!("Unhandled radar event attribute 'Attribute::{unhandled:?}'")
+                        }
+                    }
+                }
+                Ok(Event::Radar {
+                    wiphy_index,
+                    interface_index,
+                    frequency,
+                    event,
+                })
+            }
+            Command::FrameTxStatus => {
+                let mut interface_index = None;
+                let mut wdev = None;
+                let mut cookie = 0;
+                let mut acked = false;
+                let mut frame = Vec::new();
+                for attr in handle.iter() {
+                    match attr.nla_type().nla_type() {
+                        Attribute::Ifindex => interface_index = Some(attr.get_payload_as()?),
+                        Attribute::Wdev => wdev = Some(attr.get_payload_as()?),
+                        Attribute::Cookie => cookie = attr.get_payload_as()?,
+                        Attribute::Ack => acked = true,
+                        Attribute::Frame => frame = attr.payload().as_ref().to_vec(),
+                        unhandled => {
+                            debug!(
+                                "Unhandled frame TX status event attribute 'Attribute::{unhandled:?}'"
+                            )
+                        }
+                    }
+                }
+                Ok(Event::FrameTxStatus {
+                    interface_index,
+                    wdev,
+                    cookie,
+                    acked,
+                    frame,
+                })
+            }
+            Command::FtEvent => {
+                let mut interface_index = None;
+                let mut target_ap = None;
+                let mut ies = Vec::new();
+                let mut ric_ies = Vec::new();
+                for attr in handle.iter() {
+                    match attr.nla_type().nla_type() {
+                        Attribute::Ifindex => interface_index = Some(attr.get_payload_as()?),
+                        Attribute::Mac => target_ap = Some(attr.get_payload_as()?),
+                        Attribute::Ie => ies = attr.payload().as_ref().to_vec(),
+                        Attribute::IeRic => ric_ies = attr.payload().as_ref().to_vec(),
+                        unhandled => {
+                            debug!("Unhandled FT event attribute 'Attribute::{unhandled:?}'")
+                        }
+                    }
+                }
+                Ok(Event::FastTransition {
+                    interface_index,
+                    target_ap,
+                    ies,
+                    ric_ies,
+                })
+            }
+            Command::RemainOnChannel | Command::CancelRemainOnChannel => {
+                let mut interface_index = None;
+                let mut wdev = None;
+                let mut cookie = 0;
+                let mut frequency = None;
+                let mut duration = None;
+                for attr in handle.iter() {
+                    match attr.nla_type().nla_type() {
+                        Attribute::Ifindex => interface_index = Some(attr.get_payload_as()?),
+                        Attribute::Wdev => wdev = Some(attr.get_payload_as()?),
+                        Attribute::Cookie => cookie = attr.get_payload_as()?,
+                        Attribute::WiphyFreq => frequency = Some(attr.get_payload_as()?),
+                        Attribute::Duration => {
+                            let millis: u32 = attr.get_payload_as()?;
+                            duration = Some(Duration::from_millis(millis as u64));
+                        }
+                        unhandled => {
+                            debug!(
+                                "Unhandled remain on channel event attribute 'Attribute::{unhandled:?}'"
+                            )
+                        }
+                    }
+                }
+                if command == Command::RemainOnChannel {
+                    Ok(Event::RemainOnChannel {
+                        interface_index,
+                        wdev,
+                        cookie,
+                        frequency,
+                        duration,
+                    })
+                } else {
+                    Ok(Event::CancelRemainOnChannel {
+                        interface_index,
+                        wdev,
+                        cookie,
+                        frequency,
+                    })
+                }
+            }
+            command => Ok(Event::Unknown {
+                command: command.into(),
+            }),
+        }
+    }
+}
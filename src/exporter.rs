@@ -0,0 +1,213 @@
+//! Prometheus metrics from topology and survey snapshots.
+//!
+//! A topology is usually taken with `NlSocket::snapshot`.
+//!
+//! ```rust
+//! use netlink_wi::exporter::{encode_text, topology_metrics};
+//! use netlink_wi::topology::Topology;
+//!
+//! let topology = Topology::default();
+//! let families = topology_metrics(&topology);
+//! print!("{}", encode_text(&families));
+//! ```
+use std::fmt::Write;
+
+use crate::survey::SurveyInfo;
+use crate::topology::Topology;
+
+/// Prometheus metric type.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MetricType {
+    /// Value that can go up and down.
+    Gauge,
+    /// Monotonically increasing value.
+    Counter,
+}
+
+impl MetricType {
+    fn as_str(&self) -> &'static str {
+        match self {
+            MetricType::Gauge => "gauge",
+            MetricType::Counter => "counter",
+        }
+    }
+}
+
+/// Sample of a metric family.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sample {
+    /// Label names and values.
+    pub labels: Vec<(&'static str, String)>,
+    /// Sample value.
+    pub value: f64,
+}
+
+/// Metrics with the same name, help text and type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricFamily {
+    /// Metric name.
+    pub name: &'static str,
+    /// Help text.
+    pub help: &'static str,
+    /// Metric type.
+    pub metric_type: MetricType,
+    /// Samples of the metric.
+    pub samples: Vec<Sample>,
+}
+
+impl MetricFamily {
+    fn new(name: &'static str, help: &'static str, metric_type: MetricType) -> Self {
+        Self {
+            name,
+            help,
+            metric_type,
+            samples: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, labels: &[(&'static str, String)], value: Option<f64>) {
+        if let Some(value) = value {
+            self.samples.push(Sample {
+                labels: labels.to_vec(),
+                value,
+            });
+        }
+    }
+}
+
+/// Station metrics of all interfaces in a topology, labeled by interface name
+/// and station MAC address.
+pub fn topology_metrics(topology: &Topology) -> Vec<MetricFamily> {
+    let mut signal = MetricFamily::new(
+        "wifi_station_signal_dbm",
+        "Signal strength of last received PPDU in dBm.",
+        MetricType::Gauge,
+    );
+    let mut inactive = MetricFamily::new(
+        "wifi_station_inactive_seconds",
+        "Time since last activity.",
+        MetricType::Gauge,
+    );
+    let mut rx_bytes = MetricFamily::new(
+        "wifi_station_receive_bytes_total",
+        "Bytes received from the station.",
+        MetricType::Counter,
+    );
+    let mut tx_bytes = MetricFamily::new(
+        "wifi_station_transmit_bytes_total",
+        "Bytes transmitted to the station.",
+        MetricType::Counter,
+    );
+    let mut tx_retries = MetricFamily::new(
+        "wifi_station_transmit_retries_total",
+        "MPDU retries to the station.",
+        MetricType::Counter,
+    );
+    let mut tx_failed = MetricFamily::new(
+        "wifi_station_transmit_failed_total",
+        "Failed MPDUs to the station.",
+        MetricType::Counter,
+    );
+
+    for interface in topology
+        .devices
+        .values()
+        .flat_map(|device| &device.interfaces)
+    {
+        for station in &interface.stations {
+            let labels = [
+                ("interface", interface.interface.name.clone()),
+                ("mac", station.mac.to_string()),
+            ];
+            signal.push(&labels, station.signal.map(|s| s as i8 as f64));
+            inactive.push(&labels, station.inactive_time.map(|t| t.as_secs_f64()));
+            rx_bytes.push(
+                &labels,
+                station
+                    .rx_bytes64
+                    .or_else(|| station.rx_bytes.map(u64::from))
+                    .map(|b| b as f64),
+            );
+            tx_bytes.push(
+                &labels,
+                station
+                    .tx_bytes64
+                    .or_else(|| station.tx_bytes.map(u64::from))
+                    .map(|b| b as f64),
+            );
+            tx_retries.push(&labels, station.tx_retries.map(f64::from));
+            tx_failed.push(&labels, station.tx_failed.map(f64::from));
+        }
+    }
+    vec![signal, inactive, rx_bytes, tx_bytes, tx_retries, tx_failed]
+}
+
+/// Channel survey metrics, labeled by interface index and frequency.
+pub fn survey_metrics(surveys: &[SurveyInfo]) -> Vec<MetricFamily> {
+    let mut noise = MetricFamily::new(
+        "wifi_survey_noise_dbm",
+        "Noise level of the channel in dBm.",
+        MetricType::Gauge,
+    );
+    let mut active = MetricFamily::new(
+        "wifi_survey_active_seconds_total",
+        "Time the radio was on the channel.",
+        MetricType::Counter,
+    );
+    let mut busy = MetricFamily::new(
+        "wifi_survey_busy_seconds_total",
+        "Time the primary channel was sensed busy.",
+        MetricType::Counter,
+    );
+
+    for survey in surveys {
+        let labels = [
+            ("ifindex", survey.interface_index.to_string()),
+            (
+                "frequency",
+                survey
+                    .frequency
+                    .map(|f| f.0.to_string())
+                    .unwrap_or_default(),
+            ),
+        ];
+        noise.push(&labels, survey.noise.map(f64::from));
+        active.push(&labels, survey.active_time.map(|t| t.as_secs_f64()));
+        busy.push(&labels, survey.busy_time.map(|t| t.as_secs_f64()));
+    }
+    vec![noise, active, busy]
+}
+
+/// Encode metric families in the Prometheus text exposition format.
+pub fn encode_text(families: &[MetricFamily]) -> String {
+    let mut output = String::new();
+    for family in families.iter().filter(|f| !f.samples.is_empty()) {
+        let _ = writeln!(output, "# HELP {} {}", family.name, family.help);
+        let _ = writeln!(
+            output,
+            "# TYPE {} {}",
+            family.name,
+            family.metric_type.as_str()
+        );
+        for sample in &family.samples {
+            output.push_str(family.name);
+            if !sample.labels.is_empty() {
+                let labels: Vec<String> = sample
+                    .labels
+                    .iter()
+                    .map(|(name, value)| format!("{name}=\"{}\"", escape_label(value)))
+                    .collect();
+                let _ = write!(output, "{{{}}}", labels.join(","));
+            }
+            let _ = writeln!(output, " {}", sample.value);
+        }
+    }
+    output
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
@@ -0,0 +1,58 @@
+//! Saving and loading of JSON snapshots.
+//!
+//! Results captured on a machine with wireless hardware can be saved and
+//! loaded later to develop and test code without it.
+//!
+//! ```rust,no_run
+//! use netlink_wi::fixtures;
+//! use netlink_wi::topology::Topology;
+//!
+//! // Saved earlier with `fixtures::save("topology.json", &socket.snapshot()?)`.
+//! let topology: Topology = fixtures::load("topology.json").unwrap();
+//! ```
+use std::fs;
+use std::path::Path;
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use crate::error::{ErrorKind, NlError, Result};
+
+/// Load a value from a JSON file.
+pub fn load<T: DeserializeOwned>(path: impl AsRef<Path>) -> Result<T> {
+    let json = fs::read_to_string(path).map_err(NlError::new)?;
+    from_json(&json)
+}
+
+/// Parse a value from a JSON string.
+pub fn from_json<T: DeserializeOwned>(json: &str) -> Result<T> {
+    serde_json::from_str(json).map_err(|err| NlError::with_kind(ErrorKind::Deserialize, err))
+}
+
+/// Save a value to a JSON file.
+pub fn save<T: Serialize>(path: impl AsRef<Path>, value: &T) -> Result<()> {
+    let json = serde_json::to_string_pretty(value).map_err(NlError::new)?;
+    fs::write(path, json).map_err(NlError::new)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::interface::InterfaceType;
+    use crate::key::AkmSuite;
+    use crate::wiphy::PhysicalDevice;
+
+    #[test]
+    fn test_physical_device_with_unknown_interface_type() {
+        let device = PhysicalDevice {
+            interface_akm_suites: vec![
+                (InterfaceType::Station, vec![AkmSuite::Psk]),
+                (InterfaceType::Unknown(42), vec![AkmSuite::Ieee8021x]),
+            ],
+            ..Default::default()
+        };
+        let json = serde_json::to_string(&device).unwrap();
+        let loaded: PhysicalDevice = from_json(&json).unwrap();
+        assert_eq!(loaded.interface_akm_suites, device.interface_akm_suites);
+    }
+}
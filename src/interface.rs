@@ -1,17 +1,27 @@
 use std::convert::TryFrom;
 use std::fmt;
+use std::io::Write;
+use std::str::FromStr;
+use std::time::Duration;
 
 use log::debug;
 use neli::attr::Attribute as NeliAttribute;
-use neli::err::DeError;
-use neli::FromBytes;
+use neli::err::{DeError, SerError};
+use neli::{FromBytes, Size, ToBytes};
 
 use super::attributes::ChannelWidth as NlChannelWidth;
+use super::attributes::HeGuardInterval as NlHeGuardInterval;
 use super::attributes::InterfaceType as NlInterfaceType;
+use super::attributes::TxPowerSetting as NlTxPowerSetting;
+use super::attributes::TxRateGi as NlTxRateGi;
 use super::attributes::{Attribute, TxqStats};
 use crate::attributes::Attrs;
+use crate::channels::freq_to_channel;
+use crate::error::{ErrorKind, NlError};
+use crate::units::{Mbm, Mhz};
 
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Interface information returned from netlink.
 pub struct WirelessInterface {
     /// Index of wiphy to operate on.
@@ -26,19 +36,19 @@ pub struct WirelessInterface {
     /// whenever the object list being dumped changes.
     pub generation: u32,
     /// Network SSID.
-    pub ssid: Option<String>,
-    /// Channel frequency in MHz.
-    pub frequency: Option<u32>,
+    pub ssid: Option<Ssid>,
+    /// Channel frequency.
+    pub frequency: Option<Mhz>,
     /// Offset of the frequncy in KHz.
     pub frequency_offset: Option<u32>,
     /// Center frequency of the first part of the channel, used for anything but 20 MHz bandwidth.
-    pub center_frequency1: Option<u32>,
+    pub center_frequency1: Option<Mhz>,
     /// Center frequency of the second part of the channel, used only for 80+80 MHz bandwidth.
-    pub center_frequency2: Option<u32>,
+    pub center_frequency2: Option<Mhz>,
     /// Wireless channel width.
     pub channel_width: ChannelWidth,
-    /// Transmit power level (s16) in dBm.
-    pub tx_power: Option<u32>,
+    /// Transmit power level.
+    pub tx_power: Option<Mbm>,
     /// Wireless device identifier, used for pseudo-devices that don't have a netdev.
     pub wdev: Option<u64>,
     /// Use 4-address frames on a virtual interface.
@@ -47,6 +57,18 @@ pub struct WirelessInterface {
     pub interface_type: Option<InterfaceType>,
     // TXQ statistics.
     pub txq_statistics: Option<TransmitQueueStats>,
+    /// Attributes not decoded by this library as raw attribute type and
+    /// payload pairs. Only collected with the `raw-attributes` feature.
+    pub raw_attributes: Vec<(u16, Vec<u8>)>,
+}
+
+impl WirelessInterface {
+    /// Channel number of the operating frequency.
+    pub fn channel_number(&self) -> Option<u8> {
+        self.frequency
+            .and_then(|frequency| freq_to_channel(frequency.0))
+            .map(|(_, channel)| channel)
+    }
 }
 
 impl TryFrom<&Attrs<'_, Attribute>> for WirelessInterface {
@@ -68,27 +90,28 @@ impl TryFrom<&Attrs<'_, Attribute>> for WirelessInterface {
                 }
                 Attribute::Generation => interface.generation = attr.get_payload_as()?,
                 Attribute::Ssid => {
-                    interface.ssid = Some(String::from_utf8_lossy(attr.payload().as_ref()).into());
+                    interface.ssid = Some(Ssid::from(attr.payload().as_ref()));
                 }
                 Attribute::WiphyFreq => {
-                    interface.frequency = Some(attr.get_payload_as()?);
+                    interface.frequency = Some(Mhz(attr.get_payload_as()?));
                 }
                 Attribute::WiphyChannelType => (), // WiphyChannelType is deprecated.
                 Attribute::WiphyFreqOffset => {
                     interface.frequency_offset = Some(attr.get_payload_as()?);
                 }
                 Attribute::CenterFreq1 => {
-                    interface.center_frequency1 = Some(attr.get_payload_as()?);
+                    interface.center_frequency1 = Some(Mhz(attr.get_payload_as()?));
                 }
                 Attribute::CenterFreq2 => {
-                    interface.center_frequency2 = Some(attr.get_payload_as()?);
+                    interface.center_frequency2 = Some(Mhz(attr.get_payload_as()?));
                 }
                 Attribute::ChannelWidth => {
                     let attr_channel_width: NlChannelWidth = attr.get_payload_as()?;
                     interface.channel_width = attr_channel_width.into();
                 }
                 Attribute::WiphyTxPowerLevel => {
-                    interface.tx_power = Some(attr.get_payload_as()?);
+                    let level: u32 = attr.get_payload_as()?;
+                    interface.tx_power = Some(Mbm(level as i32));
                 }
                 Attribute::Wdev => {
                     interface.wdev = Some(attr.get_payload_as()?);
@@ -106,104 +129,26 @@ impl TryFrom<&Attrs<'_, Attribute>> for WirelessInterface {
                 }
                 unhandled => {
                     debug!("Unhandled wireless interface attribute 'Attribute::{unhandled:?}'");
+                    if cfg!(feature = "raw-attributes") {
+                        interface
+                            .raw_attributes
+                            .push((unhandled.into(), attr.payload().as_ref().to_vec()));
+                    }
                 }
             }
         }
         if let Some(payload) = interface_type_payload {
-            match payload {
-                NlInterfaceType::Unspecified => {
-                    interface.interface_type = Some(InterfaceType::Unspecified);
-                }
-                NlInterfaceType::Adhoc => {
-                    interface.interface_type = Some(InterfaceType::Adhoc);
-                }
-                NlInterfaceType::Station => {
-                    interface.interface_type = Some(InterfaceType::Station);
-                }
-                NlInterfaceType::Ap => {
-                    interface.interface_type = Some(InterfaceType::AccessPoint);
-                }
-                NlInterfaceType::ApVlan => {
-                    interface.interface_type = Some(InterfaceType::ApVlan);
-                }
-                NlInterfaceType::Wds => {
-                    interface.interface_type = Some(InterfaceType::Wds);
-                }
-                NlInterfaceType::Monitor => {
-                    interface.interface_type = Some(InterfaceType::Monitor);
-                }
-                NlInterfaceType::MeshPoint => {
-                    interface.interface_type = Some(InterfaceType::MeshPoint);
-                }
-                NlInterfaceType::P2pClient => {
-                    interface.interface_type = Some(InterfaceType::P2pClient);
-                }
-                NlInterfaceType::P2pGo => {
-                    interface.interface_type = Some(InterfaceType::P2pGroupOwner);
-                }
-                NlInterfaceType::P2pDevice => {
-                    interface.interface_type = Some(InterfaceType::P2pDevice);
-                }
-                NlInterfaceType::Ocb => {
-                    interface.interface_type = Some(InterfaceType::Ocb);
-                }
-                NlInterfaceType::Nan => {
-                    interface.interface_type = Some(InterfaceType::NotNetdev);
-                }
-                _ => {
-                    interface.interface_type = Some(InterfaceType::Unknown);
-                }
-            }
+            interface.interface_type = Some(payload.into());
         }
         if let Some(sub_handle) = txq_stats_attr {
-            let mut txq_statistics = TransmitQueueStats::default();
-            for sub_attr in sub_handle.iter() {
-                match sub_attr.nla_type().nla_type() {
-                    TxqStats::BacklogBytes => {
-                        txq_statistics.backlog_bytes = Some(sub_attr.get_payload_as()?);
-                    }
-                    TxqStats::BacklogPackets => {
-                        txq_statistics.backlog_packets = Some(sub_attr.get_payload_as()?);
-                    }
-                    TxqStats::Flows => {
-                        txq_statistics.flows = Some(sub_attr.get_payload_as()?);
-                    }
-                    TxqStats::Drops => {
-                        txq_statistics.drops = Some(sub_attr.get_payload_as()?);
-                    }
-                    TxqStats::EcnMarks => {
-                        txq_statistics.ecn_marks = Some(sub_attr.get_payload_as()?);
-                    }
-                    TxqStats::Overlimit => {
-                        txq_statistics.overlimit = Some(sub_attr.get_payload_as()?);
-                    }
-                    TxqStats::Overmemory => {
-                        txq_statistics.overmemory = Some(sub_attr.get_payload_as()?);
-                    }
-                    TxqStats::Collisions => {
-                        txq_statistics.collisions = Some(sub_attr.get_payload_as()?);
-                    }
-                    TxqStats::TxBytes => {
-                        txq_statistics.tx_bytes = Some(sub_attr.get_payload_as()?);
-                    }
-                    TxqStats::TxPackets => {
-                        txq_statistics.tx_packets = Some(sub_attr.get_payload_as()?);
-                    }
-                    TxqStats::MaxFlows => {
-                        txq_statistics.max_flows = Some(sub_attr.get_payload_as()?);
-                    }
-                    unhandled => {
-                        debug!("Unhandled txq statistics attribute 'TxqStats::{unhandled:?}'")
-                    }
-                }
-            }
-            interface.txq_statistics = Some(txq_statistics);
+            interface.txq_statistics = Some(sub_handle.try_into()?);
         }
         Ok(interface)
     }
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Transmit queue statistics.
 pub struct TransmitQueueStats {
     /// Number of bytes currently backlogged.
@@ -230,7 +175,136 @@ pub struct TransmitQueueStats {
     pub max_flows: Option<u32>,
 }
 
-#[derive(Debug, Copy, Clone, Default)]
+impl TryFrom<Attrs<'_, TxqStats>> for TransmitQueueStats {
+    type Error = DeError;
+
+    fn try_from(handle: Attrs<'_, TxqStats>) -> Result<Self, Self::Error> {
+        let mut txq_stats = TransmitQueueStats::default();
+        for attr in handle.iter() {
+            match attr.nla_type().nla_type() {
+                TxqStats::BacklogBytes => {
+                    txq_stats.backlog_bytes = Some(attr.get_payload_as()?);
+                }
+                TxqStats::BacklogPackets => {
+                    txq_stats.backlog_packets = Some(attr.get_payload_as()?);
+                }
+                TxqStats::Flows => {
+                    txq_stats.flows = Some(attr.get_payload_as()?);
+                }
+                TxqStats::Drops => {
+                    txq_stats.drops = Some(attr.get_payload_as()?);
+                }
+                TxqStats::EcnMarks => {
+                    txq_stats.ecn_marks = Some(attr.get_payload_as()?);
+                }
+                TxqStats::Overlimit => {
+                    txq_stats.overlimit = Some(attr.get_payload_as()?);
+                }
+                TxqStats::Overmemory => {
+                    txq_stats.overmemory = Some(attr.get_payload_as()?);
+                }
+                TxqStats::Collisions => {
+                    txq_stats.collisions = Some(attr.get_payload_as()?);
+                }
+                TxqStats::TxBytes => {
+                    txq_stats.tx_bytes = Some(attr.get_payload_as()?);
+                }
+                TxqStats::TxPackets => {
+                    txq_stats.tx_packets = Some(attr.get_payload_as()?);
+                }
+                TxqStats::MaxFlows => {
+                    txq_stats.max_flows = Some(attr.get_payload_as()?);
+                }
+                unhandled => {
+                    debug!("Unhandled txq statistics attribute 'TxqStats::{unhandled:?}'")
+                }
+            }
+        }
+        Ok(txq_stats)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+/// SSID of a network.
+///
+/// SSIDs are up to 32 bytes long and not required to be valid UTF-8, so the
+/// raw bytes are preserved. `Display` escapes non-printable bytes as `\xNN`.
+pub struct Ssid(Vec<u8>);
+
+impl Ssid {
+    /// Raw SSID bytes.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Convert the SSID to a string, replacing invalid UTF-8 sequences.
+    pub fn to_string_lossy(&self) -> String {
+        String::from_utf8_lossy(&self.0).into()
+    }
+}
+
+impl From<&[u8]> for Ssid {
+    fn from(value: &[u8]) -> Self {
+        Ssid(value.to_vec())
+    }
+}
+
+impl From<&str> for Ssid {
+    fn from(value: &str) -> Self {
+        Ssid(value.as_bytes().to_vec())
+    }
+}
+
+impl fmt::Display for Ssid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in &self.0 {
+            match byte {
+                b'\\' => write!(f, "\\\\")?,
+                0x20..=0x7e => write!(f, "{}", *byte as char)?,
+                _ => write!(f, "\\x{byte:02x}")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Ssid {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Parses the escaped format produced by `Display`.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Ssid {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let escaped = String::deserialize(deserializer)?;
+        let mut bytes = Vec::with_capacity(escaped.len());
+        let mut chars = escaped.chars();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => match chars.next() {
+                    Some('\\') => bytes.push(b'\\'),
+                    Some('x') => {
+                        let hex: String = chars.by_ref().take(2).collect();
+                        if hex.len() != 2 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+                            return Err(D::Error::custom("Invalid escape sequence in SSID"));
+                        }
+                        bytes.push(u8::from_str_radix(&hex, 16).map_err(D::Error::custom)?);
+                    }
+                    _ => return Err(D::Error::custom("Invalid escape sequence in SSID")),
+                },
+                c => bytes.extend(c.encode_utf8(&mut [0; 4]).as_bytes()),
+            }
+        }
+        Ok(Ssid(bytes))
+    }
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
 /// MAC-address.
 pub struct MacAddress {
     address_bytes: [u8; 6],
@@ -254,6 +328,69 @@ impl fmt::Display for MacAddress {
     }
 }
 
+impl FromStr for MacAddress {
+    type Err = NlError;
+
+    /// Parse a MAC address in the "aa:bb:cc:dd:ee:ff" format.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || {
+            NlError::with_kind(
+                ErrorKind::InvalidArgument,
+                format!("Invalid MAC address '{s}'"),
+            )
+        };
+        let mut address_bytes = [0u8; 6];
+        let mut parts = s.split(':');
+        for byte in address_bytes.iter_mut() {
+            let part = parts.next().ok_or_else(invalid)?;
+            if part.len() != 2 || !part.bytes().all(|b| b.is_ascii_hexdigit()) {
+                return Err(invalid());
+            }
+            *byte = u8::from_str_radix(part, 16).map_err(|_| invalid())?;
+        }
+        if parts.next().is_some() {
+            return Err(invalid());
+        }
+        Ok(MacAddress { address_bytes })
+    }
+}
+
+impl From<[u8; 6]> for MacAddress {
+    fn from(address_bytes: [u8; 6]) -> Self {
+        MacAddress { address_bytes }
+    }
+}
+
+impl TryFrom<&[u8]> for MacAddress {
+    type Error = NlError;
+
+    fn try_from(value: &[u8]) -> Result<Self, Self::Error> {
+        let address_bytes: [u8; 6] = value.try_into().map_err(|_| {
+            NlError::with_kind(
+                ErrorKind::InvalidArgument,
+                format!("Invalid MAC address length {}", value.len()),
+            )
+        })?;
+        Ok(MacAddress { address_bytes })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for MacAddress {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for MacAddress {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
 impl FromBytes for MacAddress {
     fn from_bytes(buffer: &mut std::io::Cursor<impl AsRef<[u8]>>) -> Result<Self, DeError> {
         let address_bytes = buffer
@@ -265,7 +402,21 @@ impl FromBytes for MacAddress {
     }
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+impl ToBytes for MacAddress {
+    fn to_bytes(&self, buffer: &mut std::io::Cursor<Vec<u8>>) -> Result<(), SerError> {
+        buffer.write_all(&self.address_bytes)?;
+        Ok(())
+    }
+}
+
+impl Size for MacAddress {
+    fn unpadded_size(&self) -> usize {
+        self.address_bytes.len()
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Virtual interface type.
 pub enum InterfaceType {
     /// Unspecified type, driver decides.
@@ -294,8 +445,30 @@ pub enum InterfaceType {
     Ocb,
     /// NAN device interface type (not a netdev).
     NotNetdev,
-    /// Kernel returned an unknown interface type.
-    Unknown,
+    /// Kernel returned an unknown interface type, carrying the raw kernel
+    /// value.
+    Unknown(u32),
+}
+
+impl From<NlInterfaceType> for InterfaceType {
+    fn from(value: NlInterfaceType) -> Self {
+        match value {
+            NlInterfaceType::Unspecified => InterfaceType::Unspecified,
+            NlInterfaceType::Adhoc => InterfaceType::Adhoc,
+            NlInterfaceType::Station => InterfaceType::Station,
+            NlInterfaceType::Ap => InterfaceType::AccessPoint,
+            NlInterfaceType::ApVlan => InterfaceType::ApVlan,
+            NlInterfaceType::Wds => InterfaceType::Wds,
+            NlInterfaceType::Monitor => InterfaceType::Monitor,
+            NlInterfaceType::MeshPoint => InterfaceType::MeshPoint,
+            NlInterfaceType::P2pClient => InterfaceType::P2pClient,
+            NlInterfaceType::P2pGo => InterfaceType::P2pGroupOwner,
+            NlInterfaceType::P2pDevice => InterfaceType::P2pDevice,
+            NlInterfaceType::Ocb => InterfaceType::Ocb,
+            NlInterfaceType::Nan => InterfaceType::NotNetdev,
+            NlInterfaceType::UnrecognizedConst(value) => InterfaceType::Unknown(value),
+        }
+    }
 }
 
 impl From<InterfaceType> for NlInterfaceType {
@@ -314,7 +487,7 @@ impl From<InterfaceType> for NlInterfaceType {
             InterfaceType::P2pDevice => NlInterfaceType::P2pDevice,
             InterfaceType::Ocb => NlInterfaceType::Ocb,
             InterfaceType::NotNetdev => NlInterfaceType::Nan,
-            InterfaceType::Unknown => NlInterfaceType::Unspecified,
+            InterfaceType::Unknown(value) => NlInterfaceType::UnrecognizedConst(value),
         }
     }
 }
@@ -335,13 +508,28 @@ impl fmt::Display for InterfaceType {
             InterfaceType::P2pDevice => "P2P device",
             InterfaceType::Ocb => "Outside Context of a BSS",
             InterfaceType::NotNetdev => "Not a netdev",
-            InterfaceType::Unknown => "Unknown interface type",
+            InterfaceType::Unknown(value) => {
+                return write!(f, "Unknown interface type ({value})");
+            }
         };
         write!(f, "{interface_type}")
     }
 }
 
+impl From<u32> for InterfaceType {
+    fn from(value: u32) -> Self {
+        NlInterfaceType::from(value).into()
+    }
+}
+
+impl From<InterfaceType> for u32 {
+    fn from(value: InterfaceType) -> Self {
+        NlInterfaceType::from(value).into()
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Wireless channel width.
 pub enum ChannelWidth {
     Width20NoHT,
@@ -428,3 +616,297 @@ impl fmt::Display for ChannelWidth {
         write!(f, "{channel_width}")
     }
 }
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Wireless channel configuration.
+pub struct ChannelConfig {
+    /// Control channel frequency.
+    pub frequency: Mhz,
+    /// Wireless channel width.
+    pub width: ChannelWidth,
+    /// Center frequency of the first part of the channel, used for anything but 20 MHz bandwidth.
+    pub center_frequency1: Option<Mhz>,
+    /// Center frequency of the second part of the channel, used only for 80+80 MHz bandwidth.
+    pub center_frequency2: Option<Mhz>,
+    /// Control channel frequency offset in kHz.
+    pub frequency_offset: Option<u32>,
+    /// Center frequency offset of the first part of the channel in kHz.
+    pub center_frequency1_offset: Option<u32>,
+    /// EDMG (802.11ay) channel configuration.
+    pub edmg: Option<EdmgConfig>,
+    /// Bitmap of punctured 20 MHz subchannels (EHT), lowest bit corresponding
+    /// to the lowest frequency subchannel.
+    pub puncturing_bitmap: Option<u32>,
+}
+
+impl ChannelConfig {
+    pub fn new(frequency: Mhz, width: ChannelWidth) -> Self {
+        Self {
+            frequency,
+            width,
+            center_frequency1: None,
+            center_frequency2: None,
+            frequency_offset: None,
+            center_frequency1_offset: None,
+            edmg: None,
+            puncturing_bitmap: None,
+        }
+    }
+
+    /// Check that the center frequencies required by the channel width are set.
+    pub fn validate(&self) -> Result<(), NlError> {
+        let needs_center_frequency1 = matches!(
+            self.width,
+            ChannelWidth::Width40
+                | ChannelWidth::Width80
+                | ChannelWidth::Width80P80
+                | ChannelWidth::Width160
+                | ChannelWidth::Width320
+        );
+        if needs_center_frequency1 && self.center_frequency1.is_none() {
+            return Err(NlError::with_kind(
+                ErrorKind::InvalidArgument,
+                format!("Channel width {} requires center_frequency1", self.width),
+            ));
+        }
+        if self.width == ChannelWidth::Width80P80 && self.center_frequency2.is_none() {
+            return Err(NlError::with_kind(
+                ErrorKind::InvalidArgument,
+                format!("Channel width {} requires center_frequency2", self.width),
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// EDMG (802.11ay) channel configuration.
+pub struct EdmgConfig {
+    /// Bitmap of the 2.16 GHz channels used for channel bonding.
+    pub channels: u8,
+    /// Channel BW configuration as defined in 802.11ay.
+    pub bw_config: u8,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Management frame to transmit with `send_frame`.
+pub struct FrameTx {
+    /// Raw 802.11 frame starting with the frame header.
+    pub frame: Vec<u8>,
+    /// Frequency to transmit the frame on. Current operating channel is used
+    /// if not set.
+    pub frequency: Option<Mhz>,
+    /// Time to wait on the channel for a response when transmitting
+    /// off-channel.
+    pub wait: Option<Duration>,
+    /// Transmitting the frame off-channel is allowed.
+    pub offchannel_tx_ok: bool,
+    /// Do not use CCK rates for transmitting the frame.
+    pub no_cck_rate: bool,
+    /// Do not wait for an acknowledgement. No cookie or TX status is returned.
+    pub dont_wait_for_ack: bool,
+}
+
+impl FrameTx {
+    pub fn new(frame: Vec<u8>) -> Self {
+        Self {
+            frame,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Transmit power setting.
+pub enum TxPower {
+    /// Automatically determine transmit power.
+    Automatic,
+    /// Limit transmit power to the given level.
+    Limited(Mbm),
+    /// Fix transmit power to the given level.
+    Fixed(Mbm),
+}
+
+impl TxPower {
+    pub(crate) fn setting(&self) -> NlTxPowerSetting {
+        match self {
+            TxPower::Automatic => NlTxPowerSetting::Automatic,
+            TxPower::Limited(_) => NlTxPowerSetting::Limited,
+            TxPower::Fixed(_) => NlTxPowerSetting::Fixed,
+        }
+    }
+
+    pub(crate) fn level(&self) -> Option<i32> {
+        match self {
+            TxPower::Automatic => None,
+            TxPower::Limited(level) | TxPower::Fixed(level) => Some(level.0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Transmit bitrate mask used to restrict the rates used for transmitting.
+///
+/// Bands that are `None` are left unrestricted.
+pub struct BitrateMask {
+    /// Allowed rates in the 2.4 GHz band.
+    pub band_2ghz: Option<BandBitrateMask>,
+    /// Allowed rates in the 5 GHz band.
+    pub band_5ghz: Option<BandBitrateMask>,
+    /// Allowed rates in the 6 GHz band.
+    pub band_6ghz: Option<BandBitrateMask>,
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Transmit bitrate mask for a single band.
+pub struct BandBitrateMask {
+    /// Allowed legacy rates in units of 500 kbps.
+    pub legacy: Option<Vec<u8>>,
+    /// Allowed HT MCS indices.
+    pub ht_mcs: Option<Vec<u8>>,
+    /// Allowed VHT MCS indices as a bitmap for each number of spatial streams,
+    /// starting from one spatial stream.
+    pub vht_mcs: Option<[u16; 8]>,
+    /// Allowed HE MCS indices as a bitmap for each number of spatial streams,
+    /// starting from one spatial stream.
+    pub he_mcs: Option<[u16; 8]>,
+    /// Guard interval for legacy, HT and VHT rates.
+    pub guard_interval: Option<TxGuardInterval>,
+    /// Guard interval for HE rates.
+    pub he_guard_interval: Option<HeGuardInterval>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Guard interval setting for transmitted frames.
+pub enum TxGuardInterval {
+    /// Use the default guard interval.
+    Default,
+    /// Force short guard interval.
+    Short,
+    /// Force long guard interval.
+    Long,
+}
+
+impl From<TxGuardInterval> for NlTxRateGi {
+    fn from(value: TxGuardInterval) -> Self {
+        match value {
+            TxGuardInterval::Default => NlTxRateGi::Default,
+            TxGuardInterval::Short => NlTxRateGi::ForceSgi,
+            TxGuardInterval::Long => NlTxRateGi::ForceLgi,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// HE guard interval setting for transmitted frames.
+pub enum HeGuardInterval {
+    /// 0.8 microseconds.
+    Usec0_8,
+    /// 1.6 microseconds.
+    Usec1_6,
+    /// 3.2 microseconds.
+    Usec3_2,
+}
+
+impl From<HeGuardInterval> for NlHeGuardInterval {
+    fn from(value: HeGuardInterval) -> Self {
+        match value {
+            HeGuardInterval::Usec0_8 => NlHeGuardInterval::Usec0_8,
+            HeGuardInterval::Usec1_6 => NlHeGuardInterval::Usec1_6,
+            HeGuardInterval::Usec3_2 => NlHeGuardInterval::Usec3_2,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_mac_address() {
+        let expected = MacAddress::from([0x00, 0x1a, 0x2b, 0x3c, 0x4d, 0xff]);
+        assert_eq!("00:1a:2b:3c:4d:ff".parse::<MacAddress>().unwrap(), expected);
+        assert_eq!("00:1A:2B:3C:4D:FF".parse::<MacAddress>().unwrap(), expected);
+        assert_eq!(
+            expected.to_string().parse::<MacAddress>().unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_mac_address() {
+        for invalid in [
+            "",
+            "00-1a-2b-3c-4d-ff",
+            "00:1a:2b:3c:4d",
+            "00:1a:2b:3c:4d:ff:00",
+            "00:1a:2b:3c:4d:ff:",
+            "0:1a:2b:3c:4d:ff",
+            "000:1a:2b:3c:4d:ff",
+            "00:1a:2b:3c:4d:fg",
+            "+0:1a:2b:3c:4d:ff",
+            "001a2b3c4dff",
+        ] {
+            let err = invalid.parse::<MacAddress>().unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::InvalidArgument, "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_ssid_display() {
+        assert_eq!(Ssid::from("home network").to_string(), "home network");
+        assert_eq!(Ssid::from("back\\slash").to_string(), "back\\\\slash");
+        assert_eq!(
+            Ssid::from(&b"\x00caf\xc3\xa9\x7f\n"[..]).to_string(),
+            "\\x00caf\\xc3\\xa9\\x7f\\x0a"
+        );
+        assert_eq!(Ssid::default().to_string(), "");
+    }
+
+    #[cfg(feature = "fixtures")]
+    #[test]
+    fn test_ssid_serde() {
+        for ssid in [
+            Ssid::default(),
+            Ssid::from("home network"),
+            Ssid::from("back\\slash \\x41"),
+            Ssid::from(&b"\x00caf\xc3\xa9\xff\\"[..]),
+            Ssid::from(&(0..=255).step_by(8).collect::<Vec<u8>>()[..]),
+        ] {
+            let json = serde_json::to_string(&ssid).unwrap();
+            assert_eq!(serde_json::from_str::<Ssid>(&json).unwrap(), ssid, "{json}");
+        }
+        assert_eq!(
+            serde_json::to_string(&Ssid::from(&b"a\\\x01"[..])).unwrap(),
+            r#""a\\\\\\x01""#
+        );
+        // Unescaped non-ASCII characters are accepted as UTF-8.
+        assert_eq!(
+            serde_json::from_str::<Ssid>(r#""café""#).unwrap(),
+            Ssid::from("café")
+        );
+    }
+
+    #[cfg(feature = "fixtures")]
+    #[test]
+    fn test_ssid_deserialize_invalid_escape() {
+        for invalid in [
+            r#""\\""#,
+            r#""\\n""#,
+            r#""\\x""#,
+            r#""\\x4""#,
+            r#""\\xzz""#,
+            r#""\\x+1""#,
+        ] {
+            assert!(serde_json::from_str::<Ssid>(invalid).is_err(), "{invalid}");
+        }
+    }
+}
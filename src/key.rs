@@ -0,0 +1,345 @@
+use std::fmt;
+
+use log::debug;
+use neli::attr::Attribute as NeliAttribute;
+use neli::err::DeError;
+
+use crate::attributes::KeyType as NlKeyType;
+use crate::attributes::{Attribute, Attrs};
+use crate::interface::MacAddress;
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Encryption key installed with `add_key`.
+///
+/// The key material is redacted from the `Debug` output.
+pub struct Key {
+    /// Key index (0-3 for data keys, 4-5 for management frame protection keys).
+    pub index: u8,
+    /// Cipher suite used with the key.
+    pub cipher: CipherSuite,
+    /// Key material.
+    pub data: Vec<u8>,
+    /// Transmit/receive sequence counter (IV/PN) of the key.
+    pub sequence: Option<Vec<u8>>,
+    /// Type of the key.
+    pub key_type: KeyType,
+    /// Peer MAC address for pairwise keys.
+    pub mac: Option<MacAddress>,
+}
+
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Key information returned from netlink.
+///
+/// The key material is redacted from the `Debug` output.
+pub struct KeyInfo {
+    /// Network interface index.
+    pub interface_index: u32,
+    /// Key index.
+    pub index: Option<u8>,
+    /// Peer MAC address for pairwise keys.
+    pub mac: Option<MacAddress>,
+    /// Cipher suite used with the key.
+    pub cipher: Option<CipherSuite>,
+    /// Key material, if reported by the driver.
+    pub data: Option<Vec<u8>>,
+    /// Current transmit/receive sequence counter (IV/PN) of the key.
+    pub sequence: Option<Vec<u8>>,
+}
+
+impl fmt::Debug for Key {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Key")
+            .field("index", &self.index)
+            .field("cipher", &self.cipher)
+            .field("data", &Redacted)
+            .field("sequence", &self.sequence)
+            .field("key_type", &self.key_type)
+            .field("mac", &self.mac)
+            .finish()
+    }
+}
+
+impl fmt::Debug for KeyInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyInfo")
+            .field("interface_index", &self.interface_index)
+            .field("index", &self.index)
+            .field("mac", &self.mac)
+            .field("cipher", &self.cipher)
+            .field("data", &self.data.as_ref().map(|_| Redacted))
+            .field("sequence", &self.sequence)
+            .finish()
+    }
+}
+
+/// Shown in place of key material in `Debug` output.
+struct Redacted;
+
+impl fmt::Debug for Redacted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("<redacted>")
+    }
+}
+
+impl TryFrom<&Attrs<'_, Attribute>> for KeyInfo {
+    type Error = DeError;
+
+    fn try_from(handle: &Attrs<'_, Attribute>) -> Result<Self, Self::Error> {
+        let mut key = Self::default();
+        for attr in handle.iter() {
+            match attr.nla_type().nla_type() {
+                Attribute::Ifindex => key.interface_index = attr.get_payload_as()?,
+                Attribute::KeyIdx => key.index = Some(attr.get_payload_as()?),
+                Attribute::Mac => key.mac = Some(attr.get_payload_as()?),
+                Attribute::KeyCipher => {
+                    let cipher: u32 = attr.get_payload_as()?;
+                    key.cipher = Some(cipher.into());
+                }
+                Attribute::KeyData => key.data = Some(attr.payload().as_ref().to_vec()),
+                Attribute::KeySeq => key.sequence = Some(attr.payload().as_ref().to_vec()),
+                Attribute::Key => (), // Same information as the top-level key attributes.
+                unhandled => debug!("Unhandled key attribute 'Attribute::{unhandled:?}'"),
+            }
+        }
+        Ok(key)
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Type of an encryption key.
+pub enum KeyType {
+    /// Group (broadcast/multicast) key.
+    #[default]
+    Group,
+    /// Pairwise (unicast) key.
+    Pairwise,
+    /// Peer key (DLS).
+    PeerKey,
+}
+
+impl From<KeyType> for NlKeyType {
+    fn from(value: KeyType) -> Self {
+        match value {
+            KeyType::Group => NlKeyType::Group,
+            KeyType::Pairwise => NlKeyType::Pairwise,
+            KeyType::PeerKey => NlKeyType::Peerkey,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Default key type selected with `set_key`.
+pub enum DefaultKeyType {
+    /// Default key for data frames.
+    Data,
+    /// Default key for management frames (management frame protection).
+    Management,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Cipher suite.
+///
+/// Cipher suite selectors from IEEE 802.11 standard.
+pub enum CipherSuite {
+    /// WEP with 40-bit key.
+    Wep40,
+    /// TKIP.
+    Tkip,
+    /// CCMP with 128-bit key.
+    Ccmp128,
+    /// WEP with 104-bit key.
+    Wep104,
+    /// BIP-CMAC with 128-bit key (AES-128-CMAC).
+    BipCmac128,
+    /// GCMP with 128-bit key.
+    Gcmp128,
+    /// GCMP with 256-bit key.
+    Gcmp256,
+    /// CCMP with 256-bit key.
+    Ccmp256,
+    /// BIP-GMAC with 128-bit key.
+    BipGmac128,
+    /// BIP-GMAC with 256-bit key.
+    BipGmac256,
+    /// BIP-CMAC with 256-bit key.
+    BipCmac256,
+    /// SMS4 (WAPI).
+    Sms4,
+    /// Unknown cipher suite selector.
+    Unknown(u32),
+}
+
+impl From<u32> for CipherSuite {
+    fn from(value: u32) -> Self {
+        match value {
+            0x000F_AC01 => CipherSuite::Wep40,
+            0x000F_AC02 => CipherSuite::Tkip,
+            0x000F_AC04 => CipherSuite::Ccmp128,
+            0x000F_AC05 => CipherSuite::Wep104,
+            0x000F_AC06 => CipherSuite::BipCmac128,
+            0x000F_AC08 => CipherSuite::Gcmp128,
+            0x000F_AC09 => CipherSuite::Gcmp256,
+            0x000F_AC0A => CipherSuite::Ccmp256,
+            0x000F_AC0B => CipherSuite::BipGmac128,
+            0x000F_AC0C => CipherSuite::BipGmac256,
+            0x000F_AC0D => CipherSuite::BipCmac256,
+            0x0014_7201 => CipherSuite::Sms4,
+            unknown => CipherSuite::Unknown(unknown),
+        }
+    }
+}
+
+impl From<CipherSuite> for u32 {
+    fn from(value: CipherSuite) -> Self {
+        match value {
+            CipherSuite::Wep40 => 0x000F_AC01,
+            CipherSuite::Tkip => 0x000F_AC02,
+            CipherSuite::Ccmp128 => 0x000F_AC04,
+            CipherSuite::Wep104 => 0x000F_AC05,
+            CipherSuite::BipCmac128 => 0x000F_AC06,
+            CipherSuite::Gcmp128 => 0x000F_AC08,
+            CipherSuite::Gcmp256 => 0x000F_AC09,
+            CipherSuite::Ccmp256 => 0x000F_AC0A,
+            CipherSuite::BipGmac128 => 0x000F_AC0B,
+            CipherSuite::BipGmac256 => 0x000F_AC0C,
+            CipherSuite::BipCmac256 => 0x000F_AC0D,
+            CipherSuite::Sms4 => 0x0014_7201,
+            CipherSuite::Unknown(value) => value,
+        }
+    }
+}
+
+impl fmt::Display for CipherSuite {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CipherSuite::Wep40 => write!(f, "WEP40"),
+            CipherSuite::Tkip => write!(f, "TKIP"),
+            CipherSuite::Ccmp128 => write!(f, "CCMP-128"),
+            CipherSuite::Wep104 => write!(f, "WEP104"),
+            CipherSuite::BipCmac128 => write!(f, "BIP-CMAC-128"),
+            CipherSuite::Gcmp128 => write!(f, "GCMP-128"),
+            CipherSuite::Gcmp256 => write!(f, "GCMP-256"),
+            CipherSuite::Ccmp256 => write!(f, "CCMP-256"),
+            CipherSuite::BipGmac128 => write!(f, "BIP-GMAC-128"),
+            CipherSuite::BipGmac256 => write!(f, "BIP-GMAC-256"),
+            CipherSuite::BipCmac256 => write!(f, "BIP-CMAC-256"),
+            CipherSuite::Sms4 => write!(f, "SMS4"),
+            CipherSuite::Unknown(value) => write!(f, "Unknown ({value:08X})"),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Authentication and key management (AKM) suite.
+///
+/// AKM suite selectors from IEEE 802.11 standard.
+pub enum AkmSuite {
+    /// IEEE 802.1X with SHA-1.
+    Ieee8021x,
+    /// PSK with SHA-1 (WPA2-Personal).
+    Psk,
+    /// FT with IEEE 802.1X.
+    FtIeee8021x,
+    /// FT with PSK.
+    FtPsk,
+    /// IEEE 802.1X with SHA-256.
+    Ieee8021xSha256,
+    /// PSK with SHA-256.
+    PskSha256,
+    /// TDLS.
+    Tdls,
+    /// SAE (WPA3-Personal).
+    Sae,
+    /// FT with SAE.
+    FtSae,
+    /// IEEE 802.1X Suite B.
+    Ieee8021xSuiteB,
+    /// IEEE 802.1X Suite B 192-bit (WPA3-Enterprise 192-bit mode).
+    Ieee8021xSuiteB192,
+    /// FT with IEEE 802.1X and SHA-384.
+    FtIeee8021xSha384,
+    /// FILS with SHA-256.
+    FilsSha256,
+    /// FILS with SHA-384.
+    FilsSha384,
+    /// FT with FILS and SHA-256.
+    FtFilsSha256,
+    /// FT with FILS and SHA-384.
+    FtFilsSha384,
+    /// OWE (Enhanced Open).
+    Owe,
+    /// FT with PSK and SHA-384.
+    FtPskSha384,
+    /// PSK with SHA-384.
+    PskSha384,
+    /// SAE using group-dependent hash.
+    SaeExtKey,
+    /// FT with SAE using group-dependent hash.
+    FtSaeExtKey,
+    /// Unknown AKM suite selector.
+    Unknown(u32),
+}
+
+impl From<u32> for AkmSuite {
+    fn from(value: u32) -> Self {
+        match value {
+            0x000F_AC01 => AkmSuite::Ieee8021x,
+            0x000F_AC02 => AkmSuite::Psk,
+            0x000F_AC03 => AkmSuite::FtIeee8021x,
+            0x000F_AC04 => AkmSuite::FtPsk,
+            0x000F_AC05 => AkmSuite::Ieee8021xSha256,
+            0x000F_AC06 => AkmSuite::PskSha256,
+            0x000F_AC07 => AkmSuite::Tdls,
+            0x000F_AC08 => AkmSuite::Sae,
+            0x000F_AC09 => AkmSuite::FtSae,
+            0x000F_AC0B => AkmSuite::Ieee8021xSuiteB,
+            0x000F_AC0C => AkmSuite::Ieee8021xSuiteB192,
+            0x000F_AC0D => AkmSuite::FtIeee8021xSha384,
+            0x000F_AC0E => AkmSuite::FilsSha256,
+            0x000F_AC0F => AkmSuite::FilsSha384,
+            0x000F_AC10 => AkmSuite::FtFilsSha256,
+            0x000F_AC11 => AkmSuite::FtFilsSha384,
+            0x000F_AC12 => AkmSuite::Owe,
+            0x000F_AC13 => AkmSuite::FtPskSha384,
+            0x000F_AC14 => AkmSuite::PskSha384,
+            0x000F_AC18 => AkmSuite::SaeExtKey,
+            0x000F_AC19 => AkmSuite::FtSaeExtKey,
+            unknown => AkmSuite::Unknown(unknown),
+        }
+    }
+}
+
+impl From<AkmSuite> for u32 {
+    fn from(value: AkmSuite) -> Self {
+        match value {
+            AkmSuite::Ieee8021x => 0x000F_AC01,
+            AkmSuite::Psk => 0x000F_AC02,
+            AkmSuite::FtIeee8021x => 0x000F_AC03,
+            AkmSuite::FtPsk => 0x000F_AC04,
+            AkmSuite::Ieee8021xSha256 => 0x000F_AC05,
+            AkmSuite::PskSha256 => 0x000F_AC06,
+            AkmSuite::Tdls => 0x000F_AC07,
+            AkmSuite::Sae => 0x000F_AC08,
+            AkmSuite::FtSae => 0x000F_AC09,
+            AkmSuite::Ieee8021xSuiteB => 0x000F_AC0B,
+            AkmSuite::Ieee8021xSuiteB192 => 0x000F_AC0C,
+            AkmSuite::FtIeee8021xSha384 => 0x000F_AC0D,
+            AkmSuite::FilsSha256 => 0x000F_AC0E,
+            AkmSuite::FilsSha384 => 0x000F_AC0F,
+            AkmSuite::FtFilsSha256 => 0x000F_AC10,
+            AkmSuite::FtFilsSha384 => 0x000F_AC11,
+            AkmSuite::Owe => 0x000F_AC12,
+            AkmSuite::FtPskSha384 => 0x000F_AC13,
+            AkmSuite::PskSha384 => 0x000F_AC14,
+            AkmSuite::SaeExtKey => 0x000F_AC18,
+            AkmSuite::FtSaeExtKey => 0x000F_AC19,
+            AkmSuite::Unknown(value) => value,
+        }
+    }
+}
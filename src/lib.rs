@@ -17,23 +17,56 @@
 //!
 //! See more examples in [Github](https://github.com/uption/netlink_wi/tree/master/examples).
 //!
+pub mod ap;
 pub(crate) mod attributes;
+pub mod channels;
 pub(crate) mod commands;
 
 #[cfg(feature = "async")]
 mod asynchronous;
+#[cfg(feature = "sync")]
+mod batch;
+#[cfg(any(feature = "sync", feature = "async"))]
+mod client;
+pub mod decode;
 mod error;
+pub mod event;
+#[cfg(feature = "exporter")]
+pub mod exporter;
+#[cfg(feature = "fixtures")]
+pub mod fixtures;
 pub mod interface;
+pub mod key;
+#[cfg(feature = "link")]
+pub mod link;
+pub mod mesh;
 mod netlink;
+mod observer;
 pub mod reg_domain;
 pub mod station;
+pub mod survey;
 #[cfg(feature = "sync")]
 mod synchronous;
+#[cfg(feature = "sync")]
+pub mod testing;
+pub mod topology;
+#[cfg(feature = "sync")]
+mod transport;
+pub mod units;
 pub mod wiphy;
 
 pub use crate::attributes::MonitorFlags;
+pub use crate::commands::Command;
 #[cfg(feature = "async")]
 pub use asynchronous::AsyncNlSocket;
-pub use error::NlError;
 #[cfg(feature = "sync")]
-pub use synchronous::NlSocket;
+pub use batch::Batch;
+#[cfg(feature = "async")]
+pub use client::AsyncNl80211Client;
+#[cfg(feature = "sync")]
+pub use client::Nl80211Client;
+pub use error::{ErrorKind, NlError};
+pub use netlink::Nl80211Request;
+pub use observer::SocketObserver;
+#[cfg(feature = "sync")]
+pub use synchronous::{NetworkNamespace, NlSocket, NlSocketBuilder, StationWatch};
@@ -0,0 +1,187 @@
+//! Link state of network interfaces over route netlink.
+//!
+//! ```rust,no_run
+//! use netlink_wi::link::LinkSocket;
+//! use netlink_wi::NlSocket;
+//!
+//! let socket = NlSocket::connect().unwrap();
+//! let links = LinkSocket::connect().unwrap();
+//! for (interface, link) in links.with_link_state(socket.list_interfaces().unwrap()).unwrap() {
+//!     match link {
+//!         Some(link) => println!("{}: up={} mtu={:?}", interface.name, link.is_up(), link.mtu),
+//!         None => println!("{}: no network interface", interface.name),
+//!     }
+//! }
+//! ```
+use std::collections::HashMap;
+
+use neli::consts::nl::NlmF;
+use neli::consts::rtnl::{Arphrd, Iff, Ifla, RtAddrFamily, Rtm};
+use neli::consts::socket::NlFamily;
+use neli::nl::NlPayload;
+use neli::router::synchronous::{NlRouter, NlRouterReceiverHandle};
+use neli::rtnl::{Ifinfomsg, IfinfomsgBuilder};
+use neli::utils::Groups;
+
+use crate::error::{ErrorKind, NlError, Result};
+use crate::interface::WirelessInterface;
+
+/// Operational state of a network interface (RFC 2863).
+///
+/// IF_OPER_* enum from:
+/// https://github.com/torvalds/linux/blob/master/include/uapi/linux/if.h
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum OperState {
+    Unknown,
+    NotPresent,
+    Down,
+    LowerLayerDown,
+    Testing,
+    Dormant,
+    Up,
+    /// State value not known by this library.
+    Other(u8),
+}
+
+impl From<u8> for OperState {
+    fn from(value: u8) -> Self {
+        match value {
+            0 => OperState::Unknown,
+            1 => OperState::NotPresent,
+            2 => OperState::Down,
+            3 => OperState::LowerLayerDown,
+            4 => OperState::Testing,
+            5 => OperState::Dormant,
+            6 => OperState::Up,
+            other => OperState::Other(other),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Link state of a network interface.
+pub struct LinkState {
+    /// Network interface index.
+    pub interface_index: u32,
+    /// Network interface name.
+    pub name: Option<String>,
+    /// Interface is administratively up (IFF_UP).
+    pub admin_up: bool,
+    /// Interface has carrier (IFF_LOWER_UP).
+    pub lower_up: bool,
+    /// Operational state.
+    pub oper_state: OperState,
+    /// Maximum transmission unit in bytes.
+    pub mtu: Option<u32>,
+    /// Raw IFF_* interface flags.
+    pub flags: u32,
+}
+
+impl LinkState {
+    /// Returns true if the interface is administratively and operationally up.
+    pub fn is_up(&self) -> bool {
+        self.admin_up && self.oper_state == OperState::Up
+    }
+}
+
+/// Route netlink socket for querying link state.
+pub struct LinkSocket {
+    socket: NlRouter,
+}
+
+impl LinkSocket {
+    /// Connect route netlink socket.
+    pub fn connect() -> Result<Self> {
+        let (socket, _) = NlRouter::connect(NlFamily::Route, None, Groups::empty())?;
+        Ok(Self { socket })
+    }
+
+    /// Get the link state of an interface.
+    pub fn get_link_state(&self, if_index: u32) -> Result<LinkState> {
+        let message = IfinfomsgBuilder::default()
+            .ifi_family(RtAddrFamily::Unspecified)
+            .ifi_type(Arphrd::Netrom)
+            .ifi_index(if_index as i32)
+            .build()
+            .map_err(|err| NlError::with_kind(ErrorKind::InvalidArgument, err))?;
+        let recv: NlRouterReceiverHandle<Rtm, Ifinfomsg> =
+            self.socket
+                .send(Rtm::Getlink, NlmF::REQUEST, NlPayload::Payload(message))?;
+
+        for response in recv {
+            let response = response?;
+            if let NlPayload::Payload(message) = response.nl_payload() {
+                return Ok(link_state(message));
+            }
+        }
+        Err(NlError::with_kind(
+            ErrorKind::NoDevice,
+            format!("No link information for interface {if_index}"),
+        ))
+    }
+
+    /// Get the link state of all network interfaces with a single dump.
+    pub fn list_link_states(&self) -> Result<Vec<LinkState>> {
+        let message = IfinfomsgBuilder::default()
+            .ifi_family(RtAddrFamily::Unspecified)
+            .ifi_type(Arphrd::Netrom)
+            .ifi_index(0)
+            .build()
+            .map_err(|err| NlError::with_kind(ErrorKind::InvalidArgument, err))?;
+        let recv: NlRouterReceiverHandle<Rtm, Ifinfomsg> = self.socket.send(
+            Rtm::Getlink,
+            NlmF::REQUEST | NlmF::DUMP,
+            NlPayload::Payload(message),
+        )?;
+
+        let mut links = Vec::new();
+        for response in recv {
+            let response = response?;
+            if let NlPayload::Payload(message) = response.nl_payload() {
+                links.push(link_state(message));
+            }
+        }
+        Ok(links)
+    }
+
+    /// Pair interfaces with their link state.
+    ///
+    /// Interfaces without a network interface, such as P2P devices, have no
+    /// link state.
+    pub fn with_link_state(
+        &self,
+        interfaces: Vec<WirelessInterface>,
+    ) -> Result<Vec<(WirelessInterface, Option<LinkState>)>> {
+        let links: HashMap<u32, LinkState> = self
+            .list_link_states()?
+            .into_iter()
+            .map(|link| (link.interface_index, link))
+            .collect();
+        Ok(interfaces
+            .into_iter()
+            .map(|interface| {
+                let link = links.get(&interface.interface_index).cloned();
+                (interface, link)
+            })
+            .collect())
+    }
+}
+
+fn link_state(message: &Ifinfomsg) -> LinkState {
+    let handle = message.rtattrs().get_attr_handle();
+    let flags = message.ifi_flags();
+    LinkState {
+        interface_index: *message.ifi_index() as u32,
+        name: handle.get_attr_payload_as_with_len(Ifla::Ifname).ok(),
+        admin_up: flags.contains(Iff::UP),
+        lower_up: flags.contains(Iff::LOWERUP),
+        oper_state: handle
+            .get_attr_payload_as::<u8>(Ifla::Operstate)
+            .map(OperState::from)
+            .unwrap_or(OperState::Unknown),
+        mtu: handle.get_attr_payload_as(Ifla::Mtu).ok(),
+        flags: flags.bits(),
+    }
+}
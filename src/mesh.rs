@@ -0,0 +1,377 @@
+use std::time::Duration;
+
+use log::debug;
+use neli::attr::Attribute as NeliAttribute;
+use neli::err::DeError;
+
+use crate::attributes::MeshPowerMode as NlMeshPowerMode;
+use crate::attributes::{Attribute, Attrs, MeshConfigAttr, MpathFlags, MpathInfo};
+use crate::interface::MacAddress;
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Mesh configuration parameters.
+///
+/// When used to set the configuration, only the parameters that are set are
+/// changed.
+pub struct MeshParams {
+    /// Initial retry timeout in milliseconds used by the Peer Link Open message.
+    pub retry_timeout: Option<u16>,
+    /// Initial confirm timeout in milliseconds used by the peer link management.
+    pub confirm_timeout: Option<u16>,
+    /// Holding timeout in milliseconds used by the peer link management.
+    pub holding_timeout: Option<u16>,
+    /// Maximum number of peer links allowed on the mesh interface.
+    pub max_peer_links: Option<u16>,
+    /// Maximum number of peer link open retries.
+    pub max_retries: Option<u8>,
+    /// Value of the TTL field set at a source mesh point.
+    pub ttl: Option<u8>,
+    /// Value of the TTL field set at a mesh point for path selection elements.
+    pub element_ttl: Option<u8>,
+    /// Automatically open peer links when compatible mesh peers are detected.
+    pub auto_open_peer_links: Option<bool>,
+    /// Maximum number of neighbors to synchronize to.
+    pub sync_offset_max_neighbor: Option<u32>,
+    /// Number of PREQ action frames a mesh point can send to a particular destination.
+    pub hwmp_max_preq_retries: Option<u8>,
+    /// How frequently mesh paths are refreshed in milliseconds.
+    pub path_refresh_time: Option<u32>,
+    /// Minimum time in milliseconds to wait until giving up on a path discovery.
+    pub min_discovery_timeout: Option<u16>,
+    /// Time in TUs for which forwarding information from a PREQ is valid.
+    pub hwmp_active_path_timeout: Option<u32>,
+    /// Minimum interval in TUs between two consecutive PREQs.
+    pub hwmp_preq_min_interval: Option<u16>,
+    /// Minimum interval in TUs between two consecutive PERRs.
+    pub hwmp_perr_min_interval: Option<u16>,
+    /// Time in TUs it takes for an HWMP element to propagate across the mesh.
+    pub hwmp_net_diameter_traversal_time: Option<u16>,
+    /// HWMP root mode (0 disables root mode).
+    pub hwmp_root_mode: Option<u8>,
+    /// Interval in TUs between root announcements.
+    pub hwmp_rann_interval: Option<u16>,
+    /// Advertise that this mesh station has access to a broader network beyond the MBSS.
+    pub gate_announcements: Option<bool>,
+    /// Mesh station is forwarding frames.
+    pub forwarding: Option<bool>,
+    /// Minimum average signal strength in dBm of a candidate station to establish a peer link.
+    pub rssi_threshold: Option<i32>,
+    /// Mesh HT protection mode.
+    pub ht_opmode: Option<u16>,
+    /// Time in TUs for which forwarding information to the root mesh station is valid.
+    pub hwmp_path_to_root_timeout: Option<u32>,
+    /// Interval in TUs between proactive PREQs.
+    pub hwmp_root_interval: Option<u16>,
+    /// Minimum interval in TUs between root path confirmation PREQs.
+    pub hwmp_confirmation_interval: Option<u16>,
+    /// Default mesh power mode for new peer links.
+    pub power_mode: Option<MeshPowerMode>,
+    /// Awake window duration in TUs.
+    pub awake_window: Option<u16>,
+    /// Inactivity time in seconds after which a peer is removed.
+    pub peer_link_timeout: Option<u32>,
+    /// Advertise that this mesh station is connected to a mesh gate.
+    pub connected_to_gate: Option<bool>,
+    /// Advertise that this mesh station is connected to an authentication server.
+    pub connected_to_as: Option<bool>,
+    /// Avoid multi-hop path discovery if the destination is a direct neighbor.
+    pub no_learn: Option<bool>,
+}
+
+impl TryFrom<&Attrs<'_, Attribute>> for MeshParams {
+    type Error = DeError;
+
+    fn try_from(handle: &Attrs<'_, Attribute>) -> Result<Self, Self::Error> {
+        let mut params = MeshParams::default();
+        for attr in handle.iter() {
+            match attr.nla_type().nla_type() {
+                Attribute::MeshConfig => {
+                    let sub_handle: Attrs<'_, MeshConfigAttr> = attr.get_attr_handle()?;
+                    params = sub_handle.try_into()?;
+                }
+                Attribute::Ifindex => (),
+                unhandled => debug!("Unhandled mesh config attribute 'Attribute::{unhandled:?}'"),
+            }
+        }
+        Ok(params)
+    }
+}
+
+impl TryFrom<Attrs<'_, MeshConfigAttr>> for MeshParams {
+    type Error = DeError;
+
+    fn try_from(handle: Attrs<'_, MeshConfigAttr>) -> Result<Self, Self::Error> {
+        let mut params = MeshParams::default();
+        for attr in handle.iter() {
+            match attr.nla_type().nla_type() {
+                MeshConfigAttr::RetryTimeout => params.retry_timeout = Some(attr.get_payload_as()?),
+                MeshConfigAttr::ConfirmTimeout => {
+                    params.confirm_timeout = Some(attr.get_payload_as()?)
+                }
+                MeshConfigAttr::HoldingTimeout => {
+                    params.holding_timeout = Some(attr.get_payload_as()?)
+                }
+                MeshConfigAttr::MaxPeerLinks => {
+                    params.max_peer_links = Some(attr.get_payload_as()?)
+                }
+                MeshConfigAttr::MaxRetries => params.max_retries = Some(attr.get_payload_as()?),
+                MeshConfigAttr::Ttl => params.ttl = Some(attr.get_payload_as()?),
+                MeshConfigAttr::ElementTtl => params.element_ttl = Some(attr.get_payload_as()?),
+                MeshConfigAttr::AutoOpenPlinks => {
+                    let value: u8 = attr.get_payload_as()?;
+                    params.auto_open_peer_links = Some(value != 0);
+                }
+                MeshConfigAttr::SyncOffsetMaxNeighbor => {
+                    params.sync_offset_max_neighbor = Some(attr.get_payload_as()?)
+                }
+                MeshConfigAttr::HwmpMaxPreqRetries => {
+                    params.hwmp_max_preq_retries = Some(attr.get_payload_as()?)
+                }
+                MeshConfigAttr::PathRefreshTime => {
+                    params.path_refresh_time = Some(attr.get_payload_as()?)
+                }
+                MeshConfigAttr::MinDiscoveryTimeout => {
+                    params.min_discovery_timeout = Some(attr.get_payload_as()?)
+                }
+                MeshConfigAttr::HwmpActivePathTimeout => {
+                    params.hwmp_active_path_timeout = Some(attr.get_payload_as()?)
+                }
+                MeshConfigAttr::HwmpPreqMinInterval => {
+                    params.hwmp_preq_min_interval = Some(attr.get_payload_as()?)
+                }
+                MeshConfigAttr::HwmpPerrMinInterval => {
+                    params.hwmp_perr_min_interval = Some(attr.get_payload_as()?)
+                }
+                MeshConfigAttr::HwmpNetDiamTrvsTime => {
+                    params.hwmp_net_diameter_traversal_time = Some(attr.get_payload_as()?)
+                }
+                MeshConfigAttr::HwmpRootmode => {
+                    params.hwmp_root_mode = Some(attr.get_payload_as()?)
+                }
+                MeshConfigAttr::HwmpRannInterval => {
+                    params.hwmp_rann_interval = Some(attr.get_payload_as()?)
+                }
+                MeshConfigAttr::GateAnnouncements => {
+                    let value: u8 = attr.get_payload_as()?;
+                    params.gate_announcements = Some(value != 0);
+                }
+                MeshConfigAttr::Forwarding => {
+                    let value: u8 = attr.get_payload_as()?;
+                    params.forwarding = Some(value != 0);
+                }
+                MeshConfigAttr::RssiThreshold => {
+                    params.rssi_threshold = Some(attr.get_payload_as()?)
+                }
+                MeshConfigAttr::HtOpmode => params.ht_opmode = Some(attr.get_payload_as()?),
+                MeshConfigAttr::HwmpPathToRootTimeout => {
+                    params.hwmp_path_to_root_timeout = Some(attr.get_payload_as()?)
+                }
+                MeshConfigAttr::HwmpRootInterval => {
+                    params.hwmp_root_interval = Some(attr.get_payload_as()?)
+                }
+                MeshConfigAttr::HwmpConfirmationInterval => {
+                    params.hwmp_confirmation_interval = Some(attr.get_payload_as()?)
+                }
+                MeshConfigAttr::PowerMode => {
+                    let value: NlMeshPowerMode = attr.get_payload_as()?;
+                    params.power_mode = Some(value.into());
+                }
+                MeshConfigAttr::AwakeWindow => params.awake_window = Some(attr.get_payload_as()?),
+                MeshConfigAttr::PlinkTimeout => {
+                    params.peer_link_timeout = Some(attr.get_payload_as()?)
+                }
+                MeshConfigAttr::ConnectedToGate => {
+                    let value: u8 = attr.get_payload_as()?;
+                    params.connected_to_gate = Some(value != 0);
+                }
+                MeshConfigAttr::ConnectedToAs => {
+                    let value: u8 = attr.get_payload_as()?;
+                    params.connected_to_as = Some(value != 0);
+                }
+                MeshConfigAttr::Nolearn => {
+                    let value: u8 = attr.get_payload_as()?;
+                    params.no_learn = Some(value != 0);
+                }
+                unhandled => {
+                    debug!("Unhandled mesh config attribute 'MeshConfigAttr::{unhandled:?}'")
+                }
+            }
+        }
+        Ok(params)
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Mesh power save mode.
+pub enum MeshPowerMode {
+    /// Active mode, the mesh station is always awake.
+    Active,
+    /// Light sleep mode, the mesh station wakes up for neighbor's beacons.
+    LightSleep,
+    /// Deep sleep mode, the mesh station may not wake up for neighbor's beacons.
+    DeepSleep,
+    /// Mesh power mode is not known or not set.
+    Unknown,
+}
+
+impl From<NlMeshPowerMode> for MeshPowerMode {
+    fn from(value: NlMeshPowerMode) -> Self {
+        match value {
+            NlMeshPowerMode::Active => MeshPowerMode::Active,
+            NlMeshPowerMode::LightSleep => MeshPowerMode::LightSleep,
+            NlMeshPowerMode::DeepSleep => MeshPowerMode::DeepSleep,
+            _ => MeshPowerMode::Unknown,
+        }
+    }
+}
+
+impl From<MeshPowerMode> for NlMeshPowerMode {
+    fn from(value: MeshPowerMode) -> Self {
+        match value {
+            MeshPowerMode::Active => NlMeshPowerMode::Active,
+            MeshPowerMode::LightSleep => NlMeshPowerMode::LightSleep,
+            MeshPowerMode::DeepSleep => NlMeshPowerMode::DeepSleep,
+            MeshPowerMode::Unknown => NlMeshPowerMode::Unknown,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Mesh path information returned from netlink.
+pub struct MeshPath {
+    /// Network interface index.
+    pub interface_index: u32,
+    /// Destination MAC address of the path.
+    pub destination: MacAddress,
+    /// MAC address of the next hop towards the destination.
+    pub next_hop: MacAddress,
+    /// Used to indicate consistent snapshots for dumps. This number increases
+    /// whenever the object list being dumped changes.
+    pub generation: u32,
+    /// Number of queued frames for this destination.
+    pub frame_queue_length: Option<u32>,
+    /// Destination sequence number.
+    pub sequence_number: Option<u32>,
+    /// Metric (cost) of the path.
+    pub metric: Option<u32>,
+    /// Time until the path expires.
+    pub expiration_time: Option<Duration>,
+    /// Total path discovery timeout.
+    pub discovery_timeout: Option<Duration>,
+    /// Number of path discovery retries.
+    pub discovery_retries: Option<u8>,
+    /// Hop count to the destination.
+    pub hop_count: Option<u8>,
+    /// Total number of path changes to the destination.
+    pub path_change_count: Option<u32>,
+    /// The path is active.
+    pub active: bool,
+    /// The path discovery process is running.
+    pub resolving: bool,
+    /// The sequence number is valid.
+    pub sequence_number_valid: bool,
+    /// The path is fixed.
+    pub fixed: bool,
+    /// The path discovery process succeeded.
+    pub resolved: bool,
+}
+
+impl TryFrom<&Attrs<'_, Attribute>> for MeshPath {
+    type Error = DeError;
+
+    fn try_from(handle: &Attrs<'_, Attribute>) -> Result<Self, Self::Error> {
+        let mut path = MeshPath::default();
+        let mut mpath_info_attr: Option<Attrs<'_, MpathInfo>> = None;
+        for attr in handle.iter() {
+            match attr.nla_type().nla_type() {
+                Attribute::Ifindex => path.interface_index = attr.get_payload_as()?,
+                Attribute::Mac => path.destination = attr.get_payload_as()?,
+                Attribute::MpathNextHop => path.next_hop = attr.get_payload_as()?,
+                Attribute::Generation => path.generation = attr.get_payload_as()?,
+                Attribute::MpathInfo => {
+                    mpath_info_attr = Some(attr.get_attr_handle()?);
+                }
+                unhandled => debug!("Unhandled mesh path attribute 'Attribute::{unhandled:?}'"),
+            }
+        }
+
+        if let Some(sub_handle) = mpath_info_attr {
+            for sub_attr in sub_handle.iter() {
+                match sub_attr.nla_type().nla_type() {
+                    MpathInfo::FrameQlen => {
+                        path.frame_queue_length = Some(sub_attr.get_payload_as()?);
+                    }
+                    MpathInfo::Sn => {
+                        path.sequence_number = Some(sub_attr.get_payload_as()?);
+                    }
+                    MpathInfo::Metric => {
+                        path.metric = Some(sub_attr.get_payload_as()?);
+                    }
+                    MpathInfo::Exptime => {
+                        let millis: u32 = sub_attr.get_payload_as()?;
+                        path.expiration_time = Some(Duration::from_millis(millis as u64));
+                    }
+                    MpathInfo::Flags => {
+                        let flags = MpathFlags::from_bits_truncate(sub_attr.get_payload_as()?);
+                        path.active = flags.contains(MpathFlags::ACTIVE);
+                        path.resolving = flags.contains(MpathFlags::RESOLVING);
+                        path.sequence_number_valid = flags.contains(MpathFlags::SN_VALID);
+                        path.fixed = flags.contains(MpathFlags::FIXED);
+                        path.resolved = flags.contains(MpathFlags::RESOLVED);
+                    }
+                    MpathInfo::DiscoveryTimeout => {
+                        let millis: u32 = sub_attr.get_payload_as()?;
+                        path.discovery_timeout = Some(Duration::from_millis(millis as u64));
+                    }
+                    MpathInfo::DiscoveryRetries => {
+                        path.discovery_retries = Some(sub_attr.get_payload_as()?);
+                    }
+                    MpathInfo::HopCount => {
+                        path.hop_count = Some(sub_attr.get_payload_as()?);
+                    }
+                    MpathInfo::PathChange => {
+                        path.path_change_count = Some(sub_attr.get_payload_as()?);
+                    }
+                    unhandled => {
+                        debug!("Unhandled mesh path info attribute 'MpathInfo::{unhandled:?}'")
+                    }
+                }
+            }
+        }
+        Ok(path)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Mesh proxy path information returned from netlink.
+///
+/// Maps an external (non-mesh) destination to the mesh station proxying it.
+pub struct MeshProxyPath {
+    /// Network interface index.
+    pub interface_index: u32,
+    /// External destination MAC address.
+    pub destination: MacAddress,
+    /// MAC address of the mesh station acting as a proxy for the destination.
+    pub proxy: MacAddress,
+    /// Used to indicate consistent snapshots for dumps. This number increases
+    /// whenever the object list being dumped changes.
+    pub generation: u32,
+}
+
+impl TryFrom<&Attrs<'_, Attribute>> for MeshProxyPath {
+    type Error = DeError;
+
+    fn try_from(handle: &Attrs<'_, Attribute>) -> Result<Self, Self::Error> {
+        let path: MeshPath = handle.try_into()?;
+        Ok(Self {
+            interface_index: path.interface_index,
+            destination: path.destination,
+            proxy: path.next_hop,
+            generation: path.generation,
+        })
+    }
+}
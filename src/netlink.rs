@@ -1,374 +1,3909 @@
-use neli::consts::nl::NlmF;
+use std::io::Cursor;
+use std::ops::Deref;
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use log::debug;
+use neli::consts::nl::{NlmF, Nlmsg};
 use neli::genl::{AttrTypeBuilder, Genlmsghdr, GenlmsghdrBuilder, NlattrBuilder, NoUserHeader};
-use neli::nl::NlPayload;
-use neli::types::GenlBuffer;
+use neli::nl::{NlPayload, Nlmsghdr, NlmsghdrBuilder};
+use neli::types::{Buffer, GenlBuffer};
+use neli::{FromBytesWithInput, ToBytes};
+use nix::errno::Errno;
 
-use crate::attributes::Attribute;
+use crate::ap::{AclPolicy, ApConfig, BeaconConfig, QosMap};
+use crate::attributes::{
+    Attribute, Band, CoalesceRuleAttr, CqmAttr, MeshConfigAttr, PacketPatternAttr, SarAttr,
+    SarSpecsAttr, TxRateAttr,
+};
 use crate::commands::Command;
-use crate::interface::{ChannelWidth, InterfaceType};
+use crate::error::{ErrorKind, NlError};
+use crate::interface::{
+    BitrateMask, ChannelConfig, ChannelWidth, FrameTx, InterfaceType, MacAddress, TxPower,
+};
+use crate::key::{DefaultKeyType, Key};
+use crate::mesh::MeshParams;
+use crate::reg_domain::RegulatoryHint;
+use crate::station::StationConfig;
+use crate::wiphy::{CoalesceRule, ProtocolFeatures, SarLimit, TxqParams, WiphyParams};
 use crate::MonitorFlags;
 
+use super::attributes::AclPolicy as NlAclPolicy;
 use super::attributes::ChannelWidth as NlChannelWidth;
+use super::attributes::CoalesceCondition as NlCoalesceCondition;
+use super::attributes::HeGuardInterval as NlHeGuardInterval;
+use super::attributes::HiddenSsid as NlHiddenSsid;
 use super::attributes::InterfaceType as NlInterfaceType;
+use super::attributes::KeyType as NlKeyType;
+use super::attributes::MeshPowerMode as NlMeshPowerMode;
+use super::attributes::SarType as NlSarType;
+use super::attributes::TxRateGi as NlTxRateGi;
+use super::attributes::UserRegHintType as NlUserRegHintType;
 
 const NL80211_VERSION: u8 = 1;
+
+/// Generic netlink family id of nl80211, resolved once per process. Family ids
+/// are shared by all network namespaces and only change if the nl80211 module
+/// is reloaded.
+pub(crate) static NL80211_FAMILY_ID: OnceLock<u16> = OnceLock::new();
 pub(crate) type Neli80211Header = Genlmsghdr<Command, Attribute>;
-pub(crate) struct Nl80211Request {
-    pub nl_flags: NlmF,
-    pub nl_payload: NlPayload<u16, Neli80211Header>,
+/// Initial capacity of dump result vectors. Enough for the interfaces or
+/// stations of a typical access point without reallocating.
+pub(crate) const DUMP_CAPACITY_HINT: usize = 16;
+
+/// Build a nl80211 message from a command and its raw attribute buffer.
+pub(crate) fn message_from_attributes(
+    command: Command,
+    attributes: &[u8],
+) -> Result<Neli80211Header, NlError> {
+    let attrs = GenlBuffer::<Attribute, Buffer>::from_bytes_with_input(
+        &mut Cursor::new(attributes),
+        attributes.len(),
+    )?;
+    Ok(
+        GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+            .cmd(command)
+            .version(NL80211_VERSION)
+            .attrs(attrs)
+            .build()?,
+    )
+}
+
+/// Whether wiphy information should be requested as a split dump, given the
+/// result of `GetProtocolFeatures`. Kernels before 3.10 reject that command
+/// with `EOPNOTSUPP` or `EINVAL` and only send unsplit dumps; any other error
+/// is returned.
+pub(crate) fn split_wiphy_dump(
+    features: Result<ProtocolFeatures, NlError>,
+) -> Result<bool, NlError> {
+    match features {
+        Ok(features) => Ok(features.contains(ProtocolFeatures::SPLIT_WIPHY_DUMP)),
+        Err(err)
+            if err.errno() == Some(Errno::EOPNOTSUPP as i32)
+                || err.errno() == Some(Errno::EINVAL as i32) =>
+        {
+            Ok(false)
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// Received nl80211 message with a payload. Dereferences to the payload,
+/// which stays in the received message instead of being copied out of it.
+pub(crate) struct Response(Nlmsghdr<Nlmsg, Neli80211Header>);
+
+impl Response {
+    /// Wrap a received message. Error messages are returned as errors of
+    /// `command`, acknowledgements and empty messages are skipped.
+    pub(crate) fn from_message(
+        mut message: Nlmsghdr<Nlmsg, Neli80211Header>,
+        command: Command,
+    ) -> Option<Result<Self, NlError>> {
+        if let Some(err) = message.get_err() {
+            debug!("Error when reading response: {err}");
+            return Some(Err(NlError::from(err).with_command(command)));
+        }
+        message
+            .get_payload()
+            .is_some()
+            .then_some(Ok(Response(message)))
+    }
+
+    /// Wrap a payload that was not received from the kernel.
+    pub(crate) fn from_payload(payload: Neli80211Header) -> Result<Self, NlError> {
+        let message = NlmsghdrBuilder::default()
+            .nl_type(Nlmsg::UnrecognizedConst(0))
+            .nl_flags(NlmF::empty())
+            .nl_payload(NlPayload::Payload(payload))
+            .build()
+            .map_err(|err| NlError::with_kind(ErrorKind::InvalidArgument, err))?;
+        Ok(Response(message))
+    }
+}
+
+impl Deref for Response {
+    type Target = Neli80211Header;
+
+    fn deref(&self) -> &Neli80211Header {
+        match self.0.nl_payload() {
+            NlPayload::Payload(payload) => payload,
+            _ => unreachable!("responses are only created for messages with a payload"),
+        }
+    }
+}
+
+/// Nl80211 request message.
+///
+/// Requests are sent by the socket methods of the same name. They can also be
+/// built directly to inspect exactly what will be sent.
+pub struct Nl80211Request {
+    pub(crate) nl_flags: NlmF,
+    pub(crate) nl_payload: NlPayload<u16, Neli80211Header>,
 }
 
 impl Nl80211Request {
-    pub fn list_interfaces() -> Self {
-        Self {
+    /// Command of the request.
+    pub fn command(&self) -> Command {
+        match &self.nl_payload {
+            NlPayload::Payload(payload) => *payload.cmd(),
+            _ => Command::Unspec,
+        }
+    }
+
+    /// Whether the request is a dump request returning multiple messages.
+    pub fn is_dump(&self) -> bool {
+        self.nl_flags.contains(NlmF::DUMP)
+    }
+
+    /// Whether the request carries key material, which must not be logged.
+    pub(crate) fn has_key_material(&self) -> bool {
+        matches!(self.command(), Command::NewKey | Command::SetPmk)
+    }
+
+    /// Serialize the generic netlink message of the request, i.e. the bytes
+    /// following the netlink header.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, NlError> {
+        let mut buffer = Cursor::new(Vec::new());
+        self.nl_payload.to_bytes(&mut buffer)?;
+        Ok(buffer.into_inner())
+    }
+
+    pub fn list_interfaces() -> Result<Self, NlError> {
+        Ok(Self {
             nl_flags: NlmF::REQUEST | NlmF::DUMP,
             nl_payload: NlPayload::Payload(
                 GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
                     .cmd(Command::GetInterface)
                     .version(NL80211_VERSION)
-                    .build()
-                    .unwrap(),
+                    .build()?,
             ),
-        }
+        })
     }
 
-    pub fn get_interface(if_index: u32) -> Self {
+    pub fn get_interface(if_index: u32) -> Result<Self, NlError> {
         let attrs = {
             let mut attrs = GenlBuffer::new();
             let attr_type = AttrTypeBuilder::default()
                 .nla_type(Attribute::Ifindex)
-                .build()
-                .unwrap();
+                .build()?;
             attrs.push(
                 NlattrBuilder::default()
                     .nla_type(attr_type)
                     .nla_payload(if_index)
-                    .build()
-                    .unwrap(),
+                    .build()?,
             );
             attrs
         };
-        Self {
+        Ok(Self {
             nl_flags: NlmF::REQUEST | NlmF::DUMP,
             nl_payload: NlPayload::Payload(
                 GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
                     .cmd(Command::GetInterface)
                     .version(NL80211_VERSION)
                     .attrs(attrs)
-                    .build()
-                    .unwrap(),
+                    .build()?,
             ),
-        }
+        })
     }
 
-    pub fn set_interface(if_index: u32, if_type: InterfaceType) -> Self {
+    pub fn get_interface_by_wdev(wdev: u64) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Wdev)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(wdev)
+                    .build()?,
+            );
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::DUMP,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::GetInterface)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
+    }
+
+    pub fn set_interface(if_index: u32, if_type: InterfaceType) -> Result<Self, NlError> {
         let attrs = {
             let mut attrs = GenlBuffer::new();
             let attr_type = AttrTypeBuilder::default()
                 .nla_type(Attribute::Ifindex)
-                .build()
-                .unwrap();
+                .build()?;
             attrs.push(
                 NlattrBuilder::default()
                     .nla_type(attr_type)
                     .nla_payload(if_index)
-                    .build()
-                    .unwrap(),
+                    .build()?,
             );
             let attr_type = AttrTypeBuilder::default()
                 .nla_type(Attribute::Iftype)
-                .build()
-                .unwrap();
+                .build()?;
             attrs.push(
                 NlattrBuilder::default()
                     .nla_type(attr_type)
                     .nla_payload(Into::<NlInterfaceType>::into(if_type))
-                    .build()
-                    .unwrap(),
+                    .build()?,
             );
             attrs
         };
-        Self {
+        Ok(Self {
             nl_flags: NlmF::REQUEST | NlmF::ACK,
             nl_payload: NlPayload::Payload(
                 GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
                     .cmd(Command::SetInterface)
                     .version(NL80211_VERSION)
                     .attrs(attrs)
-                    .build()
-                    .unwrap(),
+                    .build()?,
             ),
-        }
+        })
     }
 
-    pub fn set_monitor_flags(if_index: u32, flags: Vec<MonitorFlags>) -> Self {
+    pub fn set_monitor_flags(if_index: u32, flags: Vec<MonitorFlags>) -> Result<Self, NlError> {
         let attrs = {
             let mut attrs = GenlBuffer::new();
             let attr_type = AttrTypeBuilder::default()
                 .nla_type(Attribute::Ifindex)
-                .build()
-                .unwrap();
+                .build()?;
             attrs.push(
                 NlattrBuilder::default()
                     .nla_type(attr_type)
                     .nla_payload(if_index)
-                    .build()
-                    .unwrap(),
+                    .build()?,
             );
             let attr_type = AttrTypeBuilder::default()
                 .nla_type(Attribute::Iftype)
-                .build()
-                .unwrap();
+                .build()?;
             attrs.push(
                 NlattrBuilder::default()
                     .nla_type(attr_type)
                     .nla_payload(Into::<NlInterfaceType>::into(NlInterfaceType::Monitor))
-                    .build()
-                    .unwrap(),
+                    .build()?,
             );
             let attr_type = AttrTypeBuilder::default()
                 .nla_type(Attribute::MntrFlags)
-                .build()
-                .unwrap();
+                .build()?;
             attrs.push(
                 NlattrBuilder::default()
                     .nla_type(attr_type)
                     .nla_payload(flags)
-                    .build()
-                    .unwrap(),
+                    .build()?,
             );
             attrs
         };
-        Self {
+        Ok(Self {
             nl_flags: NlmF::REQUEST | NlmF::ACK,
             nl_payload: NlPayload::Payload(
                 GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
                     .cmd(Command::SetInterface)
                     .version(NL80211_VERSION)
                     .attrs(attrs)
-                    .build()
-                    .unwrap(),
+                    .build()?,
             ),
-        }
+        })
     }
 
-    pub fn set_channel(if_index: u32, freq: u32, width: ChannelWidth) -> Self {
+    pub fn set_channel(if_index: u32, freq: u32, width: ChannelWidth) -> Result<Self, NlError> {
         let attrs = {
             let mut attrs = GenlBuffer::new();
             let attr_type = AttrTypeBuilder::default()
                 .nla_type(Attribute::Ifindex)
-                .build()
-                .unwrap();
+                .build()?;
             attrs.push(
                 NlattrBuilder::default()
                     .nla_type(attr_type)
                     .nla_payload(if_index)
-                    .build()
-                    .unwrap(),
+                    .build()?,
             );
             let attr_type = AttrTypeBuilder::default()
                 .nla_type(Attribute::WiphyFreq)
-                .build()
-                .unwrap();
+                .build()?;
             attrs.push(
                 NlattrBuilder::default()
                     .nla_type(attr_type)
                     .nla_payload(freq)
-                    .build()
-                    .unwrap(),
+                    .build()?,
             );
             let attr_type = AttrTypeBuilder::default()
                 .nla_type(Attribute::ChannelWidth)
-                .build()
-                .unwrap();
+                .build()?;
             attrs.push(
                 NlattrBuilder::default()
                     .nla_type(attr_type)
                     .nla_payload(Into::<NlChannelWidth>::into(width))
-                    .build()
-                    .unwrap(),
+                    .build()?,
             );
             attrs
         };
-        Self {
+        Ok(Self {
             nl_flags: NlmF::REQUEST | NlmF::ACK,
             nl_payload: NlPayload::Payload(
                 GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
                     .cmd(Command::SetChannel)
                     .version(NL80211_VERSION)
                     .attrs(attrs)
-                    .build()
-                    .unwrap(),
+                    .build()?,
             ),
-        }
+        })
+    }
+
+    pub fn set_channel_config(if_index: u32, channel: ChannelConfig) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Ifindex)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(if_index)
+                    .build()?,
+            );
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::WiphyFreq)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(channel.frequency.0)
+                    .build()?,
+            );
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::ChannelWidth)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(Into::<NlChannelWidth>::into(channel.width))
+                    .build()?,
+            );
+            if let Some(center_frequency1) = channel.center_frequency1 {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::CenterFreq1)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(center_frequency1.0)
+                        .build()?,
+                );
+            }
+            if let Some(center_frequency2) = channel.center_frequency2 {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::CenterFreq2)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(center_frequency2.0)
+                        .build()?,
+                );
+            }
+            if let Some(frequency_offset) = channel.frequency_offset {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::WiphyFreqOffset)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(frequency_offset)
+                        .build()?,
+                );
+            }
+            if let Some(center_frequency1_offset) = channel.center_frequency1_offset {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::CenterFreq1Offset)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(center_frequency1_offset)
+                        .build()?,
+                );
+            }
+            if let Some(edmg) = channel.edmg {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::WiphyEdmgChannels)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(edmg.channels)
+                        .build()?,
+                );
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::WiphyEdmgBwConfig)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(edmg.bw_config)
+                        .build()?,
+                );
+            }
+            if let Some(puncturing_bitmap) = channel.puncturing_bitmap {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::PunctBitmap)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(puncturing_bitmap)
+                        .build()?,
+                );
+            }
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::ACK,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::SetChannel)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
     }
 
-    pub fn list_stations(if_index: u32) -> Self {
+    pub fn list_stations(if_index: u32) -> Result<Self, NlError> {
         let attrs = {
             let mut attrs = GenlBuffer::new();
             let attr_type = AttrTypeBuilder::default()
                 .nla_type(Attribute::Ifindex)
-                .build()
-                .unwrap();
+                .build()?;
             attrs.push(
                 NlattrBuilder::default()
                     .nla_type(attr_type)
                     .nla_payload(if_index)
-                    .build()
-                    .unwrap(),
+                    .build()?,
             );
             attrs
         };
-        Self {
+        Ok(Self {
             nl_flags: NlmF::REQUEST | NlmF::DUMP,
             nl_payload: NlPayload::Payload(
                 GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
                     .cmd(Command::GetStation)
                     .version(NL80211_VERSION)
                     .attrs(attrs)
-                    .build()
-                    .unwrap(),
+                    .build()?,
             ),
-        }
+        })
     }
 
-    pub fn list_physical_devices() -> Self {
+    pub fn list_physical_devices() -> Result<Self, NlError> {
         let attrs = {
             let mut attrs = GenlBuffer::new();
             let attr_type = AttrTypeBuilder::default()
                 .nla_type(Attribute::SplitWiphyDump)
-                .build()
-                .unwrap();
+                .build()?;
             attrs.push(
                 NlattrBuilder::default()
                     .nla_type(attr_type)
                     .nla_payload(())
-                    .build()
-                    .unwrap(),
+                    .build()?,
             );
             attrs
         };
-        Self {
+        Ok(Self {
             nl_flags: NlmF::REQUEST | NlmF::DUMP,
             nl_payload: NlPayload::Payload(
                 GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
                     .cmd(Command::GetWiphy)
                     .version(NL80211_VERSION)
                     .attrs(attrs)
-                    .build()
-                    .unwrap(),
+                    .build()?,
             ),
-        }
+        })
+    }
+
+    /// List physical devices without requesting a split dump, for kernels
+    /// without `ProtocolFeatures::SPLIT_WIPHY_DUMP`.
+    pub fn list_physical_devices_unsplit() -> Result<Self, NlError> {
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::DUMP,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::GetWiphy)
+                    .version(NL80211_VERSION)
+                    .build()?,
+            ),
+        })
+    }
+
+    pub fn get_protocol_features() -> Result<Self, NlError> {
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::ACK,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::GetProtocolFeatures)
+                    .version(NL80211_VERSION)
+                    .build()?,
+            ),
+        })
     }
 
-    pub fn get_physical_device(wiphy_index: u32) -> Self {
+    pub fn get_physical_device(wiphy_index: u32) -> Result<Self, NlError> {
         let attrs = {
             let mut attrs = GenlBuffer::new();
             let attr_type = AttrTypeBuilder::default()
                 .nla_type(Attribute::Wiphy)
-                .build()
-                .unwrap();
+                .build()?;
             attrs.push(
                 NlattrBuilder::default()
                     .nla_type(attr_type)
                     .nla_payload(wiphy_index)
-                    .build()
-                    .unwrap(),
+                    .build()?,
             );
             let attr_type = AttrTypeBuilder::default()
                 .nla_type(Attribute::SplitWiphyDump)
-                .build()
-                .unwrap();
+                .build()?;
             attrs.push(
                 NlattrBuilder::default()
                     .nla_type(attr_type)
                     .nla_payload(())
-                    .build()
-                    .unwrap(),
+                    .build()?,
             );
             attrs
         };
-        Self {
+        Ok(Self {
             nl_flags: NlmF::REQUEST | NlmF::DUMP,
             nl_payload: NlPayload::Payload(
                 GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
                     .cmd(Command::GetWiphy)
                     .version(NL80211_VERSION)
                     .attrs(attrs)
-                    .build()
-                    .unwrap(),
+                    .build()?,
             ),
-        }
+        })
+    }
+
+    /// Get a physical device without requesting a split dump, for kernels
+    /// without `ProtocolFeatures::SPLIT_WIPHY_DUMP`.
+    pub fn get_physical_device_unsplit(wiphy_index: u32) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Wiphy)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(wiphy_index)
+                    .build()?,
+            );
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::DUMP,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::GetWiphy)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
     }
 
-    pub fn get_regulatory_domain() -> Self {
-        Self {
+    pub fn get_regulatory_domain() -> Result<Self, NlError> {
+        Ok(Self {
             nl_flags: NlmF::REQUEST | NlmF::DUMP,
             nl_payload: NlPayload::Payload(
                 GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
                     .cmd(Command::GetReg)
                     .version(NL80211_VERSION)
-                    .build()
-                    .unwrap(),
+                    .build()?,
             ),
-        }
+        })
+    }
+
+    pub fn get_regulatory_domain_for(wiphy_index: u32) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Wiphy)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(wiphy_index)
+                    .build()?,
+            );
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::ACK,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::GetReg)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
+    }
+
+    /// Dump the scan results of an interface.
+    pub fn get_scan(if_index: u32) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Ifindex)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(if_index)
+                    .build()?,
+            );
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::DUMP,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::GetScan)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
     }
 
-    pub fn trigger_scan(if_index: u32) -> Self {
+    pub fn trigger_scan(if_index: u32) -> Result<Self, NlError> {
         let attrs = {
             let mut attrs = GenlBuffer::new();
             let attr_type = AttrTypeBuilder::default()
                 .nla_type(Attribute::Ifindex)
-                .build()
-                .unwrap();
+                .build()?;
             attrs.push(
                 NlattrBuilder::default()
                     .nla_type(attr_type)
                     .nla_payload(if_index)
-                    .build()
-                    .unwrap(),
+                    .build()?,
             );
             attrs
         };
-        Self {
+        Ok(Self {
             nl_flags: NlmF::REQUEST | NlmF::ACK,
             nl_payload: NlPayload::Payload(
                 GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
                     .cmd(Command::TriggerScan)
                     .version(NL80211_VERSION)
                     .attrs(attrs)
-                    .build()
-                    .unwrap(),
+                    .build()?,
             ),
-        }
+        })
+    }
+
+    pub fn trigger_scan_by_wdev(wdev: u64) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Wdev)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(wdev)
+                    .build()?,
+            );
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::ACK,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::TriggerScan)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
     }
 
-    pub fn abort_scan(if_index: u32) -> Self {
+    pub fn abort_scan(if_index: u32) -> Result<Self, NlError> {
         let attrs = {
             let mut attrs = GenlBuffer::new();
             let attr_type = AttrTypeBuilder::default()
                 .nla_type(Attribute::Ifindex)
-                .build()
-                .unwrap();
+                .build()?;
             attrs.push(
                 NlattrBuilder::default()
                     .nla_type(attr_type)
                     .nla_payload(if_index)
-                    .build()
-                    .unwrap(),
+                    .build()?,
             );
             attrs
         };
-        Self {
+        Ok(Self {
             nl_flags: NlmF::REQUEST | NlmF::ACK,
             nl_payload: NlPayload::Payload(
                 GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
                     .cmd(Command::AbortScan)
                     .version(NL80211_VERSION)
                     .attrs(attrs)
-                    .build()
-                    .unwrap(),
+                    .build()?,
             ),
-        }
+        })
+    }
+
+    pub fn abort_scan_by_wdev(wdev: u64) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Wdev)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(wdev)
+                    .build()?,
+            );
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::ACK,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::AbortScan)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
+    }
+
+    pub fn set_pmk(
+        if_index: u32,
+        mac: MacAddress,
+        pmk: &[u8],
+        pmkr0_name: Option<&[u8]>,
+    ) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Ifindex)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(if_index)
+                    .build()?,
+            );
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Mac)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(mac)
+                    .build()?,
+            );
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Pmk)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(Buffer::from(pmk))
+                    .build()?,
+            );
+            if let Some(pmkr0_name) = pmkr0_name {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::Pmkr0Name)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(Buffer::from(pmkr0_name))
+                        .build()?,
+                );
+            }
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::ACK,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::SetPmk)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
+    }
+
+    pub fn del_pmk(if_index: u32, mac: MacAddress) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Ifindex)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(if_index)
+                    .build()?,
+            );
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Mac)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(mac)
+                    .build()?,
+            );
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::ACK,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::DelPmk)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
+    }
+
+    pub fn update_owe_info(
+        if_index: u32,
+        mac: MacAddress,
+        status_code: u16,
+        dh_ie: &[u8],
+    ) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Ifindex)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(if_index)
+                    .build()?,
+            );
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Mac)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(mac)
+                    .build()?,
+            );
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::StatusCode)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(status_code)
+                    .build()?,
+            );
+            let attr_type = AttrTypeBuilder::default().nla_type(Attribute::Ie).build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(Buffer::from(dh_ie))
+                    .build()?,
+            );
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::ACK,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::UpdateOweInfo)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
+    }
+
+    pub fn start_ap(if_index: u32, config: ApConfig) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Ifindex)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(if_index)
+                    .build()?,
+            );
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::BeaconHead)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(Buffer::from(config.beacon_head))
+                    .build()?,
+            );
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::BeaconTail)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(Buffer::from(config.beacon_tail))
+                    .build()?,
+            );
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::BeaconInterval)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(config.beacon_interval)
+                    .build()?,
+            );
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::DtimPeriod)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(config.dtim_period)
+                    .build()?,
+            );
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Ssid)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(Buffer::from(config.ssid.as_bytes()))
+                    .build()?,
+            );
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::HiddenSsid)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(Into::<NlHiddenSsid>::into(config.hidden_ssid))
+                    .build()?,
+            );
+            if let Some(channel) = config.channel {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::WiphyFreq)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(channel.frequency.0)
+                        .build()?,
+                );
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::ChannelWidth)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(Into::<NlChannelWidth>::into(channel.width))
+                        .build()?,
+                );
+                if let Some(center_frequency1) = channel.center_frequency1 {
+                    let attr_type = AttrTypeBuilder::default()
+                        .nla_type(Attribute::CenterFreq1)
+                        .build()?;
+                    attrs.push(
+                        NlattrBuilder::default()
+                            .nla_type(attr_type)
+                            .nla_payload(center_frequency1.0)
+                            .build()?,
+                    );
+                }
+                if let Some(center_frequency2) = channel.center_frequency2 {
+                    let attr_type = AttrTypeBuilder::default()
+                        .nla_type(Attribute::CenterFreq2)
+                        .build()?;
+                    attrs.push(
+                        NlattrBuilder::default()
+                            .nla_type(attr_type)
+                            .nla_payload(center_frequency2.0)
+                            .build()?,
+                    );
+                }
+                if let Some(frequency_offset) = channel.frequency_offset {
+                    let attr_type = AttrTypeBuilder::default()
+                        .nla_type(Attribute::WiphyFreqOffset)
+                        .build()?;
+                    attrs.push(
+                        NlattrBuilder::default()
+                            .nla_type(attr_type)
+                            .nla_payload(frequency_offset)
+                            .build()?,
+                    );
+                }
+                if let Some(center_frequency1_offset) = channel.center_frequency1_offset {
+                    let attr_type = AttrTypeBuilder::default()
+                        .nla_type(Attribute::CenterFreq1Offset)
+                        .build()?;
+                    attrs.push(
+                        NlattrBuilder::default()
+                            .nla_type(attr_type)
+                            .nla_payload(center_frequency1_offset)
+                            .build()?,
+                    );
+                }
+                if let Some(edmg) = channel.edmg {
+                    let attr_type = AttrTypeBuilder::default()
+                        .nla_type(Attribute::WiphyEdmgChannels)
+                        .build()?;
+                    attrs.push(
+                        NlattrBuilder::default()
+                            .nla_type(attr_type)
+                            .nla_payload(edmg.channels)
+                            .build()?,
+                    );
+                    let attr_type = AttrTypeBuilder::default()
+                        .nla_type(Attribute::WiphyEdmgBwConfig)
+                        .build()?;
+                    attrs.push(
+                        NlattrBuilder::default()
+                            .nla_type(attr_type)
+                            .nla_payload(edmg.bw_config)
+                            .build()?,
+                    );
+                }
+                if let Some(puncturing_bitmap) = channel.puncturing_bitmap {
+                    let attr_type = AttrTypeBuilder::default()
+                        .nla_type(Attribute::PunctBitmap)
+                        .build()?;
+                    attrs.push(
+                        NlattrBuilder::default()
+                            .nla_type(attr_type)
+                            .nla_payload(puncturing_bitmap)
+                            .build()?,
+                    );
+                }
+            }
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::ACK,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::StartAp)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
+    }
+
+    pub fn stop_ap(if_index: u32) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Ifindex)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(if_index)
+                    .build()?,
+            );
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::ACK,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::StopAp)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
+    }
+
+    pub fn set_beacon(if_index: u32, config: BeaconConfig) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Ifindex)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(if_index)
+                    .build()?,
+            );
+            if let Some(beacon_head) = config.beacon_head {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::BeaconHead)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(Buffer::from(beacon_head))
+                        .build()?,
+                );
+            }
+            if let Some(beacon_tail) = config.beacon_tail {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::BeaconTail)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(Buffer::from(beacon_tail))
+                        .build()?,
+                );
+            }
+            if let Some(probe_response) = config.probe_response {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::ProbeResp)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(Buffer::from(probe_response))
+                        .build()?,
+                );
+            }
+            if let Some(ie) = config.ie {
+                let attr_type = AttrTypeBuilder::default().nla_type(Attribute::Ie).build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(Buffer::from(ie))
+                        .build()?,
+                );
+            }
+            if let Some(ie_probe_response) = config.ie_probe_response {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::IeProbeResp)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(Buffer::from(ie_probe_response))
+                        .build()?,
+                );
+            }
+            if let Some(ie_assoc_response) = config.ie_assoc_response {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::IeAssocResp)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(Buffer::from(ie_assoc_response))
+                        .build()?,
+                );
+            }
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::ACK,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::SetBeacon)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
+    }
+
+    pub fn new_station(
+        if_index: u32,
+        mac: MacAddress,
+        config: StationConfig,
+    ) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Ifindex)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(if_index)
+                    .build()?,
+            );
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Mac)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(mac)
+                    .build()?,
+            );
+            if let Some(aid) = config.aid {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::StaAid)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(aid)
+                        .build()?,
+                );
+            }
+            if let Some(listen_interval) = config.listen_interval {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::StaListenInterval)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(listen_interval)
+                        .build()?,
+                );
+            }
+            if let Some(supported_rates) = config.supported_rates {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::StaSupportedRates)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(Buffer::from(supported_rates))
+                        .build()?,
+                );
+            }
+            if !config.flags.is_empty() {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::StaFlags2)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(config.flags)
+                        .build()?,
+                );
+            }
+            if let Some(tx_power) = config.tx_power {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::StaTxPowerSetting)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(u32::from(tx_power.setting()) as u8)
+                        .build()?,
+                );
+                if let Some(level) = tx_power.level() {
+                    let attr_type = AttrTypeBuilder::default()
+                        .nla_type(Attribute::StaTxPower)
+                        .build()?;
+                    attrs.push(
+                        NlattrBuilder::default()
+                            .nla_type(attr_type)
+                            .nla_payload(level as i16)
+                            .build()?,
+                    );
+                }
+            }
+            if let Some(airtime_weight) = config.airtime_weight {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::AirtimeWeight)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(airtime_weight)
+                        .build()?,
+                );
+            }
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::ACK,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::NewStation)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
+    }
+
+    pub fn set_station(
+        if_index: u32,
+        mac: MacAddress,
+        config: StationConfig,
+    ) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Ifindex)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(if_index)
+                    .build()?,
+            );
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Mac)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(mac)
+                    .build()?,
+            );
+            if let Some(aid) = config.aid {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::StaAid)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(aid)
+                        .build()?,
+                );
+            }
+            if let Some(listen_interval) = config.listen_interval {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::StaListenInterval)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(listen_interval)
+                        .build()?,
+                );
+            }
+            if let Some(supported_rates) = config.supported_rates {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::StaSupportedRates)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(Buffer::from(supported_rates))
+                        .build()?,
+                );
+            }
+            if !config.flags.is_empty() {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::StaFlags2)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(config.flags)
+                        .build()?,
+                );
+            }
+            if let Some(tx_power) = config.tx_power {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::StaTxPowerSetting)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(u32::from(tx_power.setting()) as u8)
+                        .build()?,
+                );
+                if let Some(level) = tx_power.level() {
+                    let attr_type = AttrTypeBuilder::default()
+                        .nla_type(Attribute::StaTxPower)
+                        .build()?;
+                    attrs.push(
+                        NlattrBuilder::default()
+                            .nla_type(attr_type)
+                            .nla_payload(level as i16)
+                            .build()?,
+                    );
+                }
+            }
+            if let Some(airtime_weight) = config.airtime_weight {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::AirtimeWeight)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(airtime_weight)
+                        .build()?,
+                );
+            }
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::ACK,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::SetStation)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
+    }
+
+    pub fn del_station(if_index: u32, mac: MacAddress) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Ifindex)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(if_index)
+                    .build()?,
+            );
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Mac)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(mac)
+                    .build()?,
+            );
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::ACK,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::DelStation)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
+    }
+
+    pub fn set_mac_acl(
+        if_index: u32,
+        policy: AclPolicy,
+        mac_addresses: Vec<MacAddress>,
+    ) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Ifindex)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(if_index)
+                    .build()?,
+            );
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::AclPolicy)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(Into::<NlAclPolicy>::into(policy))
+                    .build()?,
+            );
+            let mut mac_attrs = GenlBuffer::<u16, Buffer>::new();
+            for (index, mac) in mac_addresses.into_iter().enumerate() {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(index as u16 + 1)
+                    .build()?;
+                mac_attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(mac)
+                        .build()?,
+                );
+            }
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::MacAddrs)
+                .nla_nested(true)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(mac_attrs)
+                    .build()?,
+            );
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::ACK,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::SetMacAcl)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
+    }
+
+    pub fn probe_client(if_index: u32, mac: MacAddress) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Ifindex)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(if_index)
+                    .build()?,
+            );
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Mac)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(mac)
+                    .build()?,
+            );
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::ACK,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::ProbeClient)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
+    }
+
+    pub fn register_beacons(wiphy_index: u32) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Wiphy)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(wiphy_index)
+                    .build()?,
+            );
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::ACK,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::RegisterBeacons)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
+    }
+
+    pub fn get_mesh_config(if_index: u32) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Ifindex)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(if_index)
+                    .build()?,
+            );
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::ACK,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::GetMeshConfig)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
+    }
+
+    pub fn set_mesh_config(if_index: u32, params: MeshParams) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Ifindex)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(if_index)
+                    .build()?,
+            );
+            let mut mesh_attrs = GenlBuffer::<MeshConfigAttr, Buffer>::new();
+            if let Some(retry_timeout) = params.retry_timeout {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(MeshConfigAttr::RetryTimeout)
+                    .build()?;
+                mesh_attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(retry_timeout)
+                        .build()?,
+                );
+            }
+            if let Some(confirm_timeout) = params.confirm_timeout {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(MeshConfigAttr::ConfirmTimeout)
+                    .build()?;
+                mesh_attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(confirm_timeout)
+                        .build()?,
+                );
+            }
+            if let Some(holding_timeout) = params.holding_timeout {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(MeshConfigAttr::HoldingTimeout)
+                    .build()?;
+                mesh_attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(holding_timeout)
+                        .build()?,
+                );
+            }
+            if let Some(max_peer_links) = params.max_peer_links {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(MeshConfigAttr::MaxPeerLinks)
+                    .build()?;
+                mesh_attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(max_peer_links)
+                        .build()?,
+                );
+            }
+            if let Some(max_retries) = params.max_retries {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(MeshConfigAttr::MaxRetries)
+                    .build()?;
+                mesh_attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(max_retries)
+                        .build()?,
+                );
+            }
+            if let Some(ttl) = params.ttl {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(MeshConfigAttr::Ttl)
+                    .build()?;
+                mesh_attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(ttl)
+                        .build()?,
+                );
+            }
+            if let Some(element_ttl) = params.element_ttl {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(MeshConfigAttr::ElementTtl)
+                    .build()?;
+                mesh_attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(element_ttl)
+                        .build()?,
+                );
+            }
+            if let Some(auto_open_peer_links) = params.auto_open_peer_links {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(MeshConfigAttr::AutoOpenPlinks)
+                    .build()?;
+                mesh_attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(u8::from(auto_open_peer_links))
+                        .build()?,
+                );
+            }
+            if let Some(sync_offset_max_neighbor) = params.sync_offset_max_neighbor {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(MeshConfigAttr::SyncOffsetMaxNeighbor)
+                    .build()?;
+                mesh_attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(sync_offset_max_neighbor)
+                        .build()?,
+                );
+            }
+            if let Some(hwmp_max_preq_retries) = params.hwmp_max_preq_retries {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(MeshConfigAttr::HwmpMaxPreqRetries)
+                    .build()?;
+                mesh_attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(hwmp_max_preq_retries)
+                        .build()?,
+                );
+            }
+            if let Some(path_refresh_time) = params.path_refresh_time {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(MeshConfigAttr::PathRefreshTime)
+                    .build()?;
+                mesh_attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(path_refresh_time)
+                        .build()?,
+                );
+            }
+            if let Some(min_discovery_timeout) = params.min_discovery_timeout {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(MeshConfigAttr::MinDiscoveryTimeout)
+                    .build()?;
+                mesh_attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(min_discovery_timeout)
+                        .build()?,
+                );
+            }
+            if let Some(hwmp_active_path_timeout) = params.hwmp_active_path_timeout {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(MeshConfigAttr::HwmpActivePathTimeout)
+                    .build()?;
+                mesh_attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(hwmp_active_path_timeout)
+                        .build()?,
+                );
+            }
+            if let Some(hwmp_preq_min_interval) = params.hwmp_preq_min_interval {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(MeshConfigAttr::HwmpPreqMinInterval)
+                    .build()?;
+                mesh_attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(hwmp_preq_min_interval)
+                        .build()?,
+                );
+            }
+            if let Some(hwmp_perr_min_interval) = params.hwmp_perr_min_interval {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(MeshConfigAttr::HwmpPerrMinInterval)
+                    .build()?;
+                mesh_attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(hwmp_perr_min_interval)
+                        .build()?,
+                );
+            }
+            if let Some(hwmp_net_diameter_traversal_time) = params.hwmp_net_diameter_traversal_time
+            {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(MeshConfigAttr::HwmpNetDiamTrvsTime)
+                    .build()?;
+                mesh_attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(hwmp_net_diameter_traversal_time)
+                        .build()?,
+                );
+            }
+            if let Some(hwmp_root_mode) = params.hwmp_root_mode {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(MeshConfigAttr::HwmpRootmode)
+                    .build()?;
+                mesh_attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(hwmp_root_mode)
+                        .build()?,
+                );
+            }
+            if let Some(hwmp_rann_interval) = params.hwmp_rann_interval {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(MeshConfigAttr::HwmpRannInterval)
+                    .build()?;
+                mesh_attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(hwmp_rann_interval)
+                        .build()?,
+                );
+            }
+            if let Some(gate_announcements) = params.gate_announcements {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(MeshConfigAttr::GateAnnouncements)
+                    .build()?;
+                mesh_attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(u8::from(gate_announcements))
+                        .build()?,
+                );
+            }
+            if let Some(forwarding) = params.forwarding {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(MeshConfigAttr::Forwarding)
+                    .build()?;
+                mesh_attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(u8::from(forwarding))
+                        .build()?,
+                );
+            }
+            if let Some(rssi_threshold) = params.rssi_threshold {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(MeshConfigAttr::RssiThreshold)
+                    .build()?;
+                mesh_attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(rssi_threshold)
+                        .build()?,
+                );
+            }
+            if let Some(ht_opmode) = params.ht_opmode {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(MeshConfigAttr::HtOpmode)
+                    .build()?;
+                mesh_attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(ht_opmode)
+                        .build()?,
+                );
+            }
+            if let Some(hwmp_path_to_root_timeout) = params.hwmp_path_to_root_timeout {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(MeshConfigAttr::HwmpPathToRootTimeout)
+                    .build()?;
+                mesh_attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(hwmp_path_to_root_timeout)
+                        .build()?,
+                );
+            }
+            if let Some(hwmp_root_interval) = params.hwmp_root_interval {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(MeshConfigAttr::HwmpRootInterval)
+                    .build()?;
+                mesh_attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(hwmp_root_interval)
+                        .build()?,
+                );
+            }
+            if let Some(hwmp_confirmation_interval) = params.hwmp_confirmation_interval {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(MeshConfigAttr::HwmpConfirmationInterval)
+                    .build()?;
+                mesh_attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(hwmp_confirmation_interval)
+                        .build()?,
+                );
+            }
+            if let Some(power_mode) = params.power_mode {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(MeshConfigAttr::PowerMode)
+                    .build()?;
+                mesh_attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(Into::<NlMeshPowerMode>::into(power_mode))
+                        .build()?,
+                );
+            }
+            if let Some(awake_window) = params.awake_window {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(MeshConfigAttr::AwakeWindow)
+                    .build()?;
+                mesh_attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(awake_window)
+                        .build()?,
+                );
+            }
+            if let Some(peer_link_timeout) = params.peer_link_timeout {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(MeshConfigAttr::PlinkTimeout)
+                    .build()?;
+                mesh_attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(peer_link_timeout)
+                        .build()?,
+                );
+            }
+            if let Some(connected_to_gate) = params.connected_to_gate {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(MeshConfigAttr::ConnectedToGate)
+                    .build()?;
+                mesh_attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(u8::from(connected_to_gate))
+                        .build()?,
+                );
+            }
+            if let Some(connected_to_as) = params.connected_to_as {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(MeshConfigAttr::ConnectedToAs)
+                    .build()?;
+                mesh_attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(u8::from(connected_to_as))
+                        .build()?,
+                );
+            }
+            if let Some(no_learn) = params.no_learn {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(MeshConfigAttr::Nolearn)
+                    .build()?;
+                mesh_attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(u8::from(no_learn))
+                        .build()?,
+                );
+            }
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::MeshConfig)
+                .nla_nested(true)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(mesh_attrs)
+                    .build()?,
+            );
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::ACK,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::SetMeshConfig)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
+    }
+
+    pub fn list_mesh_paths(if_index: u32) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Ifindex)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(if_index)
+                    .build()?,
+            );
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::DUMP,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::GetMpath)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
+    }
+
+    pub fn get_mesh_path(if_index: u32, destination: MacAddress) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Ifindex)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(if_index)
+                    .build()?,
+            );
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Mac)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(destination)
+                    .build()?,
+            );
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::ACK,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::GetMpath)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
+    }
+
+    pub fn list_mesh_proxy_paths(if_index: u32) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Ifindex)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(if_index)
+                    .build()?,
+            );
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::DUMP,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::GetMpp)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
+    }
+
+    pub fn probe_mesh_link(if_index: u32, mac: MacAddress, frame: &[u8]) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Ifindex)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(if_index)
+                    .build()?,
+            );
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Mac)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(mac)
+                    .build()?,
+            );
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Frame)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(Buffer::from(frame))
+                    .build()?,
+            );
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::ACK,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::ProbeMeshLink)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
+    }
+
+    pub fn add_key(if_index: u32, key: Key) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Ifindex)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(if_index)
+                    .build()?,
+            );
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::KeyIdx)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(key.index)
+                    .build()?,
+            );
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::KeyCipher)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(u32::from(key.cipher))
+                    .build()?,
+            );
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::KeyData)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(Buffer::from(key.data))
+                    .build()?,
+            );
+            if let Some(sequence) = key.sequence {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::KeySeq)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(Buffer::from(sequence))
+                        .build()?,
+                );
+            }
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::KeyType)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(Into::<NlKeyType>::into(key.key_type))
+                    .build()?,
+            );
+            if let Some(mac) = key.mac {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::Mac)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(mac)
+                        .build()?,
+                );
+            }
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::ACK,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::NewKey)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
+    }
+
+    pub fn del_key(if_index: u32, index: u8, mac: Option<MacAddress>) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Ifindex)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(if_index)
+                    .build()?,
+            );
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::KeyIdx)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(index)
+                    .build()?,
+            );
+            if let Some(mac) = mac {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::Mac)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(mac)
+                        .build()?,
+                );
+            }
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::ACK,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::DelKey)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
+    }
+
+    pub fn get_key(if_index: u32, index: u8, mac: Option<MacAddress>) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Ifindex)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(if_index)
+                    .build()?,
+            );
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::KeyIdx)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(index)
+                    .build()?,
+            );
+            if let Some(mac) = mac {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::Mac)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(mac)
+                        .build()?,
+                );
+            }
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::ACK,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::GetKey)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
+    }
+
+    pub fn set_key(
+        if_index: u32,
+        index: u8,
+        default_type: DefaultKeyType,
+    ) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Ifindex)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(if_index)
+                    .build()?,
+            );
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::KeyIdx)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(index)
+                    .build()?,
+            );
+            let default_attr = match default_type {
+                DefaultKeyType::Data => Attribute::KeyDefault,
+                DefaultKeyType::Management => Attribute::KeyDefaultMgmt,
+            };
+            let attr_type = AttrTypeBuilder::default().nla_type(default_attr).build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(())
+                    .build()?,
+            );
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::ACK,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::SetKey)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
+    }
+
+    pub fn set_antenna(wiphy_index: u32, tx_mask: u32, rx_mask: u32) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Wiphy)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(wiphy_index)
+                    .build()?,
+            );
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::WiphyAntennaTx)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(tx_mask)
+                    .build()?,
+            );
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::WiphyAntennaRx)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(rx_mask)
+                    .build()?,
+            );
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::ACK,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::SetWiphy)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
+    }
+
+    pub fn set_sar_limits(wiphy_index: u32, limits: Vec<SarLimit>) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Wiphy)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(wiphy_index)
+                    .build()?,
+            );
+            let mut spec_attrs = GenlBuffer::<u16, Buffer>::new();
+            for (index, limit) in limits.into_iter().enumerate() {
+                let mut limit_attrs = GenlBuffer::<SarSpecsAttr, Buffer>::new();
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(SarSpecsAttr::Power)
+                    .build()?;
+                limit_attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(limit.power)
+                        .build()?,
+                );
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(SarSpecsAttr::RangeIndex)
+                    .build()?;
+                limit_attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(limit.range_index)
+                        .build()?,
+                );
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(index as u16 + 1)
+                    .nla_nested(true)
+                    .build()?;
+                spec_attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(limit_attrs)
+                        .build()?,
+                );
+            }
+            let mut sar_attrs = GenlBuffer::<SarAttr, Buffer>::new();
+            let attr_type = AttrTypeBuilder::default().nla_type(SarAttr::Type).build()?;
+            sar_attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(NlSarType::Power)
+                    .build()?,
+            );
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(SarAttr::Specs)
+                .nla_nested(true)
+                .build()?;
+            sar_attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(spec_attrs)
+                    .build()?,
+            );
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::SarSpec)
+                .nla_nested(true)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(sar_attrs)
+                    .build()?,
+            );
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::ACK,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::SetSarSpecs)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
+    }
+
+    pub fn set_txq_params(wiphy_index: u32, params: TxqParams) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Wiphy)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(wiphy_index)
+                    .build()?,
+            );
+            if let Some(limit) = params.limit {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::TxqLimit)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(limit)
+                        .build()?,
+                );
+            }
+            if let Some(memory_limit) = params.memory_limit {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::TxqMemoryLimit)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(memory_limit)
+                        .build()?,
+                );
+            }
+            if let Some(quantum) = params.quantum {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::TxqQuantum)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(quantum)
+                        .build()?,
+                );
+            }
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::ACK,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::SetWiphy)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
+    }
+
+    pub fn set_station_airtime_weight(
+        if_index: u32,
+        mac: MacAddress,
+        weight: u16,
+    ) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Ifindex)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(if_index)
+                    .build()?,
+            );
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Mac)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(mac)
+                    .build()?,
+            );
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::AirtimeWeight)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(weight)
+                    .build()?,
+            );
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::ACK,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::SetStation)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
+    }
+
+    pub fn set_tx_bitrate_mask(if_index: u32, mask: BitrateMask) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Ifindex)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(if_index)
+                    .build()?,
+            );
+            let mut band_attrs = GenlBuffer::<Band, Buffer>::new();
+            for (band, band_mask) in [
+                (Band::Band2ghz, mask.band_2ghz),
+                (Band::Band5ghz, mask.band_5ghz),
+                (Band::Band6ghz, mask.band_6ghz),
+            ] {
+                let Some(band_mask) = band_mask else {
+                    continue;
+                };
+                let mut rate_attrs = GenlBuffer::<TxRateAttr, Buffer>::new();
+                if let Some(legacy) = band_mask.legacy {
+                    let attr_type = AttrTypeBuilder::default()
+                        .nla_type(TxRateAttr::Legacy)
+                        .build()?;
+                    rate_attrs.push(
+                        NlattrBuilder::default()
+                            .nla_type(attr_type)
+                            .nla_payload(Buffer::from(legacy))
+                            .build()?,
+                    );
+                }
+                if let Some(ht_mcs) = band_mask.ht_mcs {
+                    let attr_type = AttrTypeBuilder::default()
+                        .nla_type(TxRateAttr::Ht)
+                        .build()?;
+                    rate_attrs.push(
+                        NlattrBuilder::default()
+                            .nla_type(attr_type)
+                            .nla_payload(Buffer::from(ht_mcs))
+                            .build()?,
+                    );
+                }
+                if let Some(vht_mcs) = band_mask.vht_mcs {
+                    let attr_type = AttrTypeBuilder::default()
+                        .nla_type(TxRateAttr::Vht)
+                        .build()?;
+                    rate_attrs.push(
+                        NlattrBuilder::default()
+                            .nla_type(attr_type)
+                            .nla_payload(Buffer::from(
+                                vht_mcs
+                                    .iter()
+                                    .flat_map(|mcs| mcs.to_ne_bytes())
+                                    .collect::<Vec<u8>>(),
+                            ))
+                            .build()?,
+                    );
+                }
+                if let Some(he_mcs) = band_mask.he_mcs {
+                    let attr_type = AttrTypeBuilder::default()
+                        .nla_type(TxRateAttr::He)
+                        .build()?;
+                    rate_attrs.push(
+                        NlattrBuilder::default()
+                            .nla_type(attr_type)
+                            .nla_payload(Buffer::from(
+                                he_mcs
+                                    .iter()
+                                    .flat_map(|mcs| mcs.to_ne_bytes())
+                                    .collect::<Vec<u8>>(),
+                            ))
+                            .build()?,
+                    );
+                }
+                if let Some(guard_interval) = band_mask.guard_interval {
+                    let attr_type = AttrTypeBuilder::default()
+                        .nla_type(TxRateAttr::Gi)
+                        .build()?;
+                    rate_attrs.push(
+                        NlattrBuilder::default()
+                            .nla_type(attr_type)
+                            .nla_payload(NlTxRateGi::from(guard_interval))
+                            .build()?,
+                    );
+                }
+                if let Some(he_guard_interval) = band_mask.he_guard_interval {
+                    let attr_type = AttrTypeBuilder::default()
+                        .nla_type(TxRateAttr::HeGi)
+                        .build()?;
+                    rate_attrs.push(
+                        NlattrBuilder::default()
+                            .nla_type(attr_type)
+                            .nla_payload(NlHeGuardInterval::from(he_guard_interval))
+                            .build()?,
+                    );
+                }
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(band)
+                    .nla_nested(true)
+                    .build()?;
+                band_attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(rate_attrs)
+                        .build()?,
+                );
+            }
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::TxRates)
+                .nla_nested(true)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(band_attrs)
+                    .build()?,
+            );
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::ACK,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::SetTxBitrateMask)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
+    }
+
+    pub fn set_4addr(if_index: u32, enabled: bool) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Ifindex)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(if_index)
+                    .build()?,
+            );
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Use4addrFrames)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(enabled as u8)
+                    .build()?,
+            );
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::ACK,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::SetInterface)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
+    }
+
+    pub fn set_cqm_rssi(
+        if_index: u32,
+        thresholds: Vec<i32>,
+        hysteresis: u32,
+    ) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Ifindex)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(if_index)
+                    .build()?,
+            );
+            let mut cqm_attrs = GenlBuffer::<CqmAttr, Buffer>::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(CqmAttr::RssiThold)
+                .build()?;
+            cqm_attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(Buffer::from(
+                        thresholds
+                            .iter()
+                            .flat_map(|threshold| threshold.to_ne_bytes())
+                            .collect::<Vec<u8>>(),
+                    ))
+                    .build()?,
+            );
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(CqmAttr::RssiHyst)
+                .build()?;
+            cqm_attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(hysteresis)
+                    .build()?,
+            );
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Cqm)
+                .nla_nested(true)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(cqm_attrs)
+                    .build()?,
+            );
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::ACK,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::SetCqm)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
+    }
+
+    pub fn set_cqm_txe(
+        if_index: u32,
+        rate: u32,
+        packets: u32,
+        interval: u32,
+    ) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Ifindex)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(if_index)
+                    .build()?,
+            );
+            let mut cqm_attrs = GenlBuffer::<CqmAttr, Buffer>::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(CqmAttr::TxeRate)
+                .build()?;
+            cqm_attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(rate)
+                    .build()?,
+            );
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(CqmAttr::TxePkts)
+                .build()?;
+            cqm_attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(packets)
+                    .build()?,
+            );
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(CqmAttr::TxeIntvl)
+                .build()?;
+            cqm_attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(interval)
+                    .build()?,
+            );
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Cqm)
+                .nla_nested(true)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(cqm_attrs)
+                    .build()?,
+            );
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::ACK,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::SetCqm)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
+    }
+
+    pub fn set_tx_power(if_index: u32, tx_power: TxPower) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Ifindex)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(if_index)
+                    .build()?,
+            );
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::WiphyTxPowerSetting)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(tx_power.setting())
+                    .build()?,
+            );
+            if let Some(level) = tx_power.level() {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::WiphyTxPowerLevel)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(level)
+                        .build()?,
+                );
+            }
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::ACK,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::SetWiphy)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
+    }
+
+    pub fn set_wiphy_params(wiphy_index: u32, params: WiphyParams) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Wiphy)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(wiphy_index)
+                    .build()?,
+            );
+            if let Some(retry_short) = params.retry_short {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::WiphyRetryShort)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(retry_short)
+                        .build()?,
+                );
+            }
+            if let Some(retry_long) = params.retry_long {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::WiphyRetryLong)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(retry_long)
+                        .build()?,
+                );
+            }
+            if let Some(frag_threshold) = params.frag_threshold {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::WiphyFragThreshold)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(frag_threshold)
+                        .build()?,
+                );
+            }
+            if let Some(rts_threshold) = params.rts_threshold {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::WiphyRtsThreshold)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(rts_threshold)
+                        .build()?,
+                );
+            }
+            if let Some(coverage_class) = params.coverage_class {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::WiphyCoverageClass)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(coverage_class)
+                        .build()?,
+                );
+            }
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::ACK,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::SetWiphy)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
+    }
+
+    pub fn set_qos_map(if_index: u32, qos_map: QosMap) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Ifindex)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(if_index)
+                    .build()?,
+            );
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::QosMap)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(Buffer::from(qos_map.payload()))
+                    .build()?,
+            );
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::ACK,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::SetQosMap)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
+    }
+
+    pub fn set_regulatory_domain(
+        alpha2: &str,
+        hint: Option<RegulatoryHint>,
+    ) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::RegAlpha2)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(Buffer::from(alpha2.as_bytes()))
+                    .build()?,
+            );
+            if let Some(hint) = hint {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::UserRegHintType)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(NlUserRegHintType::from(hint))
+                        .build()?,
+                );
+            }
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::ACK,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::ReqSetReg)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
+    }
+
+    pub fn start_radar_detection(if_index: u32, channel: ChannelConfig) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Ifindex)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(if_index)
+                    .build()?,
+            );
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::WiphyFreq)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(channel.frequency.0)
+                    .build()?,
+            );
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::ChannelWidth)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(Into::<NlChannelWidth>::into(channel.width))
+                    .build()?,
+            );
+            if let Some(center_frequency1) = channel.center_frequency1 {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::CenterFreq1)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(center_frequency1.0)
+                        .build()?,
+                );
+            }
+            if let Some(center_frequency2) = channel.center_frequency2 {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::CenterFreq2)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(center_frequency2.0)
+                        .build()?,
+                );
+            }
+            if let Some(frequency_offset) = channel.frequency_offset {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::WiphyFreqOffset)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(frequency_offset)
+                        .build()?,
+                );
+            }
+            if let Some(center_frequency1_offset) = channel.center_frequency1_offset {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::CenterFreq1Offset)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(center_frequency1_offset)
+                        .build()?,
+                );
+            }
+            if let Some(edmg) = channel.edmg {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::WiphyEdmgChannels)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(edmg.channels)
+                        .build()?,
+                );
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::WiphyEdmgBwConfig)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(edmg.bw_config)
+                        .build()?,
+                );
+            }
+            if let Some(puncturing_bitmap) = channel.puncturing_bitmap {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::PunctBitmap)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(puncturing_bitmap)
+                        .build()?,
+                );
+            }
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::ACK,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::RadarDetect)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
+    }
+
+    pub fn get_survey(if_index: u32, radio_stats: bool) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Ifindex)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(if_index)
+                    .build()?,
+            );
+            if radio_stats {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::SurveyRadioStats)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(())
+                        .build()?,
+                );
+            }
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::DUMP,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::GetSurvey)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
+    }
+
+    pub fn remain_on_channel(
+        if_index: u32,
+        frequency: u32,
+        duration: Duration,
+    ) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Ifindex)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(if_index)
+                    .build()?,
+            );
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::WiphyFreq)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(frequency)
+                    .build()?,
+            );
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Duration)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(duration.as_millis() as u32)
+                    .build()?,
+            );
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::ACK,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::RemainOnChannel)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
+    }
+
+    pub fn remain_on_channel_by_wdev(
+        wdev: u64,
+        frequency: u32,
+        duration: Duration,
+    ) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Wdev)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(wdev)
+                    .build()?,
+            );
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::WiphyFreq)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(frequency)
+                    .build()?,
+            );
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Duration)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(duration.as_millis() as u32)
+                    .build()?,
+            );
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::ACK,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::RemainOnChannel)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
+    }
+
+    pub fn cancel_remain_on_channel(if_index: u32, cookie: u64) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Ifindex)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(if_index)
+                    .build()?,
+            );
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Cookie)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(cookie)
+                    .build()?,
+            );
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::ACK,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::CancelRemainOnChannel)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
+    }
+
+    pub fn cancel_remain_on_channel_by_wdev(wdev: u64, cookie: u64) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Wdev)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(wdev)
+                    .build()?,
+            );
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Cookie)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(cookie)
+                    .build()?,
+            );
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::ACK,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::CancelRemainOnChannel)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
+    }
+
+    pub fn send_frame(if_index: u32, frame: FrameTx) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Ifindex)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(if_index)
+                    .build()?,
+            );
+            if let Some(frequency) = frame.frequency {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::WiphyFreq)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(frequency.0)
+                        .build()?,
+                );
+            }
+            if let Some(wait) = frame.wait {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::Duration)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(wait.as_millis() as u32)
+                        .build()?,
+                );
+            }
+            if frame.offchannel_tx_ok {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::OffchannelTxOk)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(())
+                        .build()?,
+                );
+            }
+            if frame.no_cck_rate {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::TxNoCckRate)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(())
+                        .build()?,
+                );
+            }
+            if frame.dont_wait_for_ack {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::DontWaitForAck)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(())
+                        .build()?,
+                );
+            }
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Frame)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(Buffer::from(frame.frame))
+                    .build()?,
+            );
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::ACK,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::Frame)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
+    }
+
+    pub fn send_frame_by_wdev(wdev: u64, frame: FrameTx) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Wdev)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(wdev)
+                    .build()?,
+            );
+            if let Some(frequency) = frame.frequency {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::WiphyFreq)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(frequency.0)
+                        .build()?,
+                );
+            }
+            if let Some(wait) = frame.wait {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::Duration)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(wait.as_millis() as u32)
+                        .build()?,
+                );
+            }
+            if frame.offchannel_tx_ok {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::OffchannelTxOk)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(())
+                        .build()?,
+                );
+            }
+            if frame.no_cck_rate {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::TxNoCckRate)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(())
+                        .build()?,
+                );
+            }
+            if frame.dont_wait_for_ack {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::DontWaitForAck)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(())
+                        .build()?,
+                );
+            }
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Frame)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(Buffer::from(frame.frame))
+                    .build()?,
+            );
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::ACK,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::Frame)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
+    }
+
+    pub fn start_p2p_device(wdev: u64) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Wdev)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(wdev)
+                    .build()?,
+            );
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::ACK,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::StartP2pDevice)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
+    }
+
+    pub fn stop_p2p_device(wdev: u64) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Wdev)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(wdev)
+                    .build()?,
+            );
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::ACK,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::StopP2pDevice)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
+    }
+
+    pub fn get_coalesce(wiphy_index: u32) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Wiphy)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(wiphy_index)
+                    .build()?,
+            );
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::ACK,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::GetCoalesce)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
+    }
+
+    pub fn set_coalesce(wiphy_index: u32, rules: Vec<CoalesceRule>) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Wiphy)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(wiphy_index)
+                    .build()?,
+            );
+            if !rules.is_empty() {
+                let mut rule_list_attrs = GenlBuffer::<u16, Buffer>::new();
+                for (index, rule) in rules.into_iter().enumerate() {
+                    let mut rule_attrs = GenlBuffer::<CoalesceRuleAttr, Buffer>::new();
+                    let attr_type = AttrTypeBuilder::default()
+                        .nla_type(CoalesceRuleAttr::Delay)
+                        .build()?;
+                    rule_attrs.push(
+                        NlattrBuilder::default()
+                            .nla_type(attr_type)
+                            .nla_payload(rule.delay)
+                            .build()?,
+                    );
+                    let attr_type = AttrTypeBuilder::default()
+                        .nla_type(CoalesceRuleAttr::Condition)
+                        .build()?;
+                    rule_attrs.push(
+                        NlattrBuilder::default()
+                            .nla_type(attr_type)
+                            .nla_payload(Into::<NlCoalesceCondition>::into(rule.condition))
+                            .build()?,
+                    );
+                    let mut pattern_list_attrs = GenlBuffer::<u16, Buffer>::new();
+                    for (index, pattern) in rule.patterns.into_iter().enumerate() {
+                        let mut pattern_attrs = GenlBuffer::<PacketPatternAttr, Buffer>::new();
+                        let attr_type = AttrTypeBuilder::default()
+                            .nla_type(PacketPatternAttr::Mask)
+                            .build()?;
+                        pattern_attrs.push(
+                            NlattrBuilder::default()
+                                .nla_type(attr_type)
+                                .nla_payload(Buffer::from(pattern.mask))
+                                .build()?,
+                        );
+                        let attr_type = AttrTypeBuilder::default()
+                            .nla_type(PacketPatternAttr::Pattern)
+                            .build()?;
+                        pattern_attrs.push(
+                            NlattrBuilder::default()
+                                .nla_type(attr_type)
+                                .nla_payload(Buffer::from(pattern.pattern))
+                                .build()?,
+                        );
+                        let attr_type = AttrTypeBuilder::default()
+                            .nla_type(PacketPatternAttr::Offset)
+                            .build()?;
+                        pattern_attrs.push(
+                            NlattrBuilder::default()
+                                .nla_type(attr_type)
+                                .nla_payload(pattern.offset)
+                                .build()?,
+                        );
+                        let attr_type = AttrTypeBuilder::default()
+                            .nla_type(index as u16 + 1)
+                            .nla_nested(true)
+                            .build()?;
+                        pattern_list_attrs.push(
+                            NlattrBuilder::default()
+                                .nla_type(attr_type)
+                                .nla_payload(pattern_attrs)
+                                .build()?,
+                        );
+                    }
+                    let attr_type = AttrTypeBuilder::default()
+                        .nla_type(CoalesceRuleAttr::PktPattern)
+                        .nla_nested(true)
+                        .build()?;
+                    rule_attrs.push(
+                        NlattrBuilder::default()
+                            .nla_type(attr_type)
+                            .nla_payload(pattern_list_attrs)
+                            .build()?,
+                    );
+                    let attr_type = AttrTypeBuilder::default()
+                        .nla_type(index as u16 + 1)
+                        .nla_nested(true)
+                        .build()?;
+                    rule_list_attrs.push(
+                        NlattrBuilder::default()
+                            .nla_type(attr_type)
+                            .nla_payload(rule_attrs)
+                            .build()?,
+                    );
+                }
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::CoalesceRule)
+                    .nla_nested(true)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(rule_list_attrs)
+                        .build()?,
+                );
+            }
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::ACK,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::SetCoalesce)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
+    }
+
+    pub fn update_ft_ies(if_index: u32, mdid: u16, ies: Vec<u8>) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Ifindex)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(if_index)
+                    .build()?,
+            );
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Mdid)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(mdid)
+                    .build()?,
+            );
+            let attr_type = AttrTypeBuilder::default().nla_type(Attribute::Ie).build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(Buffer::from(ies))
+                    .build()?,
+            );
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::ACK,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::UpdateFtIes)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
+    }
+
+    pub fn set_wiphy_dyn_ack(wiphy_index: u32, enabled: bool) -> Result<Self, NlError> {
+        let attrs = {
+            let mut attrs = GenlBuffer::new();
+            let attr_type = AttrTypeBuilder::default()
+                .nla_type(Attribute::Wiphy)
+                .build()?;
+            attrs.push(
+                NlattrBuilder::default()
+                    .nla_type(attr_type)
+                    .nla_payload(wiphy_index)
+                    .build()?,
+            );
+            if enabled {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::WiphyDynAck)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(())
+                        .build()?,
+                );
+            } else {
+                let attr_type = AttrTypeBuilder::default()
+                    .nla_type(Attribute::WiphyCoverageClass)
+                    .build()?;
+                attrs.push(
+                    NlattrBuilder::default()
+                        .nla_type(attr_type)
+                        .nla_payload(0u8)
+                        .build()?,
+                );
+            }
+            attrs
+        };
+        Ok(Self {
+            nl_flags: NlmF::REQUEST | NlmF::ACK,
+            nl_payload: NlPayload::Payload(
+                GenlmsghdrBuilder::<Command, Attribute, NoUserHeader>::default()
+                    .cmd(Command::SetWiphy)
+                    .version(NL80211_VERSION)
+                    .attrs(attrs)
+                    .build()?,
+            ),
+        })
     }
 }
@@ -0,0 +1,52 @@
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::commands::Command;
+use crate::error::NlError;
+
+/// Hook invoked for every request sent by a socket, e.g. to collect metrics.
+pub trait SocketObserver: Send + Sync {
+    /// Called before a request is sent.
+    fn on_request(&self, _command: Command) {}
+
+    /// Called once the responses of a request have been handled. `latency` is
+    /// measured from sending the request.
+    fn on_response(&self, command: Command, outcome: Result<(), &NlError>, latency: Duration);
+}
+
+/// In-flight request reported to an observer when dropped.
+pub(crate) struct Observation {
+    observer: Arc<dyn SocketObserver>,
+    command: Command,
+    start: Instant,
+    error: Option<NlError>,
+}
+
+impl Observation {
+    pub(crate) fn start(observer: &Arc<dyn SocketObserver>, command: Command) -> Self {
+        observer.on_request(command);
+        Self {
+            observer: observer.clone(),
+            command,
+            start: Instant::now(),
+            error: None,
+        }
+    }
+
+    pub(crate) fn record<T>(&mut self, result: &Result<T, NlError>) {
+        if let Err(err) = result {
+            self.error.get_or_insert_with(|| err.clone());
+        }
+    }
+}
+
+impl Drop for Observation {
+    fn drop(&mut self) {
+        let outcome = match &self.error {
+            Some(err) => Err(err),
+            None => Ok(()),
+        };
+        self.observer
+            .on_response(self.command, outcome, self.start.elapsed());
+    }
+}
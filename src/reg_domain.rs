@@ -3,9 +3,12 @@ use neli::attr::Attribute as NeliAttribute;
 use neli::err::DeError;
 
 use super::attributes::Attribute;
+use super::attributes::UserRegHintType as NlUserRegHintType;
 use crate::attributes::{Attrs, RegRuleAttr, RegRuleFlags};
+use crate::units::{Khz, Mbm, Mhz};
 
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Regulatory domain information returned from netlink.
 ///
 /// If wiphy_index is specified and the device has a private regulatory domain,
@@ -20,6 +23,23 @@ pub struct RegulatoryDomain {
     pub wiphy_index: Option<u32>,
     /// Indicates if device is self-managing its regulatory information.
     pub self_managed: bool,
+    /// Regulatory rules of the domain.
+    pub rules: Vec<RegulatoryRule>,
+}
+
+impl RegulatoryDomain {
+    /// Find the rule covering a 20 MHz channel centered at the given frequency.
+    pub fn rule_for_frequency(&self, frequency: Mhz) -> Option<&RegulatoryRule> {
+        find_rule(&self.rules, frequency)
+    }
+}
+
+pub(crate) fn find_rule(rules: &[RegulatoryRule], frequency: Mhz) -> Option<&RegulatoryRule> {
+    let start = Khz::from(Mhz(frequency.0.saturating_sub(10)));
+    let end = Khz::from(Mhz(frequency.0 + 10));
+    rules
+        .iter()
+        .find(|rule| rule.freq_range_start <= start && rule.freq_range_end >= end)
 }
 
 impl TryFrom<&Attrs<'_, Attribute>> for RegulatoryDomain {
@@ -54,27 +74,28 @@ impl TryFrom<&Attrs<'_, Attribute>> for RegulatoryDomain {
                 }
             }
         }
+        reg_domain.rules = reg_rule_attr;
         Ok(reg_domain)
     }
 }
 
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Regulatory rule information returned from netlink.
 pub struct RegulatoryRule {
-    /// Starting frequencry for the regulatory rule in KHz.
+    /// Starting frequencry for the regulatory rule.
     /// This is not a center of frequency but an actual regulatory band edge.
-    pub freq_range_start: u32,
-    /// Ending frequency for the regulatory rule in KHz.
+    pub freq_range_start: Khz,
+    /// Ending frequency for the regulatory rule.
     /// This is not a center a frequency but an actual regulatory band edge.
-    pub freq_range_end: u32,
-    /// Maximum allowed bandwidth for this frequency range, in KHz.
-    pub max_bandwidth: u32,
+    pub freq_range_end: Khz,
+    /// Maximum allowed bandwidth for this frequency range.
+    pub max_bandwidth: Khz,
     /// The maximum allowed antenna gain for a given frequency range.
     /// The value is in mBi (100 * dBi).
     pub max_antenna_gain: u32,
     /// The maximum allowed EIRP for a given frequency range.
-    /// The value is in mBm (100 * dBm).
-    pub max_eirp: u32,
+    pub max_eirp: Mbm,
     /// DFS CAC time in milliseconds.
     /// If not present or 0 default CAC time will be used.
     pub dfs_cac_time: u32,
@@ -147,13 +168,18 @@ impl TryFrom<Attrs<'_, RegRuleAttr>> for RegulatoryRule {
                         reg_rule.no_eht = flags.contains(RegRuleFlags::NO_EHT);
                     }
                 }
-                RegRuleAttr::FreqRangeStart => reg_rule.freq_range_start = attr.get_payload_as()?,
-                RegRuleAttr::FreqRangeEnd => reg_rule.freq_range_end = attr.get_payload_as()?,
-                RegRuleAttr::FreqRangeMaxBw => reg_rule.max_bandwidth = attr.get_payload_as()?,
+                RegRuleAttr::FreqRangeStart => {
+                    reg_rule.freq_range_start = Khz(attr.get_payload_as()?)
+                }
+                RegRuleAttr::FreqRangeEnd => reg_rule.freq_range_end = Khz(attr.get_payload_as()?),
+                RegRuleAttr::FreqRangeMaxBw => reg_rule.max_bandwidth = Khz(attr.get_payload_as()?),
                 RegRuleAttr::PowerRuleMaxAntGain => {
                     reg_rule.max_antenna_gain = attr.get_payload_as()?
                 }
-                RegRuleAttr::PowerRuleMaxEirp => reg_rule.max_eirp = attr.get_payload_as()?,
+                RegRuleAttr::PowerRuleMaxEirp => {
+                    let power: u32 = attr.get_payload_as()?;
+                    reg_rule.max_eirp = Mbm(power as i32);
+                }
                 RegRuleAttr::DfsCacTime => reg_rule.dfs_cac_time = attr.get_payload_as()?,
                 unhandled => {
                     debug!("Unhandled regulatory rule attribute 'Attribute::{unhandled:?}'")
@@ -165,6 +191,7 @@ impl TryFrom<Attrs<'_, RegRuleAttr>> for RegulatoryRule {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Region for regulatory rules which this country abides to when initiating
 /// radiation on DFS channels.
 pub enum DfsRegion {
@@ -178,3 +205,25 @@ pub enum DfsRegion {
     /// Country follows DFS master rules from JP/MKK/Telec.
     JP,
 }
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Source of a regulatory domain change request.
+pub enum RegulatoryHint {
+    /// The request was made by a user.
+    User,
+    /// The request is based on information from a cellular base station.
+    CellBase,
+    /// The device is operating in an indoor environment.
+    Indoor,
+}
+
+impl From<RegulatoryHint> for NlUserRegHintType {
+    fn from(value: RegulatoryHint) -> Self {
+        match value {
+            RegulatoryHint::User => NlUserRegHintType::User,
+            RegulatoryHint::CellBase => NlUserRegHintType::CellBase,
+            RegulatoryHint::Indoor => NlUserRegHintType::Indoor,
+        }
+    }
+}
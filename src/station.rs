@@ -1,17 +1,70 @@
 use std::fmt;
+use std::io::Cursor;
+use std::ops::Deref;
+use std::sync::OnceLock;
 use std::time::Duration;
 
+use bitflags::bitflags;
 use log::debug;
 use neli::attr::Attribute as NeliAttribute;
-use neli::err::DeError;
+use neli::err::{DeError, SerError};
+use neli::types::{Buffer, GenlBuffer};
+use neli::{FromBytesWithInput, Size, ToBytes};
 
+use super::attributes::BssStatus as NlBssStatus;
+use super::attributes::MeshPowerMode as NlMeshPowerMode;
 use super::attributes::{
-    Attribute, BssParam, HeGuardInterval, HeRuAlloc, RateInfo as NlRateInfo, StationInfo, TidStats,
+    Attribute, BssAttr, BssParam, HeGuardInterval, HeRuAlloc, PlinkState, RateInfo as NlRateInfo,
+    StationFlags, StationInfo, TidStats, TxqStats,
 };
-use super::interface::{ChannelWidth, MacAddress, TransmitQueueStats};
-use crate::attributes::Attrs;
+use super::interface::{
+    ChannelWidth, MacAddress, Ssid, TransmitQueueStats, TxPower, WirelessInterface,
+};
+use crate::attributes::{nested_attributes, Attrs};
+use crate::error::NlError;
+use crate::mesh::MeshPowerMode;
+use crate::units::Mhz;
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+/// Per-chain signal strengths in dBm. Up to four chains are stored inline
+/// without a heap allocation when the `smallvec` feature is enabled.
+pub struct ChainSignal(ChainSignalStorage);
+
+#[cfg(feature = "smallvec")]
+type ChainSignalStorage = smallvec::SmallVec<[u8; 4]>;
+#[cfg(not(feature = "smallvec"))]
+type ChainSignalStorage = Vec<u8>;
+
+impl Deref for ChainSignal {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl AsRef<[u8]> for ChainSignal {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl From<&[u8]> for ChainSignal {
+    fn from(value: &[u8]) -> Self {
+        Self(ChainSignalStorage::from(value))
+    }
+}
+
+impl From<Vec<u8>> for ChainSignal {
+    fn from(value: Vec<u8>) -> Self {
+        Self(ChainSignalStorage::from(value))
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Station information returned from netlink.
 pub struct WirelessStation {
     /// Network interface index.
@@ -28,7 +81,7 @@ pub struct WirelessStation {
     /// Signal strength average for beacons only in dBm.
     pub beacon_average_signal: Option<u8>,
     /// Per-chain signal strength of last PPDU in dBm.
-    pub chain_signal: Vec<u8>,
+    pub chain_signal: ChainSignal,
     /// Time since the station was last connected.
     pub connected_time: Option<Duration>,
     /// Time since last activity.
@@ -77,16 +130,143 @@ pub struct WirelessStation {
     pub rx_bitrate: Option<RateInfo>,
     // Transmit bitrate information.
     pub tx_bitrate: Option<RateInfo>,
+    /// Mesh local link ID of the peer link.
+    pub mesh_local_link_id: Option<u16>,
+    /// Mesh peer link ID of the peer link.
+    pub mesh_peer_link_id: Option<u16>,
+    /// Mesh peer link state.
+    pub peer_link_state: Option<PeerLinkState>,
+    /// Local mesh station link-specific power mode.
+    pub local_power_mode: Option<MeshPowerMode>,
+    /// Peer mesh station link-specific power mode.
+    pub peer_power_mode: Option<MeshPowerMode>,
+    /// Neighbor mesh station power save mode towards non-peer stations.
+    pub non_peer_power_mode: Option<MeshPowerMode>,
+    /// Airtime link metric of a mesh station.
+    pub airtime_link_metric: Option<u32>,
+    /// Airtime weight used by the airtime fairness scheduler.
+    pub airtime_weight: Option<u16>,
+    /// Mesh station is connected to a mesh gate.
+    pub connected_to_gate: Option<bool>,
+    /// Mesh station is connected to an authentication server.
+    pub connected_to_as: Option<bool>,
+    /// Station is authorized (802.1X).
+    pub authorized: Option<bool>,
+    /// Station is authenticated.
+    pub authenticated: Option<bool>,
+    /// Station is associated.
+    pub associated: Option<bool>,
+    /// Station is capable of receiving frames with short barker preamble.
+    pub short_preamble: Option<bool>,
+    /// Station is WME/QoS capable.
+    pub wme: Option<bool>,
+    /// Station uses management frame protection.
+    pub mfp: Option<bool>,
+    /// Station is a TDLS peer.
+    pub tdls_peer: Option<bool>,
+    /// Attributes not decoded by this library as raw attribute type and
+    /// payload pairs. Only collected with `StationFields::RAW_ATTRIBUTES`.
+    pub raw_attributes: Vec<(u16, Vec<u8>)>,
+    /// Station information (`Attribute::StaInfo`) attributes not decoded by
+    /// this library as raw attribute type and payload pairs. Only collected
+    /// with `StationFields::RAW_ATTRIBUTES`.
+    pub raw_station_info: Vec<(u16, Vec<u8>)>,
 }
 
 impl TryFrom<&Attrs<'_, Attribute>> for WirelessStation {
     type Error = DeError;
 
     fn try_from(handle: &Attrs<'_, Attribute>) -> Result<Self, Self::Error> {
+        Self::from_attributes(handle, StationFields::default())
+    }
+}
+
+impl WirelessStation {
+    /// Copy of the station with only the station information in `fields`.
+    /// The interface index and MAC address are always kept.
+    pub fn project(&self, fields: StationFields) -> Self {
+        let mut station = Self {
+            interface_index: self.interface_index,
+            mac: self.mac,
+            ..Default::default()
+        };
+        if fields.contains(StationFields::SIGNAL) {
+            station.signal = self.signal;
+            station.average_signal = self.average_signal;
+            station.beacon_average_signal = self.beacon_average_signal;
+            station.chain_signal = self.chain_signal.clone();
+        }
+        if fields.contains(StationFields::TIMES) {
+            station.connected_time = self.connected_time;
+            station.inactive_time = self.inactive_time;
+            station.associated_at_boot_time = self.associated_at_boot_time;
+        }
+        if fields.contains(StationFields::COUNTERS) {
+            station.rx_bytes = self.rx_bytes;
+            station.tx_bytes = self.tx_bytes;
+            station.rx_bytes64 = self.rx_bytes64;
+            station.tx_bytes64 = self.tx_bytes64;
+            station.rx_duration = self.rx_duration;
+            station.tx_duration = self.tx_duration;
+            station.rx_packets = self.rx_packets;
+            station.tx_packets = self.tx_packets;
+            station.tx_retries = self.tx_retries;
+            station.tx_failed = self.tx_failed;
+            station.beacon_loss = self.beacon_loss;
+            station.rx_drop_misc = self.rx_drop_misc;
+            station.beacon_rx = self.beacon_rx;
+        }
+        if fields.contains(StationFields::FLAGS) {
+            station.authorized = self.authorized;
+            station.authenticated = self.authenticated;
+            station.associated = self.associated;
+            station.short_preamble = self.short_preamble;
+            station.wme = self.wme;
+            station.mfp = self.mfp;
+            station.tdls_peer = self.tdls_peer;
+        }
+        if fields.contains(StationFields::RX_BITRATE) {
+            station.rx_bitrate = self.rx_bitrate.clone();
+        }
+        if fields.contains(StationFields::TX_BITRATE) {
+            station.tx_bitrate = self.tx_bitrate.clone();
+        }
+        if fields.contains(StationFields::TID_STATS) {
+            station.tid_stats = self.tid_stats.clone();
+        }
+        if fields.contains(StationFields::BSS_PARAM) {
+            station.bss_cts_protection = self.bss_cts_protection;
+            station.bss_short_preamble = self.bss_short_preamble;
+            station.bss_short_slot_time = self.bss_short_slot_time;
+            station.bss_dtim_period = self.bss_dtim_period;
+            station.bss_beacon_interval = self.bss_beacon_interval;
+        }
+        if fields.contains(StationFields::MESH) {
+            station.mesh_local_link_id = self.mesh_local_link_id;
+            station.mesh_peer_link_id = self.mesh_peer_link_id;
+            station.peer_link_state = self.peer_link_state;
+            station.local_power_mode = self.local_power_mode;
+            station.peer_power_mode = self.peer_power_mode;
+            station.non_peer_power_mode = self.non_peer_power_mode;
+            station.airtime_link_metric = self.airtime_link_metric;
+            station.airtime_weight = self.airtime_weight;
+            station.connected_to_gate = self.connected_to_gate;
+            station.connected_to_as = self.connected_to_as;
+        }
+        if fields.contains(StationFields::RAW_ATTRIBUTES) {
+            station.raw_attributes = self.raw_attributes.clone();
+            station.raw_station_info = self.raw_station_info.clone();
+        }
+        station
+    }
+
+    /// Decode a station, skipping station information not in `fields`.
+    pub(crate) fn from_attributes(
+        handle: &Attrs<'_, Attribute>,
+        fields: StationFields,
+    ) -> Result<Self, DeError> {
         let mut station = WirelessStation::default();
         let mut station_info_attr: Option<Attrs<'_, StationInfo>> = None;
-        let mut tid_stats_attr: Option<Attrs<'_, u16>> = None;
-        let mut bss_param_attr: Option<Attrs<'_, BssParam>> = None;
         for attr in handle.iter() {
             match attr.nla_type().nla_type() {
                 Attribute::Ifindex => station.interface_index = attr.get_payload_as()?,
@@ -95,163 +275,602 @@ impl TryFrom<&Attrs<'_, Attribute>> for WirelessStation {
                 Attribute::StaInfo => {
                     station_info_attr = Some(attr.get_attr_handle()?);
                 }
-                unhandled => debug!("Unhandled station attribute 'Attribute::{unhandled:?}'"),
+                unhandled => {
+                    debug!("Unhandled station attribute 'Attribute::{unhandled:?}'");
+                    if fields.contains(StationFields::RAW_ATTRIBUTES) {
+                        station
+                            .raw_attributes
+                            .push((unhandled.into(), attr.payload().as_ref().to_vec()));
+                    }
+                }
             }
         }
 
         if let Some(sub_handle) = station_info_attr {
-            for sub_attr in sub_handle.iter() {
-                match sub_attr.nla_type().nla_type() {
-                    StationInfo::Signal => {
-                        station.signal = Some(sub_attr.get_payload_as()?);
-                    }
-                    StationInfo::SignalAvg => {
-                        station.average_signal = Some(sub_attr.get_payload_as()?);
-                    }
-                    StationInfo::BeaconSignalAvg => {
-                        station.beacon_average_signal = Some(sub_attr.get_payload_as()?);
-                    }
-                    StationInfo::ChainSignal => {
-                        station.chain_signal = sub_attr.payload().as_ref().to_vec();
-                    }
-                    StationInfo::ConnectedTime => {
-                        let secs: u32 = sub_attr.get_payload_as()?;
-                        station.connected_time = Some(Duration::from_secs(secs as u64));
-                    }
-                    StationInfo::InactiveTime => {
-                        let millis: u32 = sub_attr.get_payload_as()?;
-                        station.inactive_time = Some(Duration::from_millis(millis as u64));
-                    }
-                    StationInfo::AssocAtBootTime => {
-                        let millis: u64 = sub_attr.get_payload_as()?;
-                        station.associated_at_boot_time = Some(Duration::from_nanos(millis));
-                    }
-                    StationInfo::RxBytes => {
-                        station.rx_bytes = Some(sub_attr.get_payload_as()?);
-                    }
-                    StationInfo::TxBytes => {
-                        station.tx_bytes = Some(sub_attr.get_payload_as()?);
-                    }
-                    StationInfo::RxBytes64 => {
-                        station.rx_bytes64 = Some(sub_attr.get_payload_as()?);
-                    }
-                    StationInfo::TxBytes64 => {
-                        station.tx_bytes64 = Some(sub_attr.get_payload_as()?);
-                    }
-                    StationInfo::RxDuration => {
-                        let millis: u64 = sub_attr.get_payload_as()?;
-                        station.rx_duration = Some(Duration::from_millis(millis));
-                    }
-                    StationInfo::TxDuration => {
-                        let millis: u64 = sub_attr.get_payload_as()?;
-                        station.tx_duration = Some(Duration::from_millis(millis));
-                    }
-                    StationInfo::RxPackets => {
-                        station.rx_packets = Some(sub_attr.get_payload_as()?);
-                    }
-                    StationInfo::TxPackets => {
-                        station.tx_packets = Some(sub_attr.get_payload_as()?);
-                    }
-                    StationInfo::TxRetries => {
-                        station.tx_retries = Some(sub_attr.get_payload_as()?);
-                    }
-                    StationInfo::TxFailed => {
-                        station.tx_failed = Some(sub_attr.get_payload_as()?);
-                    }
-                    StationInfo::BeaconLoss => {
-                        station.beacon_loss = Some(sub_attr.get_payload_as()?);
+            station.decode_station_info(&sub_handle, fields)?;
+        }
+        Ok(station)
+    }
+
+    /// Decode the nested station information attributes in `fields`.
+    fn decode_station_info(
+        &mut self,
+        sub_handle: &Attrs<'_, StationInfo>,
+        fields: StationFields,
+    ) -> Result<(), DeError> {
+        let mut tid_stats_attr: Option<Attrs<'_, u16>> = None;
+        let mut bss_param_attr: Option<Attrs<'_, BssParam>> = None;
+        for sub_attr in sub_handle.iter() {
+            let sub_type = sub_attr.nla_type().nla_type();
+            if !fields.contains(StationFields::of(sub_type)) {
+                continue;
+            }
+            match sub_type {
+                StationInfo::Signal => {
+                    self.signal = Some(sub_attr.get_payload_as()?);
+                }
+                StationInfo::SignalAvg => {
+                    self.average_signal = Some(sub_attr.get_payload_as()?);
+                }
+                StationInfo::BeaconSignalAvg => {
+                    self.beacon_average_signal = Some(sub_attr.get_payload_as()?);
+                }
+                StationInfo::ChainSignal => {
+                    self.chain_signal = ChainSignal::from(sub_attr.payload().as_ref());
+                }
+                StationInfo::ConnectedTime => {
+                    let secs: u32 = sub_attr.get_payload_as()?;
+                    self.connected_time = Some(Duration::from_secs(secs as u64));
+                }
+                StationInfo::InactiveTime => {
+                    let millis: u32 = sub_attr.get_payload_as()?;
+                    self.inactive_time = Some(Duration::from_millis(millis as u64));
+                }
+                StationInfo::AssocAtBootTime => {
+                    let millis: u64 = sub_attr.get_payload_as()?;
+                    self.associated_at_boot_time = Some(Duration::from_nanos(millis));
+                }
+                StationInfo::RxBytes => {
+                    self.rx_bytes = Some(sub_attr.get_payload_as()?);
+                }
+                StationInfo::TxBytes => {
+                    self.tx_bytes = Some(sub_attr.get_payload_as()?);
+                }
+                StationInfo::RxBytes64 => {
+                    self.rx_bytes64 = Some(sub_attr.get_payload_as()?);
+                }
+                StationInfo::TxBytes64 => {
+                    self.tx_bytes64 = Some(sub_attr.get_payload_as()?);
+                }
+                StationInfo::RxDuration => {
+                    let millis: u64 = sub_attr.get_payload_as()?;
+                    self.rx_duration = Some(Duration::from_millis(millis));
+                }
+                StationInfo::TxDuration => {
+                    let millis: u64 = sub_attr.get_payload_as()?;
+                    self.tx_duration = Some(Duration::from_millis(millis));
+                }
+                StationInfo::RxPackets => {
+                    self.rx_packets = Some(sub_attr.get_payload_as()?);
+                }
+                StationInfo::TxPackets => {
+                    self.tx_packets = Some(sub_attr.get_payload_as()?);
+                }
+                StationInfo::TxRetries => {
+                    self.tx_retries = Some(sub_attr.get_payload_as()?);
+                }
+                StationInfo::TxFailed => {
+                    self.tx_failed = Some(sub_attr.get_payload_as()?);
+                }
+                StationInfo::BeaconLoss => {
+                    self.beacon_loss = Some(sub_attr.get_payload_as()?);
+                }
+                StationInfo::RxDropMisc => {
+                    self.rx_drop_misc = Some(sub_attr.get_payload_as()?);
+                }
+                StationInfo::BeaconRx => {
+                    self.beacon_rx = Some(sub_attr.get_payload_as()?);
+                }
+                StationInfo::StaFlags => {
+                    let flags = StationFlagUpdate::from_payload(sub_attr.payload().as_ref());
+                    self.authorized = flags.authorized;
+                    self.authenticated = flags.authenticated;
+                    self.associated = flags.associated;
+                    self.short_preamble = flags.short_preamble;
+                    self.wme = flags.wme;
+                    self.mfp = flags.mfp;
+                    self.tdls_peer = flags.tdls_peer;
+                }
+                StationInfo::RxBitrate => {
+                    let sub_handle = sub_attr.get_attr_handle()?;
+                    self.rx_bitrate = Some(sub_handle.try_into()?);
+                }
+                StationInfo::TxBitrate => {
+                    let sub_handle = sub_attr.get_attr_handle()?;
+                    self.tx_bitrate = Some(sub_handle.try_into()?);
+                }
+                StationInfo::TidStats => {
+                    tid_stats_attr = Some(sub_attr.get_attr_handle()?);
+                }
+                StationInfo::BssParam => {
+                    bss_param_attr = Some(sub_attr.get_attr_handle()?);
+                }
+                StationInfo::Llid => {
+                    self.mesh_local_link_id = Some(sub_attr.get_payload_as()?);
+                }
+                StationInfo::Plid => {
+                    self.mesh_peer_link_id = Some(sub_attr.get_payload_as()?);
+                }
+                StationInfo::PlinkState => {
+                    let state: PlinkState = sub_attr.get_payload_as()?;
+                    self.peer_link_state = Some(state.into());
+                }
+                StationInfo::LocalPm => {
+                    let mode: NlMeshPowerMode = sub_attr.get_payload_as()?;
+                    self.local_power_mode = Some(mode.into());
+                }
+                StationInfo::PeerPm => {
+                    let mode: NlMeshPowerMode = sub_attr.get_payload_as()?;
+                    self.peer_power_mode = Some(mode.into());
+                }
+                StationInfo::NonpeerPm => {
+                    let mode: NlMeshPowerMode = sub_attr.get_payload_as()?;
+                    self.non_peer_power_mode = Some(mode.into());
+                }
+                StationInfo::ConnectedToGate => {
+                    let connected: u8 = sub_attr.get_payload_as()?;
+                    self.connected_to_gate = Some(connected != 0);
+                }
+                StationInfo::ConnectedToAs => {
+                    let connected: u8 = sub_attr.get_payload_as()?;
+                    self.connected_to_as = Some(connected != 0);
+                }
+                StationInfo::AirtimeWeight => {
+                    self.airtime_weight = Some(sub_attr.get_payload_as()?);
+                }
+                StationInfo::AirtimeLinkMetric => {
+                    self.airtime_link_metric = Some(sub_attr.get_payload_as()?);
+                }
+                unhandled => {
+                    debug!("Unhandled station info attribute 'StationInfo::{unhandled:?}'");
+                    if fields.contains(StationFields::RAW_ATTRIBUTES) {
+                        self.raw_station_info
+                            .push((unhandled.into(), sub_attr.payload().as_ref().to_vec()));
                     }
-                    StationInfo::RxDropMisc => {
-                        station.rx_drop_misc = Some(sub_attr.get_payload_as()?);
+                }
+            }
+        }
+
+        if let Some(sub_handle) = tid_stats_attr {
+            let mut all_tid_stats: [TrafficIdStats; 17] = Default::default();
+            for sub_attr in sub_handle.iter() {
+                let nested_handle = sub_attr.get_attr_handle()?;
+                let mut tid_stats = TrafficIdStats::new(*sub_attr.nla_type().nla_type());
+                for tid_attr in nested_handle.iter() {
+                    match tid_attr.nla_type().nla_type() {
+                        TidStats::RxMsdu => {
+                            tid_stats.rx_msdu = Some(tid_attr.get_payload_as()?);
+                        }
+                        TidStats::TxMsdu => {
+                            tid_stats.tx_msdu = Some(tid_attr.get_payload_as()?);
+                        }
+                        TidStats::TxMsduRetries => {
+                            tid_stats.tx_msdu_retries = Some(tid_attr.get_payload_as()?);
+                        }
+                        TidStats::TxMsduFailed => {
+                            tid_stats.tx_msdu_failed = Some(tid_attr.get_payload_as()?);
+                        }
+                        TidStats::Pad => (), // Attribute used for padding for 64-bit alignment.
+                        TidStats::TxqStats => {
+                            let txq_handle: Attrs<'_, TxqStats> = tid_attr.get_attr_handle()?;
+                            tid_stats.txq_stats = Some(txq_handle.try_into()?);
+                        }
+                        unhandled => {
+                            debug!("Unhandled tid stats attribute 'TidStats::{unhandled:?}'")
+                        }
                     }
-                    StationInfo::BeaconRx => {
-                        station.beacon_rx = Some(sub_attr.get_payload_as()?);
+                }
+                all_tid_stats[*sub_attr.nla_type().nla_type() as usize - 1] = tid_stats;
+            }
+            self.tid_stats = Some(all_tid_stats);
+        }
+
+        if let Some(sub_handle) = bss_param_attr {
+            self.bss_cts_protection = Some(false);
+            self.bss_short_preamble = Some(false);
+            self.bss_short_slot_time = Some(false);
+            for sub_attr in sub_handle.iter() {
+                match sub_attr.nla_type().nla_type() {
+                    BssParam::CtsProt => {
+                        self.bss_cts_protection = Some(true);
                     }
-                    StationInfo::StaFlags => (), // TODO: Get station flags
-                    StationInfo::RxBitrate => {
-                        let sub_handle = sub_attr.get_attr_handle()?;
-                        station.rx_bitrate = Some(sub_handle.try_into()?);
+                    BssParam::ShortPreamble => {
+                        self.bss_short_preamble = Some(true);
                     }
-                    StationInfo::TxBitrate => {
-                        let sub_handle = sub_attr.get_attr_handle()?;
-                        station.tx_bitrate = Some(sub_handle.try_into()?);
+                    BssParam::ShortSlotTime => {
+                        self.bss_short_slot_time = Some(true);
                     }
-                    StationInfo::TidStats => {
-                        tid_stats_attr = Some(sub_attr.get_attr_handle()?);
+                    BssParam::DtimPeriod => {
+                        self.bss_dtim_period = Some(sub_attr.get_payload_as()?);
                     }
-                    StationInfo::BssParam => {
-                        bss_param_attr = Some(sub_attr.get_attr_handle()?);
+                    BssParam::BeaconInterval => {
+                        self.bss_beacon_interval = Some(sub_attr.get_payload_as()?);
                     }
                     unhandled => {
-                        debug!("Unhandled station info attribute 'StationInfo::{unhandled:?}'",)
+                        debug!("Unhandled BSS param attribute 'BssParam::{unhandled:?}'")
                     }
                 }
             }
+        }
+        Ok(())
+    }
+}
 
-            if let Some(sub_handle) = tid_stats_attr {
-                let mut all_tid_stats: [TrafficIdStats; 17] = Default::default();
-                for sub_attr in sub_handle.iter() {
-                    let nested_handle = sub_attr.get_attr_handle()?;
-                    for tid_attr in nested_handle.iter() {
-                        let mut tid_stats = TrafficIdStats::new(*sub_attr.nla_type().nla_type());
-                        match tid_attr.nla_type().nla_type() {
-                            TidStats::RxMsdu => {
-                                tid_stats.rx_msdu = Some(tid_attr.get_payload_as()?);
-                            }
-                            TidStats::TxMsdu => {
-                                tid_stats.tx_msdu = Some(tid_attr.get_payload_as()?);
-                            }
-                            TidStats::TxMsduRetries => {
-                                tid_stats.tx_msdu_retries = Some(tid_attr.get_payload_as()?);
-                            }
-                            TidStats::TxMsduFailed => {
-                                tid_stats.tx_msdu_failed = Some(tid_attr.get_payload_as()?);
-                            }
-                            TidStats::Pad => (), // Attribute used for padding for 64-bit alignment.
-                            TidStats::TxqStats => (), // TODO: Get txq stats.
-                            unhandled => {
-                                debug!("Unhandled tid stats attribute 'TidStats::{unhandled:?}'")
-                            }
-                        }
-                        all_tid_stats[*sub_attr.nla_type().nla_type() as usize - 1] = tid_stats;
-                    }
-                }
-                station.tid_stats = Some(all_tid_stats);
+#[derive(Debug, Clone)]
+/// Station with its station information decoded on first access.
+///
+/// Only interface index, MAC address and generation are decoded when the
+/// station is received, and the raw station information attribute is kept.
+/// Signal strengths are read directly from it, and the complete
+/// `WirelessStation` is decoded from it once when first requested.
+///
+/// Laziness is limited to station dumps, which are polled frequently and
+/// carry the bulk of their data in the station information. Physical device
+/// dumps are comparatively rare, so their bands and other nested attributes
+/// are always decoded eagerly.
+pub struct LazyStation {
+    /// Network interface index.
+    pub interface_index: u32,
+    /// Station MAC address (BSSID).
+    pub mac: MacAddress,
+    /// Used to indicate consistent snapshots for dumps.
+    pub generation: u32,
+    station_info: Vec<u8>,
+    station: OnceLock<WirelessStation>,
+}
+
+impl LazyStation {
+    /// Signal strength of last received PPDU in dBm.
+    pub fn signal(&self) -> Option<u8> {
+        self.station_info(StationInfo::Signal)?.first().copied()
+    }
+
+    /// Signal strength average in dBm.
+    pub fn average_signal(&self) -> Option<u8> {
+        self.station_info(StationInfo::SignalAvg)?.first().copied()
+    }
+
+    /// Decode the complete station. The result is cached, so the station
+    /// information is decoded only once.
+    pub fn station(&self) -> Result<&WirelessStation, NlError> {
+        if let Some(station) = self.station.get() {
+            return Ok(station);
+        }
+        let mut station = WirelessStation {
+            interface_index: self.interface_index,
+            mac: self.mac,
+            generation: self.generation,
+            ..Default::default()
+        };
+        let station_info = GenlBuffer::<StationInfo, Buffer>::from_bytes_with_input(
+            &mut Cursor::new(&self.station_info),
+            self.station_info.len(),
+        )?;
+        station.decode_station_info(&Attrs::new(station_info), StationFields::default())?;
+        Ok(self.station.get_or_init(|| station))
+    }
+
+    /// Raw payload of a station information attribute.
+    fn station_info(&self, attr: StationInfo) -> Option<&[u8]> {
+        let attr = u16::from(attr);
+        nested_attributes(&self.station_info)
+            .find(|(t, _)| *t == attr)
+            .map(|(_, payload)| payload)
+    }
+}
+
+impl TryFrom<&Attrs<'_, Attribute>> for LazyStation {
+    type Error = DeError;
+
+    fn try_from(handle: &Attrs<'_, Attribute>) -> Result<Self, Self::Error> {
+        let mut station = LazyStation {
+            interface_index: 0,
+            mac: MacAddress::default(),
+            generation: 0,
+            station_info: Vec::new(),
+            station: OnceLock::new(),
+        };
+        for attr in handle.iter() {
+            match attr.nla_type().nla_type() {
+                Attribute::Ifindex => station.interface_index = attr.get_payload_as()?,
+                Attribute::Mac => station.mac = attr.get_payload_as()?,
+                Attribute::Generation => station.generation = attr.get_payload_as()?,
+                Attribute::StaInfo => station.station_info = attr.payload().as_ref().to_vec(),
+                _ => (),
             }
+        }
+        Ok(station)
+    }
+}
 
-            if let Some(sub_handle) = bss_param_attr {
-                station.bss_cts_protection = Some(false);
-                station.bss_short_preamble = Some(false);
-                station.bss_short_slot_time = Some(false);
-                for sub_attr in sub_handle.iter() {
-                    match sub_attr.nla_type().nla_type() {
-                        BssParam::CtsProt => {
-                            station.bss_cts_protection = Some(true);
-                        }
-                        BssParam::ShortPreamble => {
-                            station.bss_short_preamble = Some(true);
-                        }
-                        BssParam::ShortSlotTime => {
-                            station.bss_short_slot_time = Some(true);
-                        }
-                        BssParam::DtimPeriod => {
-                            station.bss_dtim_period = Some(sub_attr.get_payload_as()?);
-                        }
-                        BssParam::BeaconInterval => {
-                            station.bss_beacon_interval = Some(sub_attr.get_payload_as()?);
-                        }
-                        unhandled => {
-                            debug!("Unhandled BSS param attribute 'BssParam::{unhandled:?}'")
-                        }
-                    }
+bitflags! {
+    /// Groups of station information to decode.
+    ///
+    /// Interface index, MAC address and generation are always decoded.
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+    pub struct StationFields: u32 {
+        /// Signal strengths, including per-chain signal.
+        const SIGNAL         = 1<<0;
+        /// Connected, inactive and association times.
+        const TIMES          = 1<<1;
+        /// Byte, packet, retry, beacon and airtime duration counters.
+        const COUNTERS       = 1<<2;
+        /// Station flags such as authorized and associated.
+        const FLAGS          = 1<<3;
+        /// Receive bitrate information.
+        const RX_BITRATE     = 1<<4;
+        /// Transmit bitrate information.
+        const TX_BITRATE     = 1<<5;
+        /// Per TID statistics.
+        const TID_STATS      = 1<<6;
+        /// BSS parameters.
+        const BSS_PARAM      = 1<<7;
+        /// Mesh peer link, power mode and airtime information.
+        const MESH           = 1<<8;
+        /// Attributes not decoded by this library, including station
+        /// information attributes.
+        const RAW_ATTRIBUTES = 1<<9;
+    }
+}
+
+impl Default for StationFields {
+    /// All decoded station information. Undecoded attributes are only
+    /// collected by default with the `raw-attributes` feature.
+    fn default() -> Self {
+        if cfg!(feature = "raw-attributes") {
+            Self::all()
+        } else {
+            Self::all().difference(Self::RAW_ATTRIBUTES)
+        }
+    }
+}
+
+impl StationFields {
+    /// Group of a station information attribute. Attributes that are not
+    /// decoded belong to no group.
+    fn of(attr: &StationInfo) -> Self {
+        match attr {
+            StationInfo::Signal
+            | StationInfo::SignalAvg
+            | StationInfo::BeaconSignalAvg
+            | StationInfo::ChainSignal => Self::SIGNAL,
+            StationInfo::ConnectedTime
+            | StationInfo::InactiveTime
+            | StationInfo::AssocAtBootTime => Self::TIMES,
+            StationInfo::RxBytes
+            | StationInfo::TxBytes
+            | StationInfo::RxBytes64
+            | StationInfo::TxBytes64
+            | StationInfo::RxDuration
+            | StationInfo::TxDuration
+            | StationInfo::RxPackets
+            | StationInfo::TxPackets
+            | StationInfo::TxRetries
+            | StationInfo::TxFailed
+            | StationInfo::BeaconLoss
+            | StationInfo::RxDropMisc
+            | StationInfo::BeaconRx => Self::COUNTERS,
+            StationInfo::StaFlags => Self::FLAGS,
+            StationInfo::RxBitrate => Self::RX_BITRATE,
+            StationInfo::TxBitrate => Self::TX_BITRATE,
+            StationInfo::TidStats => Self::TID_STATS,
+            StationInfo::BssParam => Self::BSS_PARAM,
+            StationInfo::Llid
+            | StationInfo::Plid
+            | StationInfo::PlinkState
+            | StationInfo::LocalPm
+            | StationInfo::PeerPm
+            | StationInfo::NonpeerPm
+            | StationInfo::ConnectedToGate
+            | StationInfo::ConnectedToAs
+            | StationInfo::AirtimeWeight
+            | StationInfo::AirtimeLinkMetric => Self::MESH,
+            _ => Self::empty(),
+        }
+    }
+}
+
+#[derive(Clone)]
+/// Station counters borrowed from a received netlink message.
+///
+/// Only the statistics typically read when polling are decoded, without
+/// copying variable length payloads. Use `to_owned_station` to decode the
+/// complete `WirelessStation`.
+pub struct WirelessStationRef<'a> {
+    /// Network interface index.
+    pub interface_index: u32,
+    /// Station MAC address (BSSID).
+    pub mac: MacAddress,
+    /// Used to indicate consistent snapshots for dumps.
+    pub generation: u32,
+    /// Signal strength of last received PPDU in dBm.
+    pub signal: Option<u8>,
+    /// Signal strength average in dBm.
+    pub average_signal: Option<u8>,
+    /// Signal strength average for beacons only in dBm.
+    pub beacon_average_signal: Option<u8>,
+    /// Per-chain signal strength of last PPDU in dBm.
+    pub chain_signal: &'a [u8],
+    /// Time since the station was last connected.
+    pub connected_time: Option<Duration>,
+    /// Time since last activity.
+    pub inactive_time: Option<Duration>,
+    /// Total received bytes (MPDU length) from this station.
+    pub rx_bytes: Option<u32>,
+    /// Total transmitted bytes (MPDU length) to this station.
+    pub tx_bytes: Option<u32>,
+    /// Total received bytes (MPDU length) from this station.
+    pub rx_bytes64: Option<u64>,
+    /// Total transmitted bytes (MPDU length) to this station.
+    pub tx_bytes64: Option<u64>,
+    /// Total received packet (MSDUs and MMPDUs) from this station.
+    pub rx_packets: Option<u32>,
+    /// Total transmitted packet (MSDUs and MMPDUs) to this station.
+    pub tx_packets: Option<u32>,
+    /// Total retries (MPDUs) to this station.
+    pub tx_retries: Option<u32>,
+    /// Total failed packets (MPDUs) to this station.
+    pub tx_failed: Option<u32>,
+    /// Count of times beacon loss was detected.
+    pub beacon_loss: Option<u32>,
+    /// Total received packets dropped for unspecified reasons.
+    pub rx_drop_misc: Option<u64>,
+    /// Number of beacons received from this station.
+    pub beacon_rx: Option<u64>,
+    attributes: &'a Attrs<'a, Attribute>,
+}
+
+impl WirelessStationRef<'_> {
+    /// Decode all station attributes into an owned `WirelessStation`.
+    pub fn to_owned_station(&self) -> Result<WirelessStation, DeError> {
+        WirelessStation::try_from(self.attributes)
+    }
+}
+
+impl fmt::Debug for WirelessStationRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WirelessStationRef")
+            .field("interface_index", &self.interface_index)
+            .field("mac", &self.mac)
+            .field("generation", &self.generation)
+            .field("signal", &self.signal)
+            .field("average_signal", &self.average_signal)
+            .field("beacon_average_signal", &self.beacon_average_signal)
+            .field("chain_signal", &self.chain_signal)
+            .field("connected_time", &self.connected_time)
+            .field("inactive_time", &self.inactive_time)
+            .field("rx_bytes", &self.rx_bytes)
+            .field("tx_bytes", &self.tx_bytes)
+            .field("rx_bytes64", &self.rx_bytes64)
+            .field("tx_bytes64", &self.tx_bytes64)
+            .field("rx_packets", &self.rx_packets)
+            .field("tx_packets", &self.tx_packets)
+            .field("tx_retries", &self.tx_retries)
+            .field("tx_failed", &self.tx_failed)
+            .field("beacon_loss", &self.beacon_loss)
+            .field("rx_drop_misc", &self.rx_drop_misc)
+            .field("beacon_rx", &self.beacon_rx)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a> TryFrom<&'a Attrs<'a, Attribute>> for WirelessStationRef<'a> {
+    type Error = DeError;
+
+    fn try_from(handle: &'a Attrs<'a, Attribute>) -> Result<Self, Self::Error> {
+        let mut station = WirelessStationRef {
+            interface_index: 0,
+            mac: MacAddress::default(),
+            generation: 0,
+            signal: None,
+            average_signal: None,
+            beacon_average_signal: None,
+            chain_signal: &[],
+            connected_time: None,
+            inactive_time: None,
+            rx_bytes: None,
+            tx_bytes: None,
+            rx_bytes64: None,
+            tx_bytes64: None,
+            rx_packets: None,
+            tx_packets: None,
+            tx_retries: None,
+            tx_failed: None,
+            beacon_loss: None,
+            rx_drop_misc: None,
+            beacon_rx: None,
+            attributes: handle,
+        };
+        let mut station_info: &[u8] = &[];
+        for attr in handle.iter() {
+            match attr.nla_type().nla_type() {
+                Attribute::Ifindex => station.interface_index = attr.get_payload_as()?,
+                Attribute::Mac => station.mac = attr.get_payload_as()?,
+                Attribute::Generation => station.generation = attr.get_payload_as()?,
+                Attribute::StaInfo => station_info = attr.payload().as_ref(),
+                _ => (),
+            }
+        }
+
+        for (sub_type, payload) in nested_attributes(station_info) {
+            match StationInfo::from(sub_type) {
+                StationInfo::Signal => station.signal = Some(payload_u8(payload)?),
+                StationInfo::SignalAvg => station.average_signal = Some(payload_u8(payload)?),
+                StationInfo::BeaconSignalAvg => {
+                    station.beacon_average_signal = Some(payload_u8(payload)?);
+                }
+                StationInfo::ChainSignal => station.chain_signal = payload,
+                StationInfo::ConnectedTime => {
+                    let secs = u32::from_ne_bytes(payload_array(payload)?);
+                    station.connected_time = Some(Duration::from_secs(secs as u64));
+                }
+                StationInfo::InactiveTime => {
+                    let millis = u32::from_ne_bytes(payload_array(payload)?);
+                    station.inactive_time = Some(Duration::from_millis(millis as u64));
+                }
+                StationInfo::RxBytes => {
+                    station.rx_bytes = Some(u32::from_ne_bytes(payload_array(payload)?));
+                }
+                StationInfo::TxBytes => {
+                    station.tx_bytes = Some(u32::from_ne_bytes(payload_array(payload)?));
+                }
+                StationInfo::RxBytes64 => {
+                    station.rx_bytes64 = Some(u64::from_ne_bytes(payload_array(payload)?));
+                }
+                StationInfo::TxBytes64 => {
+                    station.tx_bytes64 = Some(u64::from_ne_bytes(payload_array(payload)?));
+                }
+                StationInfo::RxPackets => {
+                    station.rx_packets = Some(u32::from_ne_bytes(payload_array(payload)?));
+                }
+                StationInfo::TxPackets => {
+                    station.tx_packets = Some(u32::from_ne_bytes(payload_array(payload)?));
                 }
+                StationInfo::TxRetries => {
+                    station.tx_retries = Some(u32::from_ne_bytes(payload_array(payload)?));
+                }
+                StationInfo::TxFailed => {
+                    station.tx_failed = Some(u32::from_ne_bytes(payload_array(payload)?));
+                }
+                StationInfo::BeaconLoss => {
+                    station.beacon_loss = Some(u32::from_ne_bytes(payload_array(payload)?));
+                }
+                StationInfo::RxDropMisc => {
+                    station.rx_drop_misc = Some(u64::from_ne_bytes(payload_array(payload)?));
+                }
+                StationInfo::BeaconRx => {
+                    station.beacon_rx = Some(u64::from_ne_bytes(payload_array(payload)?));
+                }
+                _ => (),
             }
         }
         Ok(station)
     }
 }
 
-#[derive(Debug, Clone, Default)]
+fn payload_u8(payload: &[u8]) -> Result<u8, DeError> {
+    Ok(payload_array::<1>(payload)?[0])
+}
+
+fn payload_array<const N: usize>(payload: &[u8]) -> Result<[u8; N], DeError> {
+    payload
+        .get(..N)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| DeError::new("Station info attribute payload is too short"))
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Traffic identifier statistics.
 pub struct TrafficIdStats {
     /// TID number 1-16 and 17 for non-QoS traffic.
@@ -277,7 +896,85 @@ impl TrafficIdStats {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Change of station statistics between two samples.
+pub struct StationDelta {
+    /// Time between the samples.
+    pub elapsed: Duration,
+    /// Received bytes per second.
+    pub rx_bytes_per_second: Option<f64>,
+    /// Transmitted bytes per second.
+    pub tx_bytes_per_second: Option<f64>,
+    /// Received packets per second.
+    pub rx_packets_per_second: Option<f64>,
+    /// Transmitted packets per second.
+    pub tx_packets_per_second: Option<f64>,
+    /// Transmit retries per second.
+    pub tx_retries_per_second: Option<f64>,
+    /// Failed transmissions per second.
+    pub tx_failed_per_second: Option<f64>,
+    /// Ratio of retries to transmitted packets between the samples.
+    pub retry_ratio: Option<f64>,
+    /// Change of signal strength in dB. Uses the average signal when available.
+    pub signal_change: Option<i16>,
+}
+
+impl StationDelta {
+    /// Calculate the change between two samples of the same station taken
+    /// `elapsed` apart.
+    ///
+    /// 64-bit byte counters are used when both samples have them. 32-bit
+    /// counters are assumed to have wrapped around if they decreased. Rates
+    /// are `None` if a counter is missing from either sample, or if `elapsed`
+    /// is zero.
+    pub fn new(previous: &WirelessStation, current: &WirelessStation, elapsed: Duration) -> Self {
+        let rate = |delta: Option<u64>| {
+            let seconds = elapsed.as_secs_f64();
+            delta.filter(|_| seconds > 0.0).map(|d| d as f64 / seconds)
+        };
+        let rx_bytes = match (previous.rx_bytes64, current.rx_bytes64) {
+            // A 64-bit counter does not wrap around, so a decrease means it was reset.
+            (Some(previous), Some(current)) => current.checked_sub(previous),
+            _ => counter_delta32(previous.rx_bytes, current.rx_bytes),
+        };
+        let tx_bytes = match (previous.tx_bytes64, current.tx_bytes64) {
+            (Some(previous), Some(current)) => current.checked_sub(previous),
+            _ => counter_delta32(previous.tx_bytes, current.tx_bytes),
+        };
+        let tx_packets = counter_delta32(previous.tx_packets, current.tx_packets);
+        let tx_retries = counter_delta32(previous.tx_retries, current.tx_retries);
+        let retry_ratio = match (tx_retries, tx_packets) {
+            (Some(retries), Some(packets)) if packets > 0 => Some(retries as f64 / packets as f64),
+            _ => None,
+        };
+        let signal = |station: &WirelessStation| station.average_signal.or(station.signal);
+        let signal_change = match (signal(previous), signal(current)) {
+            // Signal strength is a signed dBm value.
+            (Some(previous), Some(current)) => Some(current as i8 as i16 - previous as i8 as i16),
+            _ => None,
+        };
+
+        Self {
+            elapsed,
+            rx_bytes_per_second: rate(rx_bytes),
+            tx_bytes_per_second: rate(tx_bytes),
+            rx_packets_per_second: rate(counter_delta32(previous.rx_packets, current.rx_packets)),
+            tx_packets_per_second: rate(tx_packets),
+            tx_retries_per_second: rate(tx_retries),
+            tx_failed_per_second: rate(counter_delta32(previous.tx_failed, current.tx_failed)),
+            retry_ratio,
+            signal_change,
+        }
+    }
+}
+
+fn counter_delta32(previous: Option<u32>, current: Option<u32>) -> Option<u64> {
+    Some(current?.wrapping_sub(previous?) as u64)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Station bitrate information.
 pub struct RateInfo {
     /// Bitrate in 100kbit/s.
@@ -416,6 +1113,7 @@ impl TryFrom<Attrs<'_, NlRateInfo>> for RateInfo {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Connection or frame type.
 pub enum ConnectionType {
     /// High Throughput (802.11n).
@@ -441,6 +1139,7 @@ impl fmt::Display for ConnectionType {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Wifi connection guard intervals.
 pub enum GuardIntervals {
     /// 0.4 microseconds.
@@ -455,7 +1154,45 @@ pub enum GuardIntervals {
     Unknown,
 }
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Mesh peer link state.
+pub enum PeerLinkState {
+    /// Initial state of a non existent peer link.
+    Listen,
+    /// Peer link open frame has been sent to the peer.
+    OpenSent,
+    /// Peer link open frame has been received from the peer.
+    OpenReceived,
+    /// Peer link confirm frame has been received from the peer.
+    ConfirmReceived,
+    /// Peer link is established.
+    Established,
+    /// Peer link is being closed or cancelled.
+    Holding,
+    /// All frames transmitted from the peer are discarded.
+    Blocked,
+    /// Kernel returned an unknown peer link state.
+    Unknown,
+}
+
+impl From<PlinkState> for PeerLinkState {
+    fn from(value: PlinkState) -> Self {
+        match value {
+            PlinkState::Listen => PeerLinkState::Listen,
+            PlinkState::OpnSnt => PeerLinkState::OpenSent,
+            PlinkState::OpnRcvd => PeerLinkState::OpenReceived,
+            PlinkState::CnfRcvd => PeerLinkState::ConfirmReceived,
+            PlinkState::Estab => PeerLinkState::Established,
+            PlinkState::Holding => PeerLinkState::Holding,
+            PlinkState::Blocked => PeerLinkState::Blocked,
+            PlinkState::UnrecognizedConst(_) => PeerLinkState::Unknown,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// HE RU allocation values.
 pub enum HeRuAllocation {
     /// 26-tone RU allocation.
@@ -475,3 +1212,302 @@ pub enum HeRuAllocation {
     /// Unknown RU allocation.
     Unknown,
 }
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Current association information of a station mode interface.
+pub struct LinkStatus {
+    /// SSID of the connected network.
+    pub ssid: Option<Ssid>,
+    /// BSSID of the associated access point.
+    pub bssid: MacAddress,
+    /// Operating channel frequency.
+    pub frequency: Option<Mhz>,
+    /// Signal strength of last received PPDU in dBm.
+    pub signal: Option<u8>,
+    /// Receive bitrate information.
+    pub rx_bitrate: Option<RateInfo>,
+    /// Transmit bitrate information.
+    pub tx_bitrate: Option<RateInfo>,
+    /// Time since the station was connected.
+    pub connected_time: Option<Duration>,
+}
+
+impl LinkStatus {
+    pub(crate) fn new(interface: WirelessInterface, station: WirelessStation) -> Self {
+        Self {
+            ssid: interface.ssid,
+            bssid: station.mac,
+            frequency: interface.frequency,
+            signal: station.signal,
+            rx_bitrate: station.rx_bitrate,
+            tx_bitrate: station.tx_bitrate,
+            connected_time: station.connected_time,
+        }
+    }
+}
+
+/// BSSID of a scan result if the interface is associated with the BSS.
+pub(crate) fn associated_bssid(
+    handle: &Attrs<'_, Attribute>,
+) -> Result<Option<MacAddress>, DeError> {
+    for attr in handle.iter() {
+        if attr.nla_type().nla_type() != &Attribute::Bss {
+            continue;
+        }
+        let bss: Attrs<'_, BssAttr> = attr.get_attr_handle()?;
+        return match bss.get_attr_payload_as::<NlBssStatus>(BssAttr::Status) {
+            Ok(NlBssStatus::Associated) => Ok(Some(bss.get_attr_payload_as(BssAttr::Bssid)?)),
+            _ => Ok(None),
+        };
+    }
+    Ok(None)
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Station parameters used when adding or modifying a station in AP mode.
+pub struct StationConfig {
+    /// Association ID of the station.
+    pub aid: Option<u16>,
+    /// Listen interval of the station.
+    pub listen_interval: Option<u16>,
+    /// Supported rates of the station as in the Supported Rates element.
+    pub supported_rates: Option<Vec<u8>>,
+    /// Station flags to update.
+    pub flags: StationFlagUpdate,
+    /// Transmit power towards the station. Only `TxPower::Automatic` and
+    /// `TxPower::Limited` are supported for stations.
+    pub tx_power: Option<TxPower>,
+    /// Airtime weight used by the airtime fairness scheduler.
+    pub airtime_weight: Option<u16>,
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Station flag update.
+///
+/// Flags that are `None` are left unchanged.
+pub struct StationFlagUpdate {
+    /// Station is authorized (802.1X).
+    pub authorized: Option<bool>,
+    /// Station is capable of receiving frames with short barker preamble.
+    pub short_preamble: Option<bool>,
+    /// Station is WME/QoS capable.
+    pub wme: Option<bool>,
+    /// Station uses management frame protection.
+    pub mfp: Option<bool>,
+    /// Station is authenticated.
+    pub authenticated: Option<bool>,
+    /// Station is a TDLS peer.
+    pub tdls_peer: Option<bool>,
+    /// Station is associated.
+    pub associated: Option<bool>,
+}
+
+impl StationFlagUpdate {
+    fn mask_and_set(&self) -> (StationFlags, StationFlags) {
+        let mut mask = StationFlags::empty();
+        let mut set = StationFlags::empty();
+        for (value, flag) in [
+            (self.authorized, StationFlags::AUTHORIZED),
+            (self.short_preamble, StationFlags::SHORT_PREAMBLE),
+            (self.wme, StationFlags::WME),
+            (self.mfp, StationFlags::MFP),
+            (self.authenticated, StationFlags::AUTHENTICATED),
+            (self.tdls_peer, StationFlags::TDLS_PEER),
+            (self.associated, StationFlags::ASSOCIATED),
+        ] {
+            if let Some(value) = value {
+                mask |= flag;
+                set.set(flag, value);
+            }
+        }
+        (mask, set)
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.mask_and_set().0.is_empty()
+    }
+
+    /// Decode a `nl80211_sta_flag_update` struct. Flags not included in the
+    /// mask are left as `None`.
+    pub(crate) fn from_payload(payload: &[u8]) -> Self {
+        let mut update = StationFlagUpdate::default();
+        if payload.len() < 8 {
+            return update;
+        }
+        let mask = StationFlags::from_bits_truncate(u32::from_ne_bytes([
+            payload[0], payload[1], payload[2], payload[3],
+        ]));
+        let set = StationFlags::from_bits_truncate(u32::from_ne_bytes([
+            payload[4], payload[5], payload[6], payload[7],
+        ]));
+        let value = |flag| mask.contains(flag).then(|| set.contains(flag));
+        update.authorized = value(StationFlags::AUTHORIZED);
+        update.short_preamble = value(StationFlags::SHORT_PREAMBLE);
+        update.wme = value(StationFlags::WME);
+        update.mfp = value(StationFlags::MFP);
+        update.authenticated = value(StationFlags::AUTHENTICATED);
+        update.tdls_peer = value(StationFlags::TDLS_PEER);
+        update.associated = value(StationFlags::ASSOCIATED);
+        update
+    }
+}
+
+impl ToBytes for StationFlagUpdate {
+    fn to_bytes(&self, buffer: &mut std::io::Cursor<Vec<u8>>) -> Result<(), SerError> {
+        let (mask, set) = self.mask_and_set();
+        mask.bits().to_bytes(buffer)?;
+        set.bits().to_bytes(buffer)
+    }
+}
+
+impl Size for StationFlagUpdate {
+    fn unpadded_size(&self) -> usize {
+        2 * std::mem::size_of::<u32>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_station_delta_rates() {
+        let previous = WirelessStation {
+            rx_bytes: Some(1000),
+            tx_bytes: Some(2000),
+            rx_packets: Some(10),
+            tx_packets: Some(20),
+            tx_retries: Some(2),
+            tx_failed: Some(0),
+            signal: Some(-60i8 as u8),
+            ..Default::default()
+        };
+        let current = WirelessStation {
+            rx_bytes: Some(3000),
+            tx_bytes: Some(6000),
+            rx_packets: Some(30),
+            tx_packets: Some(60),
+            tx_retries: Some(6),
+            tx_failed: Some(2),
+            signal: Some(-55i8 as u8),
+            ..Default::default()
+        };
+        let delta = StationDelta::new(&previous, &current, Duration::from_secs(2));
+        assert_eq!(delta.rx_bytes_per_second, Some(1000.0));
+        assert_eq!(delta.tx_bytes_per_second, Some(2000.0));
+        assert_eq!(delta.rx_packets_per_second, Some(10.0));
+        assert_eq!(delta.tx_packets_per_second, Some(20.0));
+        assert_eq!(delta.tx_retries_per_second, Some(2.0));
+        assert_eq!(delta.tx_failed_per_second, Some(1.0));
+        assert_eq!(delta.retry_ratio, Some(0.1));
+        assert_eq!(delta.signal_change, Some(5));
+    }
+
+    #[test]
+    fn test_station_delta_32bit_wrap() {
+        let previous = WirelessStation {
+            rx_bytes: Some(u32::MAX - 99),
+            rx_packets: Some(u32::MAX),
+            ..Default::default()
+        };
+        let current = WirelessStation {
+            rx_bytes: Some(100),
+            rx_packets: Some(9),
+            ..Default::default()
+        };
+        let delta = StationDelta::new(&previous, &current, Duration::from_secs(1));
+        assert_eq!(delta.rx_bytes_per_second, Some(200.0));
+        assert_eq!(delta.rx_packets_per_second, Some(10.0));
+    }
+
+    #[test]
+    fn test_station_delta_64bit_reset() {
+        let previous = WirelessStation {
+            rx_bytes: Some(500),
+            rx_bytes64: Some(5_000_000_000),
+            tx_bytes64: Some(1000),
+            ..Default::default()
+        };
+        let current = WirelessStation {
+            rx_bytes: Some(100),
+            rx_bytes64: Some(100),
+            tx_bytes64: Some(3000),
+            ..Default::default()
+        };
+        let delta = StationDelta::new(&previous, &current, Duration::from_secs(1));
+        // A decreasing 64-bit counter was reset and is not treated as a wrap.
+        assert_eq!(delta.rx_bytes_per_second, None);
+        assert_eq!(delta.tx_bytes_per_second, Some(2000.0));
+    }
+
+    #[test]
+    fn test_station_delta_zero_interval() {
+        let previous = WirelessStation {
+            rx_bytes: Some(1000),
+            tx_packets: Some(10),
+            tx_retries: Some(1),
+            signal: Some(-70i8 as u8),
+            ..Default::default()
+        };
+        let current = WirelessStation {
+            rx_bytes: Some(2000),
+            tx_packets: Some(20),
+            tx_retries: Some(3),
+            signal: Some(-72i8 as u8),
+            ..Default::default()
+        };
+        let delta = StationDelta::new(&previous, &current, Duration::ZERO);
+        assert_eq!(delta.elapsed, Duration::ZERO);
+        assert_eq!(delta.rx_bytes_per_second, None);
+        assert_eq!(delta.tx_packets_per_second, None);
+        assert_eq!(delta.tx_retries_per_second, None);
+        // Values that do not depend on the interval are still reported.
+        assert_eq!(delta.retry_ratio, Some(0.2));
+        assert_eq!(delta.signal_change, Some(-2));
+    }
+
+    #[test]
+    fn test_station_project() {
+        let station = WirelessStation {
+            interface_index: 3,
+            mac: MacAddress::from([2, 0, 0, 0, 0, 1]),
+            signal: Some(-60i8 as u8),
+            inactive_time: Some(Duration::from_millis(20)),
+            rx_bytes: Some(1000),
+            authorized: Some(true),
+            ..Default::default()
+        };
+        let projected = station.project(StationFields::SIGNAL | StationFields::FLAGS);
+        assert_eq!(projected.interface_index, 3);
+        assert_eq!(projected.mac, station.mac);
+        assert_eq!(projected.signal, station.signal);
+        assert_eq!(projected.authorized, Some(true));
+        assert_eq!(projected.inactive_time, None);
+        assert_eq!(projected.rx_bytes, None);
+
+        let later = WirelessStation {
+            inactive_time: Some(Duration::from_millis(120)),
+            rx_bytes: Some(2000),
+            ..station.clone()
+        };
+        assert_eq!(
+            later.project(StationFields::SIGNAL | StationFields::FLAGS),
+            projected
+        );
+    }
+
+    #[test]
+    fn test_station_delta_missing_counters() {
+        let previous = WirelessStation::default();
+        let current = WirelessStation {
+            rx_bytes: Some(1000),
+            ..Default::default()
+        };
+        let delta = StationDelta::new(&previous, &current, Duration::from_secs(1));
+        assert_eq!(delta.rx_bytes_per_second, None);
+        assert_eq!(delta.signal_change, None);
+    }
+}
@@ -0,0 +1,304 @@
+use std::time::Duration;
+
+use log::debug;
+use neli::attr::Attribute as NeliAttribute;
+use neli::err::DeError;
+
+use crate::attributes::{Attribute, Attrs, SurveyInfoAttr};
+use crate::reg_domain::{find_rule, RegulatoryRule};
+use crate::units::Mhz;
+use crate::wiphy::{DfsChannelState, Frequency, WifiBand};
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Channel survey information returned from netlink.
+pub struct SurveyInfo {
+    /// Network interface index.
+    pub interface_index: u32,
+    /// Center frequency of the channel. Not set for cumulative radio
+    /// statistics.
+    pub frequency: Option<Mhz>,
+    /// Center frequency offset in kHz.
+    pub frequency_offset: Option<u32>,
+    /// Noise level of the channel in dBm.
+    pub noise: Option<i8>,
+    /// The channel is currently in use.
+    pub in_use: bool,
+    /// Amount of time the radio was on the channel.
+    pub active_time: Option<Duration>,
+    /// Amount of time the primary channel was sensed busy.
+    pub busy_time: Option<Duration>,
+    /// Amount of time the extension channel was sensed busy.
+    pub extension_busy_time: Option<Duration>,
+    /// Amount of time the radio spent receiving data.
+    pub rx_time: Option<Duration>,
+    /// Amount of time the radio spent receiving data on the local BSS.
+    pub bss_rx_time: Option<Duration>,
+    /// Amount of time the radio spent transmitting data.
+    pub tx_time: Option<Duration>,
+    /// Amount of time the radio spent for scanning.
+    pub scan_time: Option<Duration>,
+}
+
+impl TryFrom<&Attrs<'_, Attribute>> for SurveyInfo {
+    type Error = DeError;
+
+    fn try_from(handle: &Attrs<'_, Attribute>) -> Result<Self, Self::Error> {
+        let mut survey = SurveyInfo::default();
+        let mut survey_info_attr: Option<Attrs<'_, SurveyInfoAttr>> = None;
+        for attr in handle.iter() {
+            match attr.nla_type().nla_type() {
+                Attribute::Ifindex => survey.interface_index = attr.get_payload_as()?,
+                Attribute::SurveyInfo => {
+                    survey_info_attr = Some(attr.get_attr_handle()?);
+                }
+                unhandled => debug!("Unhandled survey attribute 'Attribute::{unhandled:?}'"),
+            }
+        }
+
+        if let Some(sub_handle) = survey_info_attr {
+            for sub_attr in sub_handle.iter() {
+                match sub_attr.nla_type().nla_type() {
+                    SurveyInfoAttr::Frequency => {
+                        survey.frequency = Some(Mhz(sub_attr.get_payload_as()?))
+                    }
+                    SurveyInfoAttr::FrequencyOffset => {
+                        survey.frequency_offset = Some(sub_attr.get_payload_as()?);
+                    }
+                    SurveyInfoAttr::Noise => survey.noise = Some(sub_attr.get_payload_as()?),
+                    SurveyInfoAttr::InUse => survey.in_use = true,
+                    SurveyInfoAttr::Time => {
+                        survey.active_time =
+                            Some(Duration::from_millis(sub_attr.get_payload_as()?));
+                    }
+                    SurveyInfoAttr::TimeBusy => {
+                        survey.busy_time = Some(Duration::from_millis(sub_attr.get_payload_as()?));
+                    }
+                    SurveyInfoAttr::TimeExtBusy => {
+                        survey.extension_busy_time =
+                            Some(Duration::from_millis(sub_attr.get_payload_as()?));
+                    }
+                    SurveyInfoAttr::TimeRx => {
+                        survey.rx_time = Some(Duration::from_millis(sub_attr.get_payload_as()?));
+                    }
+                    SurveyInfoAttr::TimeBssRx => {
+                        survey.bss_rx_time =
+                            Some(Duration::from_millis(sub_attr.get_payload_as()?));
+                    }
+                    SurveyInfoAttr::TimeTx => {
+                        survey.tx_time = Some(Duration::from_millis(sub_attr.get_payload_as()?));
+                    }
+                    SurveyInfoAttr::TimeScan => {
+                        survey.scan_time = Some(Duration::from_millis(sub_attr.get_payload_as()?));
+                    }
+                    SurveyInfoAttr::Pad => (),
+                    unhandled => {
+                        debug!("Unhandled survey info attribute 'SurveyInfoAttr::{unhandled:?}'")
+                    }
+                }
+            }
+        }
+        Ok(survey)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Supported frequency of a device joined with its channel survey results,
+/// returned by `PhysicalDevice::frequency_surveys`.
+pub struct FrequencySurvey {
+    /// Supported frequency.
+    pub frequency: Frequency,
+    /// Noise level of the channel in dBm.
+    pub noise: Option<i8>,
+    /// Amount of time the radio was on the channel.
+    pub active_time: Option<Duration>,
+    /// Amount of time the primary channel was sensed busy.
+    pub busy_time: Option<Duration>,
+    /// The channel is currently in use.
+    pub in_use: bool,
+}
+
+/// Recommend the best channel of a band for operation based on survey results.
+///
+/// Channels are scored by the ratio of busy time to active time and by the
+/// noise level, lower being better. Channels that are disabled, do not allow
+/// initiating radiation or require a radar detection that has not been done are
+/// skipped, as are channels not covered by the given regulatory rules. Channels
+/// without survey results or without measured busy and active times are not
+/// considered. Channels with an unknown noise level are ranked after all
+/// channels with a measured one.
+///
+/// Returns the frequency of the recommended channel.
+pub fn recommend_channel(
+    band: &WifiBand,
+    surveys: &[SurveyInfo],
+    reg_rules: &[RegulatoryRule],
+) -> Option<Mhz> {
+    band.frequencies
+        .iter()
+        .filter(|frequency| {
+            !frequency.disabled
+                && !frequency.no_ir
+                && (!frequency.radar_detection
+                    || frequency.dfs_state == Some(DfsChannelState::Available))
+        })
+        .filter(|frequency| {
+            reg_rules.is_empty()
+                || find_rule(reg_rules, frequency.frequency).is_some_and(|rule| !rule.no_ir)
+        })
+        .filter_map(|frequency| {
+            let survey = surveys
+                .iter()
+                .find(|survey| survey.frequency == Some(frequency.frequency))?;
+            Some((frequency.frequency, channel_score(survey)?))
+        })
+        .min_by(|(_, (a_unknown, a)), (_, (b_unknown, b))| {
+            a_unknown.cmp(b_unknown).then(a.total_cmp(b))
+        })
+        .map(|(frequency, _)| frequency)
+}
+
+/// Score a channel from its survey results, lower is better. Busy ratio is
+/// weighted in percents and noise in dB above -100 dBm. The score is paired
+/// with whether the noise level is unknown, in which case only the busy ratio
+/// is scored. Returns `None` if the busy and active times were not measured.
+fn channel_score(survey: &SurveyInfo) -> Option<(bool, f64)> {
+    let busy_ratio = match (survey.busy_time, survey.active_time) {
+        (Some(busy), Some(active)) if !active.is_zero() => {
+            busy.as_secs_f64() / active.as_secs_f64()
+        }
+        _ => return None,
+    };
+    let noise = survey
+        .noise
+        .map(|noise| (f64::from(noise) + 100.0).max(0.0));
+    Some((noise.is_none(), busy_ratio * 100.0 + noise.unwrap_or(0.0)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::units::Khz;
+
+    fn frequency(frequency: u32) -> Frequency {
+        Frequency {
+            frequency: Mhz(frequency),
+            ..Default::default()
+        }
+    }
+
+    fn survey(frequency: u32, busy_ms: u64, noise: Option<i8>) -> SurveyInfo {
+        SurveyInfo {
+            frequency: Some(Mhz(frequency)),
+            noise,
+            active_time: Some(Duration::from_millis(1000)),
+            busy_time: Some(Duration::from_millis(busy_ms)),
+            ..Default::default()
+        }
+    }
+
+    fn band(frequencies: Vec<Frequency>) -> WifiBand {
+        WifiBand {
+            frequencies,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_recommend_least_busy_channel() {
+        let band = band(vec![frequency(2412), frequency(2437), frequency(2462)]);
+        let surveys = [
+            survey(2412, 600, Some(-95)),
+            survey(2437, 100, Some(-95)),
+            survey(2462, 300, Some(-95)),
+        ];
+        assert_eq!(recommend_channel(&band, &surveys, &[]), Some(Mhz(2437)));
+    }
+
+    #[test]
+    fn test_recommend_channel_noise() {
+        let band = band(vec![frequency(2412), frequency(2437)]);
+        let surveys = [survey(2412, 100, Some(-70)), survey(2437, 100, Some(-95))];
+        assert_eq!(recommend_channel(&band, &surveys, &[]), Some(Mhz(2437)));
+    }
+
+    #[test]
+    fn test_recommend_channel_skips_unmeasured() {
+        let band = band(vec![frequency(2412), frequency(2437), frequency(2462)]);
+        let surveys = [
+            survey(2412, 500, Some(-90)),
+            SurveyInfo {
+                frequency: Some(Mhz(2437)),
+                noise: Some(-95),
+                ..Default::default()
+            },
+        ];
+        assert_eq!(recommend_channel(&band, &surveys, &[]), Some(Mhz(2412)));
+    }
+
+    #[test]
+    fn test_recommend_channel_unknown_noise_ranked_last() {
+        let band = band(vec![frequency(2412), frequency(2437)]);
+        let surveys = [survey(2412, 0, None), survey(2437, 500, Some(-60))];
+        assert_eq!(recommend_channel(&band, &surveys, &[]), Some(Mhz(2437)));
+
+        let surveys = [survey(2412, 0, None)];
+        assert_eq!(recommend_channel(&band, &surveys, &[]), Some(Mhz(2412)));
+    }
+
+    #[test]
+    fn test_recommend_channel_skips_unavailable() {
+        let band = band(vec![
+            Frequency {
+                disabled: true,
+                ..frequency(5180)
+            },
+            Frequency {
+                no_ir: true,
+                ..frequency(5200)
+            },
+            Frequency {
+                radar_detection: true,
+                dfs_state: Some(DfsChannelState::Usable),
+                ..frequency(5260)
+            },
+            Frequency {
+                radar_detection: true,
+                dfs_state: Some(DfsChannelState::Available),
+                ..frequency(5280)
+            },
+            frequency(5745),
+        ]);
+        let surveys = [
+            survey(5180, 0, Some(-95)),
+            survey(5200, 0, Some(-95)),
+            survey(5260, 0, Some(-95)),
+            survey(5280, 100, Some(-95)),
+            survey(5745, 200, Some(-95)),
+        ];
+        assert_eq!(recommend_channel(&band, &surveys, &[]), Some(Mhz(5280)));
+    }
+
+    #[test]
+    fn test_recommend_channel_regulatory_rules() {
+        let band = band(vec![frequency(5180), frequency(5745)]);
+        let surveys = [survey(5180, 100, Some(-95)), survey(5745, 200, Some(-95))];
+        let rule = |start: u32, end: u32, no_ir: bool| RegulatoryRule {
+            freq_range_start: Khz(start * 1000),
+            freq_range_end: Khz(end * 1000),
+            no_ir,
+            ..Default::default()
+        };
+
+        // 5745 MHz is not covered by any rule.
+        let rules = [rule(5170, 5250, false)];
+        assert_eq!(recommend_channel(&band, &surveys, &rules), Some(Mhz(5180)));
+        let rules = [rule(5170, 5250, true), rule(5725, 5850, false)];
+        assert_eq!(recommend_channel(&band, &surveys, &rules), Some(Mhz(5745)));
+        let rules = [rule(5725, 5850, false)];
+        assert_eq!(recommend_channel(&band, &surveys, &rules), Some(Mhz(5745)));
+        let rules = [rule(5490, 5710, false)];
+        assert_eq!(recommend_channel(&band, &surveys, &rules), None);
+    }
+}
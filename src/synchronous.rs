@@ -1,60 +1,142 @@
-use std::collections::HashMap;
-use std::fmt::Write;
-use std::io::Cursor;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, VecDeque};
+use std::fmt::{self, Write};
+use std::fs::{self, File};
+use std::os::fd::{AsFd, BorrowedFd, OwnedFd, RawFd};
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, RecvError, RecvTimeoutError, TryRecvError};
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
+use std::thread;
+use std::time::Duration;
 
-use log::debug;
+use log::{debug, log_enabled, Level};
 use neli::consts::nl::Nlmsg;
 use neli::consts::socket::NlFamily;
-use neli::err::RouterError;
+use neli::genl::Genlmsghdr;
 use neli::nl::NlPayload;
 use neli::router::synchronous::{NlRouter, NlRouterReceiverHandle};
 use neli::utils::Groups;
-use neli::ToBytes;
+use nix::sched::{setns, CloneFlags};
+use nix::sys::socket::{setsockopt, sockopt};
 
+use crate::ap::{AclPolicy, ApConfig, BeaconConfig, QosMap};
 use crate::attributes::{Attribute, Attrs, MonitorFlags};
-use crate::error::Result;
-use crate::interface::{ChannelWidth, InterfaceType};
-use crate::netlink::{Neli80211Header, Nl80211Request};
-use crate::reg_domain::RegulatoryDomain;
-use crate::station::WirelessStation;
-use crate::wiphy::PhysicalDevice;
+use crate::batch::Batch;
+use crate::error::{ErrorKind, NlError, Result};
+use crate::event::{Event, MulticastGroup, RadarEvent};
+use crate::interface::{
+    BitrateMask, ChannelConfig, ChannelWidth, FrameTx, InterfaceType, MacAddress, TxPower,
+};
+use crate::key::{DefaultKeyType, Key, KeyInfo};
+use crate::mesh::{MeshParams, MeshPath, MeshProxyPath};
+use crate::netlink::{
+    split_wiphy_dump, Neli80211Header, Nl80211Request, Response, DUMP_CAPACITY_HINT,
+    NL80211_FAMILY_ID,
+};
+use crate::observer::{Observation, SocketObserver};
+use crate::reg_domain::{RegulatoryDomain, RegulatoryHint};
+use crate::station::{
+    associated_bssid, LazyStation, LinkStatus, StationConfig, StationFields, WirelessStation,
+    WirelessStationRef,
+};
+use crate::survey::SurveyInfo;
+use crate::topology::Topology;
+use crate::transport::{Responses, Transport};
+use crate::wiphy::{
+    parse_coalesce_rules, CoalesceRule, PhysicalDevice, ProtocolFeatures, SarLimit, TxqParams,
+    WiphyParams,
+};
 
 use super::interface::WirelessInterface;
 
 /// Netlink socket.
+///
+/// Requests take `&self`, so a single socket can be shared between callers.
+/// Only methods reading from the event receiver, such as `next_event`,
+/// require `&mut self`.
+///
+/// The socket is `Send + Sync` and cheap to clone: clones share the same
+/// underlying netlink router, resolved family id and event receiver, so
+/// requests can be issued from several threads without reconnecting. Events
+/// received by one clone are not seen by the others.
+#[derive(Clone)]
 pub struct NlSocket {
-    socket: NlRouter,
-    nl_type: u16,
+    transport: Arc<dyn Transport>,
+    dump_retries: Option<u32>,
+    observer: Option<Arc<dyn SocketObserver>>,
+    protocol_features: Arc<OnceLock<ProtocolFeatures>>,
+    skipped_events: Arc<Mutex<VecDeque<Event>>>,
 }
 
 impl NlSocket {
+    pub(crate) fn from_transport(transport: Arc<dyn Transport>) -> Self {
+        Self {
+            transport,
+            dump_retries: None,
+            observer: None,
+            protocol_features: Arc::default(),
+            skipped_events: Arc::default(),
+        }
+    }
+
     /// Connect netlink socket.
     pub fn connect() -> Result<Self> {
-        let (socket, _) = NlRouter::connect(NlFamily::Generic, None, Groups::empty())?;
-        let nl_type = socket.resolve_genl_family("nl80211")?;
-        Ok(Self { socket, nl_type })
+        Self::builder().connect()
+    }
+
+    /// Create a builder for connecting a netlink socket with options.
+    pub fn builder() -> NlSocketBuilder {
+        NlSocketBuilder::default()
+    }
+
+    /// Verify that all messages of a dump share the same generation number.
+    ///
+    /// The kernel increments the generation whenever the dumped object list
+    /// changes. When enabled, interface, station, physical device and mesh path
+    /// dumps are repeated up to `max_retries` times if the generation changes
+    /// mid-dump, after which an `ErrorKind::InconsistentDump` error is
+    /// returned. `None` disables the check.
+    pub fn set_consistent_dumps(&mut self, max_retries: Option<u32>) {
+        self.dump_retries = max_retries;
+    }
+
+    /// Set an observer called for every request sent by this socket.
+    pub fn set_observer(&mut self, observer: Option<Arc<dyn SocketObserver>>) {
+        self.observer = observer;
     }
 
     pub fn list_interfaces(&self) -> Result<Vec<WirelessInterface>> {
-        let request = Nl80211Request::list_interfaces();
+        self.handle_consistent_dump(Nl80211Request::list_interfaces, |handle| {
+            Ok(TryInto::<WirelessInterface>::try_into(handle)?)
+        })
+    }
+
+    pub fn get_interface(&self, if_index: u32) -> Result<Option<WirelessInterface>> {
+        let request = Nl80211Request::get_interface(if_index)?;
         let recv = self.send(request)?;
 
-        let mut responses = Vec::new();
+        let mut result: Option<WirelessInterface> = None;
         Self::handle_dump_response(recv, |handle| {
-            responses.push(TryInto::<WirelessInterface>::try_into(handle)?);
+            let device: WirelessInterface = handle.try_into()?;
+            if device.interface_index == if_index {
+                result = Some(device);
+            }
             Ok(())
         })?;
-        Ok(responses)
+        Ok(result)
     }
 
-    pub fn get_interface(&self, if_index: u32) -> Result<Option<WirelessInterface>> {
-        let request = Nl80211Request::get_interface(if_index);
+    /// Get an interface by its wireless device identifier.
+    ///
+    /// Used for interfaces without a network device, e.g. P2P-Device and NAN.
+    pub fn get_interface_by_wdev(&self, wdev: u64) -> Result<Option<WirelessInterface>> {
+        let request = Nl80211Request::get_interface_by_wdev(wdev)?;
         let recv = self.send(request)?;
 
         let mut result: Option<WirelessInterface> = None;
         Self::handle_dump_response(recv, |handle| {
             let device: WirelessInterface = handle.try_into()?;
-            if device.interface_index == if_index {
+            if device.wdev == Some(wdev) {
                 result = Some(device);
             }
             Ok(())
@@ -62,54 +144,248 @@ impl NlSocket {
         Ok(result)
     }
 
+    /// Get an interface by its name, e.g. "wlan0".
+    pub fn get_interface_by_name(&self, name: &str) -> Result<Option<WirelessInterface>> {
+        let interfaces = self.list_interfaces()?;
+        Ok(interfaces
+            .into_iter()
+            .find(|interface| interface.name == name))
+    }
+
     pub fn set_interface(&self, if_index: u32, if_type: InterfaceType) -> Result<()> {
-        let request = Nl80211Request::set_interface(if_index, if_type);
+        let request = Nl80211Request::set_interface(if_index, if_type)?;
         let recv = self.send(request)?;
         Self::handle_ack_response(recv)
     }
 
     pub fn set_monitor_flags(&self, if_index: u32, flags: Vec<MonitorFlags>) -> Result<()> {
-        let request = Nl80211Request::set_monitor_flags(if_index, flags);
+        let request = Nl80211Request::set_monitor_flags(if_index, flags)?;
         let recv = self.send(request)?;
         Self::handle_ack_response(recv)
     }
 
     pub fn set_channel(&self, if_index: u32, freq: u32, width: ChannelWidth) -> Result<()> {
-        let request = Nl80211Request::set_channel(if_index, freq, width);
+        let request = Nl80211Request::set_channel(if_index, freq, width)?;
         let recv = self.send(request)?;
         Self::handle_ack_response(recv)
     }
 
-    pub fn list_stations(&self, if_index: u32) -> Result<Vec<WirelessStation>> {
-        let request = Nl80211Request::list_stations(if_index);
+    /// Set the operating channel of an interface, including the center
+    /// frequencies required by channels wider than 20 MHz.
+    pub fn set_channel_config(&self, if_index: u32, channel: ChannelConfig) -> Result<()> {
+        channel.validate()?;
+        let request = Nl80211Request::set_channel_config(if_index, channel)?;
         let recv = self.send(request)?;
+        Self::handle_ack_response(recv)
+    }
 
-        let mut responses = Vec::new();
+    pub fn list_stations(&self, if_index: u32) -> Result<Vec<WirelessStation>> {
+        self.handle_consistent_dump(
+            || Nl80211Request::list_stations(if_index),
+            |handle| Ok(TryInto::<WirelessStation>::try_into(handle)?),
+        )
+    }
+
+    /// List stations associated to an interface, decoding only the station
+    /// information in `fields`.
+    pub fn list_stations_with(
+        &self,
+        if_index: u32,
+        fields: StationFields,
+    ) -> Result<Vec<WirelessStation>> {
+        self.handle_consistent_dump(
+            || Nl80211Request::list_stations(if_index),
+            |handle| Ok(WirelessStation::from_attributes(handle, fields)?),
+        )
+    }
+
+    /// List stations associated to an interface, deferring decoding of the
+    /// station information until it is accessed.
+    pub fn list_stations_lazy(&self, if_index: u32) -> Result<Vec<LazyStation>> {
+        self.handle_consistent_dump(
+            || Nl80211Request::list_stations(if_index),
+            |handle| Ok(LazyStation::try_from(handle)?),
+        )
+    }
+
+    /// List stations associated to an interface into `stations`.
+    ///
+    /// The vector is cleared before the request is sent and left empty if it
+    /// fails, so its allocation can be reused when polling stations
+    /// repeatedly. The request itself is still built per call: neli takes
+    /// ownership of the message attributes when sending, so there is no
+    /// attribute buffer to hand back for reuse.
+    pub fn list_stations_into(
+        &self,
+        if_index: u32,
+        stations: &mut Vec<WirelessStation>,
+    ) -> Result<()> {
+        self.handle_consistent_dump_into(
+            || Nl80211Request::list_stations(if_index),
+            |handle| Ok(TryInto::<WirelessStation>::try_into(handle)?),
+            stations,
+        )
+    }
+
+    /// List stations of all wireless interfaces.
+    pub fn list_all_stations(&self) -> Result<Vec<(WirelessInterface, Vec<WirelessStation>)>> {
+        self.list_interfaces()?
+            .into_iter()
+            .map(|interface| {
+                let stations = match interface.interface_index {
+                    0 => Vec::new(),
+                    if_index => self.list_stations(if_index)?,
+                };
+                Ok((interface, stations))
+            })
+            .collect()
+    }
+
+    /// Poll stations of an interface every `interval`.
+    ///
+    /// With `changes_in`, a station list is only delivered when the stations
+    /// projected onto these fields differ from the previously delivered
+    /// list. Errors are delivered and polling continues.
+    ///
+    /// Polling runs on a background thread using a clone of this socket and
+    /// stops when the returned watch is dropped.
+    pub fn watch_stations(
+        &self,
+        if_index: u32,
+        interval: Duration,
+        changes_in: Option<StationFields>,
+    ) -> StationWatch {
+        let socket = self.clone();
+        let (sender, receiver) = mpsc::channel();
+        let alive = Arc::new(());
+        let watcher = Arc::downgrade(&alive);
+        thread::spawn(move || {
+            let mut previous: Option<Vec<WirelessStation>> = None;
+            while watcher.strong_count() > 0 {
+                let result = socket.list_stations(if_index);
+                if let (Some(fields), Ok(stations)) = (changes_in, &result) {
+                    let projected: Vec<_> = stations.iter().map(|s| s.project(fields)).collect();
+                    if previous.as_ref() == Some(&projected) {
+                        thread::sleep(interval);
+                        continue;
+                    }
+                    previous = Some(projected);
+                }
+                if sender.send(result).is_err() {
+                    break;
+                }
+                thread::sleep(interval);
+            }
+        });
+        StationWatch {
+            receiver,
+            _alive: alive,
+        }
+    }
+
+    /// Create a batch of requests that are sent back-to-back before their
+    /// responses are received.
+    pub fn batch(&self) -> Batch<'_> {
+        Batch::new(self)
+    }
+
+    /// Get a snapshot of all physical devices with their interfaces and
+    /// stations.
+    pub fn snapshot(&self) -> Result<Topology> {
+        let devices = self.list_physical_devices()?;
+        let interfaces = self.list_all_stations()?;
+        Ok(Topology::new(devices, interfaces))
+    }
+
+    /// Call `f` for each station associated to an interface with counters
+    /// borrowed from the received message.
+    ///
+    /// Intended for frequent polling, where only a few statistics are read.
+    pub fn for_each_station<F: FnMut(WirelessStationRef<'_>)>(
+        &self,
+        if_index: u32,
+        mut f: F,
+    ) -> Result<()> {
+        let request = Nl80211Request::list_stations(if_index)?;
+        let recv = self.send(request)?;
         Self::handle_dump_response(recv, |handle| {
-            responses.push(TryInto::<WirelessStation>::try_into(handle)?);
+            f(WirelessStationRef::try_from(handle)?);
             Ok(())
-        })?;
-        Ok(responses)
+        })
     }
 
-    pub fn list_physical_devices(&self) -> Result<Vec<PhysicalDevice>> {
-        let request = Nl80211Request::list_physical_devices();
+    /// List stations associated to an interface, decoding each station lazily
+    /// as it is read from the socket.
+    ///
+    /// Unlike `list_stations`, the dump is not buffered, so consumers can stop
+    /// early by dropping the iterator.
+    pub fn list_stations_iter(
+        &self,
+        if_index: u32,
+    ) -> Result<impl Iterator<Item = Result<WirelessStation>> + '_> {
+        let request = Nl80211Request::list_stations(if_index)?;
         let recv = self.send(request)?;
 
-        let mut responses = HashMap::new();
+        Ok(recv.map(|payload| {
+            let payload = payload?;
+            let handle = payload.attrs().get_attr_handle();
+            Ok(WirelessStation::try_from(&handle)?)
+        }))
+    }
+
+    /// Get the protocol features supported by the kernel. The result is
+    /// cached by the socket.
+    pub fn get_protocol_features(&self) -> Result<ProtocolFeatures> {
+        if let Some(features) = self.protocol_features.get() {
+            return Ok(*features);
+        }
+        let request = Nl80211Request::get_protocol_features()?;
+        let recv = self.send(request)?;
+
+        let mut features = ProtocolFeatures::empty();
         Self::handle_dump_response(recv, |handle| {
-            let device: PhysicalDevice = handle.try_into()?;
-            responses
-                .entry(device.wiphy_index)
-                .and_modify(|d: &mut PhysicalDevice| d.merge(&device))
-                .or_insert(device);
+            if let Ok(bits) = handle.get_attr_payload_as::<u32>(Attribute::ProtocolFeatures) {
+                features = ProtocolFeatures::from_bits_retain(bits);
+            }
             Ok(())
         })?;
-        Ok(responses.values().cloned().collect())
+        Ok(*self.protocol_features.get_or_init(|| features))
     }
 
+    /// List physical devices. Split dumps are requested if the kernel supports
+    /// them, and the parts of each device are merged. Kernels that do not
+    /// support `GetProtocolFeatures` (before 3.10) get an unsplit dump.
+    pub fn list_physical_devices(&self) -> Result<Vec<PhysicalDevice>> {
+        let request: fn() -> Result<Nl80211Request> =
+            if split_wiphy_dump(self.get_protocol_features())? {
+                Nl80211Request::list_physical_devices
+            } else {
+                Nl80211Request::list_physical_devices_unsplit
+            };
+        let devices = self.handle_consistent_dump(request, |handle| {
+            Ok(TryInto::<PhysicalDevice>::try_into(handle)?)
+        })?;
+
+        let mut responses: HashMap<u32, PhysicalDevice> = HashMap::new();
+        for device in devices {
+            match responses.entry(device.wiphy_index) {
+                Entry::Occupied(mut entry) => entry.get_mut().merge(device),
+                Entry::Vacant(entry) => {
+                    entry.insert(device);
+                }
+            }
+        }
+        Ok(responses.into_values().collect())
+    }
+
+    /// Get a physical device by its index, following the same split dump
+    /// selection as `list_physical_devices`.
     pub fn get_physical_device(&self, wiphy_index: u32) -> Result<Option<PhysicalDevice>> {
-        let request = Nl80211Request::get_physical_device(wiphy_index);
+        let request = if split_wiphy_dump(self.get_protocol_features())? {
+            Nl80211Request::get_physical_device(wiphy_index)?
+        } else {
+            Nl80211Request::get_physical_device_unsplit(wiphy_index)?
+        };
         let recv = self.send(request)?;
 
         let mut result: Option<PhysicalDevice> = None;
@@ -117,7 +393,7 @@ impl NlSocket {
             let device: PhysicalDevice = handle.try_into()?;
             if device.wiphy_index == wiphy_index {
                 if let Some(d) = result.as_mut() {
-                    d.merge(&device);
+                    d.merge(device);
                 } else {
                     result = Some(device);
                 }
@@ -128,7 +404,7 @@ impl NlSocket {
     }
 
     pub fn get_regulatory_domain(&self) -> Result<Vec<RegulatoryDomain>> {
-        let request = Nl80211Request::get_regulatory_domain();
+        let request = Nl80211Request::get_regulatory_domain()?;
         let recv = self.send(request)?;
 
         let mut responses = Vec::new();
@@ -139,9 +415,32 @@ impl NlSocket {
         Ok(responses)
     }
 
+    /// Get the regulatory domain of a device.
+    ///
+    /// Self-managed devices return their private regulatory domain, other
+    /// devices return the global regulatory domain.
+    pub fn get_regulatory_domain_for(&self, wiphy_index: u32) -> Result<Option<RegulatoryDomain>> {
+        let request = Nl80211Request::get_regulatory_domain_for(wiphy_index)?;
+        let recv = self.send(request)?;
+
+        let mut result: Option<RegulatoryDomain> = None;
+        Self::handle_dump_response(recv, |handle| {
+            result = Some(handle.try_into()?);
+            Ok(())
+        })?;
+        Ok(result)
+    }
+
     /// Trigger a new scan.
     pub fn trigger_scan(&self, if_index: u32) -> Result<()> {
-        let request = Nl80211Request::trigger_scan(if_index);
+        let request = Nl80211Request::trigger_scan(if_index)?;
+        let recv = self.send(request)?;
+        Self::handle_ack_response(recv)
+    }
+
+    /// Trigger a new scan on a wireless device without a network interface.
+    pub fn trigger_scan_by_wdev(&self, wdev: u64) -> Result<()> {
+        let request = Nl80211Request::trigger_scan_by_wdev(wdev)?;
         let recv = self.send(request)?;
         Self::handle_ack_response(recv)
     }
@@ -150,63 +449,1023 @@ impl NlSocket {
     ///
     /// Returns NlError ENOENT if a scan is not running.
     pub fn abort_scan(&self, if_index: u32) -> Result<()> {
-        let request = Nl80211Request::abort_scan(if_index);
+        let request = Nl80211Request::abort_scan(if_index)?;
+        let recv = self.send(request)?;
+        Self::handle_ack_response(recv)
+    }
+
+    /// Stop an ongoing scan on a wireless device without a network interface.
+    ///
+    /// Returns NlError ENOENT if a scan is not running.
+    pub fn abort_scan_by_wdev(&self, wdev: u64) -> Result<()> {
+        let request = Nl80211Request::abort_scan_by_wdev(wdev)?;
+        let recv = self.send(request)?;
+        Self::handle_ack_response(recv)
+    }
+
+    /// Install a PMK for 4-way handshake offload.
+    ///
+    /// Used with drivers that offload the 4-way handshake (`Want1x4wayHs`) to
+    /// provide the PMK (and PMK-R0 name for FT) for the given authenticator.
+    pub fn set_pmk(
+        &self,
+        if_index: u32,
+        mac: MacAddress,
+        pmk: &[u8],
+        pmkr0_name: Option<&[u8]>,
+    ) -> Result<()> {
+        let request = Nl80211Request::set_pmk(if_index, mac, pmk, pmkr0_name)?;
+        let recv = self.send(request)?;
+        Self::handle_ack_response(recv)
+    }
+
+    /// Remove a previously installed PMK for the given authenticator.
+    pub fn del_pmk(&self, if_index: u32, mac: MacAddress) -> Result<()> {
+        let request = Nl80211Request::del_pmk(if_index, mac)?;
         let recv = self.send(request)?;
         Self::handle_ack_response(recv)
     }
 
-    fn send(
+    /// Send the OWE (Opportunistic Wireless Encryption) processing result for a peer.
+    ///
+    /// Used in AP mode when the driver requested OWE handling from userspace.
+    /// `dh_ie` contains the Diffie-Hellman parameter element that is sent to the peer
+    /// in the association response.
+    pub fn update_owe_info(
         &self,
-        request: Nl80211Request,
-    ) -> std::result::Result<
-        NlRouterReceiverHandle<Nlmsg, Neli80211Header>,
-        RouterError<u16, Neli80211Header>,
-    > {
-        if cfg!(debug_assertions) {
-            let mut b: Cursor<Vec<u8>> = Cursor::new(Vec::new());
-            request.nl_payload.to_bytes(&mut b).unwrap();
-            let octets: String = b.get_ref().iter().fold(String::new(), |mut output, b| {
-                let _ = write!(output, "{b:02x} ");
-                output
-            });
-            debug!("[PAYLOAD] {octets}");
+        if_index: u32,
+        mac: MacAddress,
+        status_code: u16,
+        dh_ie: &[u8],
+    ) -> Result<()> {
+        let request = Nl80211Request::update_owe_info(if_index, mac, status_code, dh_ie)?;
+        let recv = self.send(request)?;
+        Self::handle_ack_response(recv)
+    }
+
+    /// Get current association information of a station mode interface.
+    ///
+    /// Combines the interface information with the station entry of the
+    /// associated access point, similar to `iw dev <ifname> link`. The access
+    /// point is the BSS marked as associated in the scan results. Returns `None`
+    /// if the interface does not exist, is not a station or P2P client
+    /// interface, or is not associated.
+    pub fn get_link(&self, if_index: u32) -> Result<Option<LinkStatus>> {
+        let Some(interface) = self.get_interface(if_index)? else {
+            return Ok(None);
+        };
+        if !matches!(
+            interface.interface_type,
+            Some(InterfaceType::Station | InterfaceType::P2pClient)
+        ) {
+            return Ok(None);
         }
-        self.socket
-            .send(self.nl_type, request.nl_flags, request.nl_payload)
+
+        let request = Nl80211Request::get_scan(if_index)?;
+        let recv = self.send(request)?;
+        let mut bssid = None;
+        Self::handle_dump_response(recv, |handle| {
+            if let Some(associated) = associated_bssid(handle)? {
+                bssid = Some(associated);
+            }
+            Ok(())
+        })?;
+        let Some(bssid) = bssid else {
+            return Ok(None);
+        };
+
+        let station = self
+            .list_stations(if_index)?
+            .into_iter()
+            .find(|station| station.mac == bssid);
+        Ok(station.map(|station| LinkStatus::new(interface, station)))
     }
 
-    fn handle_dump_response<F: FnMut(&Attrs<'_, Attribute>) -> Result<()>>(
-        recv: NlRouterReceiverHandle<Nlmsg, Neli80211Header>,
-        mut f: F,
+    /// Start operating as an access point on the given interface.
+    ///
+    /// The interface must already be in access point mode.
+    pub fn start_ap(&self, if_index: u32, config: ApConfig) -> Result<()> {
+        if let Some(channel) = &config.channel {
+            channel.validate()?;
+        }
+        let request = Nl80211Request::start_ap(if_index, config)?;
+        let recv = self.send(request)?;
+        Self::handle_ack_response(recv)
+    }
+
+    /// Stop access point operation on the given interface.
+    pub fn stop_ap(&self, if_index: u32) -> Result<()> {
+        let request = Nl80211Request::stop_ap(if_index)?;
+        let recv = self.send(request)?;
+        Self::handle_ack_response(recv)
+    }
+
+    /// Update beacon and probe response templates of a running access point.
+    pub fn set_beacon(&self, if_index: u32, config: BeaconConfig) -> Result<()> {
+        let request = Nl80211Request::set_beacon(if_index, config)?;
+        let recv = self.send(request)?;
+        Self::handle_ack_response(recv)
+    }
+
+    /// Add a new station to an access point interface.
+    pub fn new_station(&self, if_index: u32, mac: MacAddress, config: StationConfig) -> Result<()> {
+        let request = Nl80211Request::new_station(if_index, mac, config)?;
+        let recv = self.send(request)?;
+        Self::handle_ack_response(recv)
+    }
+
+    /// Modify parameters of an existing station.
+    pub fn set_station(&self, if_index: u32, mac: MacAddress, config: StationConfig) -> Result<()> {
+        let request = Nl80211Request::set_station(if_index, mac, config)?;
+        let recv = self.send(request)?;
+        Self::handle_ack_response(recv)
+    }
+
+    /// Remove a station from an access point interface.
+    pub fn del_station(&self, if_index: u32, mac: MacAddress) -> Result<()> {
+        let request = Nl80211Request::del_station(if_index, mac)?;
+        let recv = self.send(request)?;
+        Self::handle_ack_response(recv)
+    }
+
+    /// Set MAC address based access control list of an access point.
+    ///
+    /// The maximum number of addresses supported by the device is reported in
+    /// `PhysicalDevice::max_acl_mac_addresses`.
+    pub fn set_mac_acl(
+        &self,
+        if_index: u32,
+        policy: AclPolicy,
+        mac_addresses: Vec<MacAddress>,
     ) -> Result<()> {
-        for response in recv {
-            let response = response?;
-            match response.nl_payload() {
-                NlPayload::Err(err) => {
-                    debug!("Error when reading dump response: {err}");
-                    return Err(err.clone().into());
+        let request = Nl80211Request::set_mac_acl(if_index, policy, mac_addresses)?;
+        let recv = self.send(request)?;
+        Self::handle_ack_response(recv)
+    }
+
+    /// Probe if a client associated to an access point is still reachable.
+    ///
+    /// Returns a cookie identifying the probe. The result is delivered as an
+    /// `Event::ProbeClient` with the same cookie to sockets subscribed to
+    /// `MulticastGroup::Mlme`.
+    pub fn probe_client(&self, if_index: u32, mac: MacAddress) -> Result<u64> {
+        let request = Nl80211Request::probe_client(if_index, mac)?;
+        let recv = self.send(request)?;
+
+        let mut cookie = None;
+        Self::handle_dump_response(recv, |handle| {
+            cookie = Some(handle.get_attr_payload_as::<u64>(Attribute::Cookie)?);
+            Ok(())
+        })?;
+        cookie.ok_or_else(|| NlError::new("Probe client response did not contain a cookie"))
+    }
+
+    /// Subscribe to a nl80211 multicast group to receive its events with
+    /// `next_event`.
+    pub fn subscribe(&self, group: MulticastGroup) -> Result<()> {
+        self.transport.subscribe(group)
+    }
+
+    /// Wait for the next event delivered to this socket.
+    ///
+    /// Returns `None` if the socket was closed.
+    pub fn next_event(&mut self) -> Result<Option<Event>> {
+        let skipped = self
+            .skipped_events
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .pop_front();
+        match skipped {
+            Some(event) => Ok(Some(event)),
+            None => self.transport.next_event(),
+        }
+    }
+
+    /// Register this socket to receive all beacon frames seen by the wiphy.
+    ///
+    /// The beacons are delivered as `Event::Frame` events to this socket with
+    /// `next_event`. The registration is removed when the socket is closed.
+    pub fn register_beacons(&self, wiphy_index: u32) -> Result<()> {
+        let request = Nl80211Request::register_beacons(wiphy_index)?;
+        let recv = self.send(request)?;
+        Self::handle_ack_response(recv)
+    }
+
+    /// Get mesh configuration parameters of a mesh point interface.
+    pub fn get_mesh_config(&self, if_index: u32) -> Result<MeshParams> {
+        let request = Nl80211Request::get_mesh_config(if_index)?;
+        let recv = self.send(request)?;
+
+        let mut params = MeshParams::default();
+        Self::handle_dump_response(recv, |handle| {
+            params = handle.try_into()?;
+            Ok(())
+        })?;
+        Ok(params)
+    }
+
+    /// Set mesh configuration parameters of a mesh point interface.
+    pub fn set_mesh_config(&self, if_index: u32, params: MeshParams) -> Result<()> {
+        let request = Nl80211Request::set_mesh_config(if_index, params)?;
+        let recv = self.send(request)?;
+        Self::handle_ack_response(recv)
+    }
+
+    /// List mesh paths of a mesh point interface.
+    pub fn list_mesh_paths(&self, if_index: u32) -> Result<Vec<MeshPath>> {
+        self.handle_consistent_dump(
+            || Nl80211Request::list_mesh_paths(if_index),
+            |handle| Ok(TryInto::<MeshPath>::try_into(handle)?),
+        )
+    }
+
+    /// Get the mesh path to a destination.
+    pub fn get_mesh_path(
+        &self,
+        if_index: u32,
+        destination: MacAddress,
+    ) -> Result<Option<MeshPath>> {
+        let request = Nl80211Request::get_mesh_path(if_index, destination)?;
+        let recv = self.send(request)?;
+
+        let mut result: Option<MeshPath> = None;
+        Self::handle_dump_response(recv, |handle| {
+            result = Some(handle.try_into()?);
+            Ok(())
+        })?;
+        Ok(result)
+    }
+
+    /// List mesh proxy paths of a mesh point interface.
+    pub fn list_mesh_proxy_paths(&self, if_index: u32) -> Result<Vec<MeshProxyPath>> {
+        let request = Nl80211Request::list_mesh_proxy_paths(if_index)?;
+        let recv = self.send(request)?;
+
+        let mut responses = Vec::new();
+        Self::handle_dump_response(recv, |handle| {
+            responses.push(TryInto::<MeshProxyPath>::try_into(handle)?);
+            Ok(())
+        })?;
+        Ok(responses)
+    }
+
+    /// Send a directed frame to a mesh peer to verify that the peer link is alive.
+    ///
+    /// `frame` is an Ethernet frame (starting with the destination address) that is
+    /// sent to the peer bypassing the mesh path selection.
+    pub fn probe_mesh_link(&self, if_index: u32, mac: MacAddress, frame: &[u8]) -> Result<()> {
+        let request = Nl80211Request::probe_mesh_link(if_index, mac, frame)?;
+        let recv = self.send(request)?;
+        Self::handle_ack_response(recv)
+    }
+
+    /// Install an encryption key.
+    pub fn add_key(&self, if_index: u32, key: Key) -> Result<()> {
+        let request = Nl80211Request::add_key(if_index, key)?;
+        let recv = self.send(request)?;
+        Self::handle_ack_response(recv)
+    }
+
+    /// Remove an encryption key.
+    ///
+    /// `mac` is used to select a pairwise key.
+    pub fn del_key(&self, if_index: u32, index: u8, mac: Option<MacAddress>) -> Result<()> {
+        let request = Nl80211Request::del_key(if_index, index, mac)?;
+        let recv = self.send(request)?;
+        Self::handle_ack_response(recv)
+    }
+
+    /// Get information about an encryption key.
+    ///
+    /// `mac` is used to select a pairwise key.
+    pub fn get_key(
+        &self,
+        if_index: u32,
+        index: u8,
+        mac: Option<MacAddress>,
+    ) -> Result<Option<KeyInfo>> {
+        let request = Nl80211Request::get_key(if_index, index, mac)?;
+        let recv = self.send(request)?;
+
+        let mut result: Option<KeyInfo> = None;
+        Self::handle_dump_response(recv, |handle| {
+            result = Some(handle.try_into()?);
+            Ok(())
+        })?;
+        Ok(result)
+    }
+
+    /// Select the default key.
+    pub fn set_key(&self, if_index: u32, index: u8, default_type: DefaultKeyType) -> Result<()> {
+        let request = Nl80211Request::set_key(if_index, index, default_type)?;
+        let recv = self.send(request)?;
+        Self::handle_ack_response(recv)
+    }
+
+    /// Set the antennas used for transmitting and receiving.
+    ///
+    /// Each bit in the masks enables one antenna.
+    pub fn set_antenna(&self, wiphy_index: u32, tx_mask: u32, rx_mask: u32) -> Result<()> {
+        let request = Nl80211Request::set_antenna(wiphy_index, tx_mask, rx_mask)?;
+        let recv = self.send(request)?;
+        Self::handle_ack_response(recv)
+    }
+
+    /// Set SAR (specific absorption rate) power limits.
+    ///
+    /// Supported frequency ranges are listed in `PhysicalDevice::sar_capabilities`.
+    pub fn set_sar_limits(&self, wiphy_index: u32, limits: Vec<SarLimit>) -> Result<()> {
+        let request = Nl80211Request::set_sar_limits(wiphy_index, limits)?;
+        let recv = self.send(request)?;
+        Self::handle_ack_response(recv)
+    }
+
+    /// Set transmit queue parameters. Parameters set to `None` are left unchanged.
+    pub fn set_txq_params(&self, wiphy_index: u32, params: TxqParams) -> Result<()> {
+        let request = Nl80211Request::set_txq_params(wiphy_index, params)?;
+        let recv = self.send(request)?;
+        Self::handle_ack_response(recv)
+    }
+
+    /// Set the airtime weight of a station used by the airtime fairness scheduler.
+    pub fn set_station_airtime_weight(
+        &self,
+        if_index: u32,
+        mac: MacAddress,
+        weight: u16,
+    ) -> Result<()> {
+        let request = Nl80211Request::set_station_airtime_weight(if_index, mac, weight)?;
+        let recv = self.send(request)?;
+        Self::handle_ack_response(recv)
+    }
+
+    /// Restrict the bitrates used for transmitting frames.
+    ///
+    /// Equivalent to `iw dev <devname> set bitrates`.
+    pub fn set_tx_bitrate_mask(&self, if_index: u32, mask: BitrateMask) -> Result<()> {
+        let request = Nl80211Request::set_tx_bitrate_mask(if_index, mask)?;
+        let recv = self.send(request)?;
+        Self::handle_ack_response(recv)
+    }
+
+    /// Enable or disable 4-address (WDS) frames on an interface.
+    pub fn set_4addr(&self, if_index: u32, enabled: bool) -> Result<()> {
+        let request = Nl80211Request::set_4addr(if_index, enabled)?;
+        let recv = self.send(request)?;
+        Self::handle_ack_response(recv)
+    }
+
+    /// Configure connection quality monitor RSSI thresholds in dBm.
+    ///
+    /// An `Event::ConnectionQuality` is sent to the MLME multicast group when the
+    /// RSSI crosses a threshold by more than `hysteresis` dBm. Beacon loss events
+    /// are reported once any threshold is configured. An empty threshold list
+    /// disables RSSI monitoring.
+    pub fn set_cqm_rssi(&self, if_index: u32, thresholds: Vec<i32>, hysteresis: u32) -> Result<()> {
+        let request = Nl80211Request::set_cqm_rssi(if_index, thresholds, hysteresis)?;
+        let recv = self.send(request)?;
+        Self::handle_ack_response(recv)
+    }
+
+    /// Configure connection quality monitor TX error reporting.
+    ///
+    /// An `Event::ConnectionQuality` is sent when at least `rate` percent of
+    /// `packets` transmitted packets fail within `interval` seconds.
+    pub fn set_cqm_txe(&self, if_index: u32, rate: u32, packets: u32, interval: u32) -> Result<()> {
+        let request = Nl80211Request::set_cqm_txe(if_index, rate, packets, interval)?;
+        let recv = self.send(request)?;
+        Self::handle_ack_response(recv)
+    }
+
+    /// Set the transmit power of an interface.
+    pub fn set_tx_power(&self, if_index: u32, tx_power: TxPower) -> Result<()> {
+        let request = Nl80211Request::set_tx_power(if_index, tx_power)?;
+        let recv = self.send(request)?;
+        Self::handle_ack_response(recv)
+    }
+
+    /// Set retry limits, fragmentation and RTS thresholds and coverage class.
+    pub fn set_wiphy_params(&self, wiphy_index: u32, params: WiphyParams) -> Result<()> {
+        let request = Nl80211Request::set_wiphy_params(wiphy_index, params)?;
+        let recv = self.send(request)?;
+        Self::handle_ack_response(recv)
+    }
+
+    /// Set the QoS map used to map DSCP values to user priorities.
+    pub fn set_qos_map(&self, if_index: u32, qos_map: QosMap) -> Result<()> {
+        let request = Nl80211Request::set_qos_map(if_index, qos_map)?;
+        let recv = self.send(request)?;
+        Self::handle_ack_response(recv)
+    }
+
+    /// Request a regulatory domain change to the given ISO/IEC 3166-1 alpha2
+    /// country code, e.g. "US". Equivalent to `iw reg set`.
+    pub fn set_regulatory_domain(&self, alpha2: &str, hint: Option<RegulatoryHint>) -> Result<()> {
+        let request = Nl80211Request::set_regulatory_domain(alpha2, hint)?;
+        let recv = self.send(request)?;
+        Self::handle_ack_response(recv)
+    }
+
+    /// Start DFS radar detection (channel availability check) on a channel.
+    ///
+    /// Waits until the check finishes, is aborted or a radar is detected and
+    /// returns the final radar event. There is no CAC time parameter: the
+    /// kernel derives the CAC time from the DFS CAC time of the channels
+    /// covered by `channel` in the current regulatory domain.
+    ///
+    /// This socket is subscribed to the MLME multicast group to receive the
+    /// result and stays subscribed afterwards. Other events received while
+    /// waiting are kept and returned by later `next_event` calls.
+    pub fn start_radar_detection(
+        &mut self,
+        if_index: u32,
+        channel: ChannelConfig,
+    ) -> Result<RadarEvent> {
+        channel.validate()?;
+        self.subscribe(MulticastGroup::Mlme)?;
+        let request = Nl80211Request::start_radar_detection(if_index, channel)?;
+        let recv = self.send(request)?;
+        Self::handle_ack_response(recv)?;
+
+        while let Some(event) = self.transport.next_event()? {
+            if let Event::Radar {
+                interface_index: Some(interface_index),
+                event:
+                    radar_event @ (RadarEvent::RadarDetected
+                    | RadarEvent::CacFinished
+                    | RadarEvent::CacAborted),
+                ..
+            } = event
+            {
+                if interface_index == if_index {
+                    return Ok(radar_event);
                 }
-                NlPayload::Payload(payload) => {
-                    let handle = payload.attrs().get_attr_handle();
-                    f(&handle)?
+            }
+            self.skipped_events
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .push_back(event);
+        }
+        Err(NlError::new(
+            "Socket closed before radar detection finished",
+        ))
+    }
+
+    /// Get channel survey information of an interface.
+    ///
+    /// If `radio_stats` is set, drivers that support it also report cumulative
+    /// radio statistics as an entry without a frequency.
+    pub fn get_survey(&self, if_index: u32, radio_stats: bool) -> Result<Vec<SurveyInfo>> {
+        let request = Nl80211Request::get_survey(if_index, radio_stats)?;
+        let recv = self.send(request)?;
+
+        let mut responses = Vec::new();
+        Self::handle_dump_response(recv, |handle| {
+            responses.push(TryInto::<SurveyInfo>::try_into(handle)?);
+            Ok(())
+        })?;
+        Ok(responses)
+    }
+
+    /// Remain on a channel for the given duration, e.g. to exchange action
+    /// frames off-channel.
+    ///
+    /// Returns a cookie identifying the request. `Event::RemainOnChannel` is
+    /// delivered to the MLME multicast group when the device is on the channel.
+    pub fn remain_on_channel(
+        &self,
+        if_index: u32,
+        frequency: u32,
+        duration: Duration,
+    ) -> Result<u64> {
+        let request = Nl80211Request::remain_on_channel(if_index, frequency, duration)?;
+        let recv = self.send(request)?;
+
+        let mut cookie = None;
+        Self::handle_dump_response(recv, |handle| {
+            cookie = Some(handle.get_attr_payload_as::<u64>(Attribute::Cookie)?);
+            Ok(())
+        })?;
+        cookie.ok_or_else(|| NlError::new("Remain on channel response did not contain a cookie"))
+    }
+
+    /// Remain on a channel for the given duration, e.g. to exchange action
+    /// frames off-channel.
+    ///
+    /// Returns a cookie identifying the request. `Event::RemainOnChannel` is
+    /// delivered to the MLME multicast group when the device is on the channel.
+    pub fn remain_on_channel_by_wdev(
+        &self,
+        wdev: u64,
+        frequency: u32,
+        duration: Duration,
+    ) -> Result<u64> {
+        let request = Nl80211Request::remain_on_channel_by_wdev(wdev, frequency, duration)?;
+        let recv = self.send(request)?;
+
+        let mut cookie = None;
+        Self::handle_dump_response(recv, |handle| {
+            cookie = Some(handle.get_attr_payload_as::<u64>(Attribute::Cookie)?);
+            Ok(())
+        })?;
+        cookie.ok_or_else(|| NlError::new("Remain on channel response did not contain a cookie"))
+    }
+
+    /// Cancel a remain on channel request identified by its cookie.
+    pub fn cancel_remain_on_channel(&self, if_index: u32, cookie: u64) -> Result<()> {
+        let request = Nl80211Request::cancel_remain_on_channel(if_index, cookie)?;
+        let recv = self.send(request)?;
+        Self::handle_ack_response(recv)
+    }
+
+    /// Cancel a remain on channel request identified by its cookie.
+    pub fn cancel_remain_on_channel_by_wdev(&self, wdev: u64, cookie: u64) -> Result<()> {
+        let request = Nl80211Request::cancel_remain_on_channel_by_wdev(wdev, cookie)?;
+        let recv = self.send(request)?;
+        Self::handle_ack_response(recv)
+    }
+
+    /// Transmit a management frame.
+    ///
+    /// Returns a cookie identifying the transmission unless the frame is sent
+    /// without waiting for an acknowledgement. The result is delivered as
+    /// `Event::FrameTxStatus` to the MLME multicast group.
+    pub fn send_frame(&self, if_index: u32, frame: FrameTx) -> Result<Option<u64>> {
+        let request = Nl80211Request::send_frame(if_index, frame)?;
+        let recv = self.send(request)?;
+
+        let mut cookie = None;
+        Self::handle_dump_response(recv, |handle| {
+            cookie = Some(handle.get_attr_payload_as::<u64>(Attribute::Cookie)?);
+            Ok(())
+        })?;
+        Ok(cookie)
+    }
+
+    /// Transmit a management frame.
+    ///
+    /// Returns a cookie identifying the transmission unless the frame is sent
+    /// without waiting for an acknowledgement. The result is delivered as
+    /// `Event::FrameTxStatus` to the MLME multicast group.
+    pub fn send_frame_by_wdev(&self, wdev: u64, frame: FrameTx) -> Result<Option<u64>> {
+        let request = Nl80211Request::send_frame_by_wdev(wdev, frame)?;
+        let recv = self.send(request)?;
+
+        let mut cookie = None;
+        Self::handle_dump_response(recv, |handle| {
+            cookie = Some(handle.get_attr_payload_as::<u64>(Attribute::Cookie)?);
+            Ok(())
+        })?;
+        Ok(cookie)
+    }
+
+    /// Start a P2P device identified by its wireless device identifier.
+    pub fn start_p2p_device(&self, wdev: u64) -> Result<()> {
+        let request = Nl80211Request::start_p2p_device(wdev)?;
+        let recv = self.send(request)?;
+        Self::handle_ack_response(recv)
+    }
+
+    /// Stop a P2P device identified by its wireless device identifier.
+    pub fn stop_p2p_device(&self, wdev: u64) -> Result<()> {
+        let request = Nl80211Request::stop_p2p_device(wdev)?;
+        let recv = self.send(request)?;
+        Self::handle_ack_response(recv)
+    }
+
+    /// Get packet coalescing rules of a device.
+    pub fn get_coalesce(&self, wiphy_index: u32) -> Result<Vec<CoalesceRule>> {
+        let request = Nl80211Request::get_coalesce(wiphy_index)?;
+        let recv = self.send(request)?;
+
+        let mut rules = Vec::new();
+        Self::handle_dump_response(recv, |handle| {
+            rules.extend(parse_coalesce_rules(handle)?);
+            Ok(())
+        })?;
+        Ok(rules)
+    }
+
+    /// Set packet coalescing rules of a device.
+    ///
+    /// An empty list of rules disables packet coalescing.
+    pub fn set_coalesce(&self, wiphy_index: u32, rules: Vec<CoalesceRule>) -> Result<()> {
+        let request = Nl80211Request::set_coalesce(wiphy_index, rules)?;
+        let recv = self.send(request)?;
+        Self::handle_ack_response(recv)
+    }
+
+    /// Update fast transition (802.11r) IEs of the current connection.
+    ///
+    /// `mdid` is the mobility domain identifier and `ies` contains the FT IEs
+    /// for the driver to use in the next reassociation.
+    pub fn update_ft_ies(&self, if_index: u32, mdid: u16, ies: Vec<u8>) -> Result<()> {
+        let request = Nl80211Request::update_ft_ies(if_index, mdid, ies)?;
+        let recv = self.send(request)?;
+        Self::handle_ack_response(recv)
+    }
+
+    /// Enable or disable dynamic ACK timeout estimation of a device.
+    ///
+    /// Dynamic ACK replaces the static coverage class. Disabling it resets the
+    /// coverage class to 0; set `WiphyParams::coverage_class` afterwards for
+    /// a fixed ACK timeout. Requires `WiphyFeatures::ACKTO_ESTIMATION`.
+    pub fn set_wiphy_dyn_ack(&self, wiphy_index: u32, enabled: bool) -> Result<()> {
+        let request = Nl80211Request::set_wiphy_dyn_ack(wiphy_index, enabled)?;
+        let recv = self.send(request)?;
+        Self::handle_ack_response(recv)
+    }
+
+    pub(crate) fn send(&self, request: Nl80211Request) -> Result<Responses<'_>> {
+        let Some(observer) = &self.observer else {
+            return self.transport.send(request);
+        };
+        let mut observation = Observation::start(observer, request.command());
+        let responses = self.transport.send(request);
+        observation.record(&responses);
+        let responses = responses?;
+        Ok(Box::new(responses.inspect(move |response| {
+            observation.record(response);
+        })))
+    }
+
+    fn handle_consistent_dump<T, F: FnMut(&Attrs<'_, Attribute>) -> Result<T>>(
+        &self,
+        request: impl Fn() -> Result<Nl80211Request>,
+        parse: F,
+    ) -> Result<Vec<T>> {
+        let mut responses = Vec::with_capacity(DUMP_CAPACITY_HINT);
+        self.handle_consistent_dump_into(request, parse, &mut responses)?;
+        Ok(responses)
+    }
+
+    /// Collect a dump into `responses`, reusing its allocation. Dumps are
+    /// retried on generation changes when consistent dumps are enabled.
+    /// `responses` is left empty if the dump fails.
+    fn handle_consistent_dump_into<T, F: FnMut(&Attrs<'_, Attribute>) -> Result<T>>(
+        &self,
+        request: impl Fn() -> Result<Nl80211Request>,
+        mut parse: F,
+        responses: &mut Vec<T>,
+    ) -> Result<()> {
+        let mut retries = 0;
+        let mut generations = HashMap::new();
+        loop {
+            // Cleared before sending so that a failed request leaves no
+            // results of an earlier dump behind.
+            responses.clear();
+            generations.clear();
+            let mut consistent = true;
+            let result = request()
+                .and_then(|request| self.send(request))
+                .and_then(|recv| {
+                    Self::handle_dump_response(recv, |handle| {
+                        if let Ok(generation) =
+                            handle.get_attr_payload_as::<u32>(Attribute::Generation)
+                        {
+                            // Generation counters are tracked per wiphy by the kernel.
+                            let wiphy = handle.get_attr_payload_as::<u32>(Attribute::Wiphy).ok();
+                            consistent &=
+                                *generations.entry(wiphy).or_insert(generation) == generation;
+                        }
+                        responses.push(parse(handle)?);
+                        Ok(())
+                    })
+                });
+            if let Err(err) = result {
+                responses.clear();
+                return Err(err);
+            }
+
+            match self.dump_retries {
+                Some(max_retries) if !consistent => {
+                    if retries >= max_retries {
+                        responses.clear();
+                        return Err(NlError::with_kind(
+                            ErrorKind::InconsistentDump,
+                            format!("Dump was inconsistent after {retries} retries"),
+                        ));
+                    }
+                    debug!("Dump was interrupted by a generation change, retrying");
+                    retries += 1;
                 }
-                NlPayload::Empty | NlPayload::Ack(_) => (),
-            };
+                _ => return Ok(()),
+            }
+        }
+    }
+
+    fn handle_dump_response<F: FnMut(&Attrs<'_, Attribute>) -> Result<()>>(
+        recv: Responses<'_>,
+        mut f: F,
+    ) -> Result<()> {
+        for payload in recv {
+            let payload = payload?;
+            let handle = payload.attrs().get_attr_handle();
+            f(&handle)?
         }
         Ok(())
     }
 
-    fn handle_ack_response(recv: NlRouterReceiverHandle<Nlmsg, Neli80211Header>) -> Result<()> {
+    fn handle_ack_response(recv: Responses<'_>) -> Result<()> {
         for response in recv {
-            let response = response?;
-            match response.nl_payload() {
-                NlPayload::Err(err) => {
-                    debug!("Error when reading ack response: {err}");
-                    return Err(err.clone().into());
-                }
-                NlPayload::Payload(_) | NlPayload::Empty | NlPayload::Ack(_) => (),
-            };
+            response?;
+        }
+        Ok(())
+    }
+}
+
+/// Receiver of the messages not matching a request, i.e. multicast events.
+type EventReceiver = NlRouterReceiverHandle<u16, Genlmsghdr<u8, u16>>;
+
+/// Transport over a netlink socket connected to the kernel.
+struct RouterTransport {
+    socket: NlRouter,
+    nl_type: u16,
+    events: Mutex<EventReceiver>,
+}
+
+impl Transport for RouterTransport {
+    fn send(&self, request: Nl80211Request) -> Result<Responses<'_>> {
+        if cfg!(debug_assertions) && log_enabled!(Level::Debug) && !request.has_key_material() {
+            if let Ok(payload) = request.to_bytes() {
+                let octets = payload.iter().fold(String::new(), |mut output, b| {
+                    let _ = write!(output, "{b:02x} ");
+                    output
+                });
+                debug!("[PAYLOAD] {octets}");
+            }
         }
+        let command = request.command();
+        let recv: NlRouterReceiverHandle<Nlmsg, Neli80211Header> = self
+            .socket
+            .send(self.nl_type, request.nl_flags, request.nl_payload)
+            .map_err(|err| NlError::from(err).with_command(command))?;
+
+        Ok(Box::new(recv.filter_map(move |response| match response {
+            Ok(message) => Response::from_message(message, command),
+            Err(err) => Some(Err(NlError::from(err).with_command(command))),
+        })))
+    }
+
+    fn subscribe(&self, group: MulticastGroup) -> Result<()> {
+        let id = self
+            .socket
+            .resolve_nl_mcast_group("nl80211", group.name())?;
+        self.socket
+            .add_mcast_membership(Groups::new_groups(&[id]))?;
         Ok(())
     }
+
+    fn next_event(&self) -> Result<Option<Event>> {
+        let mut events = self.events.lock().unwrap_or_else(PoisonError::into_inner);
+        while let Some(response) = events.next_typed::<Nlmsg, Neli80211Header>() {
+            let response = response?;
+            if let NlPayload::Payload(payload) = response.nl_payload() {
+                let handle = payload.attrs().get_attr_handle();
+                return Ok(Some(Event::from_message(*payload.cmd(), &handle)?));
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// Station lists delivered by [`NlSocket::watch_stations`]. Polling stops
+/// when the watch is dropped.
+pub struct StationWatch {
+    receiver: Receiver<Result<Vec<WirelessStation>>>,
+    _alive: Arc<()>,
+}
+
+impl StationWatch {
+    /// Wait for the next station list.
+    pub fn recv(&self) -> std::result::Result<Result<Vec<WirelessStation>>, RecvError> {
+        self.receiver.recv()
+    }
+
+    /// Get the next station list if one is available.
+    pub fn try_recv(&self) -> std::result::Result<Result<Vec<WirelessStation>>, TryRecvError> {
+        self.receiver.try_recv()
+    }
+
+    /// Wait for the next station list for at most `timeout`.
+    pub fn recv_timeout(
+        &self,
+        timeout: Duration,
+    ) -> std::result::Result<Result<Vec<WirelessStation>>, RecvTimeoutError> {
+        self.receiver.recv_timeout(timeout)
+    }
+}
+
+impl Iterator for StationWatch {
+    type Item = Result<Vec<WirelessStation>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.receiver.recv().ok()
+    }
+}
+
+/// Network namespace to connect a netlink socket in.
+#[derive(Debug, Clone)]
+pub enum NetworkNamespace {
+    /// Path to a network namespace file, e.g. `/run/netns/<name>` or
+    /// `/proc/<pid>/ns/net`.
+    Path(PathBuf),
+    /// File descriptor referring to a network namespace. The descriptor is
+    /// closed when the last builder using it is dropped.
+    Fd(Arc<OwnedFd>),
+}
+
+impl From<OwnedFd> for NetworkNamespace {
+    fn from(fd: OwnedFd) -> Self {
+        NetworkNamespace::Fd(Arc::new(fd))
+    }
+}
+
+/// Builder for connecting a netlink socket with options.
+///
+/// Non-blocking mode is not offered: the socket is read by the neli router
+/// thread, which relies on blocking receives. Use `AsyncNlSocket` to avoid
+/// blocking the caller.
+#[derive(Clone, Default)]
+pub struct NlSocketBuilder {
+    rx_buffer_size: Option<usize>,
+    tx_buffer_size: Option<usize>,
+    network_namespace: Option<NetworkNamespace>,
+    groups: Vec<MulticastGroup>,
+    dump_retries: Option<u32>,
+    observer: Option<Arc<dyn SocketObserver>>,
+    family_id: Option<u16>,
+}
+
+impl fmt::Debug for NlSocketBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("NlSocketBuilder")
+            .field("rx_buffer_size", &self.rx_buffer_size)
+            .field("tx_buffer_size", &self.tx_buffer_size)
+            .field("network_namespace", &self.network_namespace)
+            .field("groups", &self.groups)
+            .field("dump_retries", &self.dump_retries)
+            .field("observer", &self.observer.is_some())
+            .field("family_id", &self.family_id)
+            .finish()
+    }
+}
+
+impl NlSocketBuilder {
+    /// Set the receive buffer size of the socket in bytes.
+    pub fn rx_buffer_size(mut self, size: usize) -> Self {
+        self.rx_buffer_size = Some(size);
+        self
+    }
+
+    /// Set the transmit buffer size of the socket in bytes.
+    pub fn tx_buffer_size(mut self, size: usize) -> Self {
+        self.tx_buffer_size = Some(size);
+        self
+    }
+
+    /// Connect the socket in the given network namespace instead of the
+    /// namespace of the calling thread.
+    pub fn network_namespace(mut self, namespace: NetworkNamespace) -> Self {
+        self.network_namespace = Some(namespace);
+        self
+    }
+
+    /// Subscribe to a multicast group when connecting.
+    pub fn multicast_group(mut self, group: MulticastGroup) -> Self {
+        self.groups.push(group);
+        self
+    }
+
+    /// Verify that dumps are consistent and retry them at most `max_retries`
+    /// times. See `NlSocket::set_consistent_dumps`.
+    pub fn consistent_dumps(mut self, max_retries: u32) -> Self {
+        self.dump_retries = Some(max_retries);
+        self
+    }
+
+    /// Set an observer called for every request sent by the socket.
+    pub fn observer(mut self, observer: Arc<dyn SocketObserver>) -> Self {
+        self.observer = Some(observer);
+        self
+    }
+
+    /// Use a known nl80211 generic netlink family id instead of resolving it.
+    ///
+    /// By default the family id is resolved by the first socket connected in
+    /// the process and reused by later sockets.
+    pub fn family_id(mut self, id: u16) -> Self {
+        self.family_id = Some(id);
+        self
+    }
+
+    /// Connect netlink socket.
+    ///
+    /// With a network namespace, the socket is created on a short-lived
+    /// thread that enters the namespace, so the namespace of the calling
+    /// thread never changes.
+    pub fn connect(self) -> Result<NlSocket> {
+        let (rx_buffer_size, tx_buffer_size) = (self.rx_buffer_size, self.tx_buffer_size);
+        let (socket, events) = match &self.network_namespace {
+            Some(namespace) => thread::scope(|scope| {
+                scope
+                    .spawn(|| {
+                        match namespace {
+                            NetworkNamespace::Path(path) => {
+                                let target = File::open(path).map_err(NlError::new)?;
+                                setns(target, CloneFlags::CLONE_NEWNET)
+                                    .map_err(NlError::from_errno)?;
+                            }
+                            NetworkNamespace::Fd(fd) => {
+                                setns(fd.as_fd(), CloneFlags::CLONE_NEWNET)
+                                    .map_err(NlError::from_errno)?;
+                            }
+                        }
+                        connect_router(rx_buffer_size, tx_buffer_size)
+                    })
+                    .join()
+                    .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+            })?,
+            None => connect_router(rx_buffer_size, tx_buffer_size)?,
+        };
+
+        let nl_type = match self.family_id.or_else(|| NL80211_FAMILY_ID.get().copied()) {
+            Some(id) => id,
+            None => {
+                let id = socket.resolve_genl_family("nl80211")?;
+                *NL80211_FAMILY_ID.get_or_init(|| id)
+            }
+        };
+        let mut socket = NlSocket::from_transport(Arc::new(RouterTransport {
+            socket,
+            nl_type,
+            events: Mutex::new(events),
+        }));
+        socket.set_consistent_dumps(self.dump_retries);
+        socket.set_observer(self.observer);
+        for group in self.groups {
+            socket.subscribe(group)?;
+        }
+        Ok(socket)
+    }
+}
+
+/// Connect a generic netlink router in the network namespace of the calling
+/// thread and set its buffer sizes.
+fn connect_router(
+    rx_buffer_size: Option<usize>,
+    tx_buffer_size: Option<usize>,
+) -> Result<(NlRouter, EventReceiver)> {
+    let (socket, events) = NlRouter::connect(NlFamily::Generic, None, Groups::empty())?;
+    if rx_buffer_size.is_some() || tx_buffer_size.is_some() {
+        // SAFETY: the descriptor is owned by the router, which outlives
+        // this borrow.
+        let fd = unsafe { BorrowedFd::borrow_raw(router_fd(&socket)?) };
+        if let Some(size) = rx_buffer_size {
+            setsockopt(&fd, sockopt::RcvBuf, &size).map_err(NlError::from_errno)?;
+        }
+        if let Some(size) = tx_buffer_size {
+            setsockopt(&fd, sockopt::SndBuf, &size).map_err(NlError::from_errno)?;
+        }
+    }
+    Ok((socket, events))
+}
+
+/// Find the descriptor of the generic netlink socket of `router`.
+///
+/// neli does not expose the descriptor, so the socket is looked up by its port
+/// id in `/proc/thread-self/net/netlink` and matched to a descriptor by inode.
+/// Must be called in the network namespace the router was connected in.
+fn router_fd(router: &NlRouter) -> Result<RawFd> {
+    const NETLINK_GENERIC: &str = "16";
+    let sockets = fs::read_to_string("/proc/thread-self/net/netlink").map_err(NlError::new)?;
+    let pid = router.pid().to_string();
+    // Columns: sk Eth Pid Groups Rmem Wmem Dump Locks Drops Inode
+    let inode = sockets
+        .lines()
+        .skip(1)
+        .map(|line| line.split_whitespace().collect::<Vec<_>>())
+        .find(|columns| columns.get(1) == Some(&NETLINK_GENERIC) && columns.get(2) == Some(&&*pid))
+        .and_then(|columns| columns.get(9).map(|inode| format!("socket:[{inode}]")))
+        .ok_or_else(|| NlError::with_kind(ErrorKind::Socket, "Netlink socket not found"))?;
+
+    for entry in fs::read_dir("/proc/thread-self/fd").map_err(NlError::new)? {
+        let entry = entry.map_err(NlError::new)?;
+        let Ok(target) = fs::read_link(entry.path()) else {
+            continue;
+        };
+        if target.as_os_str() == inode.as_str() {
+            if let Some(fd) = entry
+                .file_name()
+                .to_str()
+                .and_then(|name| name.parse().ok())
+            {
+                return Ok(fd);
+            }
+        }
+    }
+    Err(NlError::with_kind(
+        ErrorKind::Socket,
+        "Netlink socket descriptor not found",
+    ))
 }
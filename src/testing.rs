@@ -0,0 +1,305 @@
+//! Mock socket for testing code built on [`NlSocket`] without root
+//! privileges or wireless hardware.
+//!
+//! Responses are given as raw nl80211 attribute buffers, the bytes following
+//! the generic netlink header of a kernel message, and decoded with the same
+//! parsers as real responses.
+//!
+//! ```rust
+//! use netlink_wi::testing::{attribute, MockSocket};
+//! use netlink_wi::Command;
+//!
+//! let mock = MockSocket::new();
+//! // NL80211_ATTR_IFINDEX = 3, NL80211_ATTR_IFNAME = 4
+//! let mut attributes = attribute(3, &7u32.to_ne_bytes());
+//! attributes.extend(attribute(4, b"wlan0\0"));
+//! mock.respond(Command::GetInterface, vec![attributes]);
+//!
+//! let socket = mock.socket();
+//! let interfaces = socket.list_interfaces().unwrap();
+//! assert_eq!(interfaces[0].interface_index, 7);
+//! ```
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use neli::nl::NlPayload;
+use nix::errno::Errno;
+
+use crate::attributes::encode_attribute;
+use crate::commands::Command;
+use crate::error::{NlError, Result};
+use crate::event::{Event, MulticastGroup};
+use crate::netlink::{message_from_attributes, Nl80211Request, Response};
+use crate::transport::{Responses, Transport};
+use crate::NlSocket;
+
+/// Socket returning canned responses instead of communicating with the kernel.
+///
+/// Clones share the same responses and recorded requests.
+#[derive(Clone, Default)]
+pub struct MockSocket {
+    state: Arc<Mutex<MockState>>,
+}
+
+/// Attribute buffers of the response messages, or a positive errno value.
+type MockResponse = std::result::Result<Vec<Vec<u8>>, i32>;
+
+#[derive(Default)]
+struct MockState {
+    responses: Vec<(Command, MockResponse)>,
+    events: VecDeque<(Command, Vec<u8>)>,
+    requests: Vec<Command>,
+    groups: Vec<MulticastGroup>,
+}
+
+impl MockSocket {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a `NlSocket` using this mock as its transport.
+    pub fn socket(&self) -> NlSocket {
+        NlSocket::from_transport(Arc::new(self.clone()))
+    }
+
+    /// Respond to the next request with `command`. Each buffer contains the
+    /// attributes of one response message.
+    ///
+    /// Requests without a queued response are acknowledged with no messages.
+    pub fn respond(&self, command: Command, messages: Vec<Vec<u8>>) {
+        self.state().responses.push((command, Ok(messages)));
+    }
+
+    /// Fail the next request with `command` with the given positive errno
+    /// value, e.g. 95 for `EOPNOTSUPP`.
+    pub fn fail(&self, command: Command, errno: i32) {
+        self.state().responses.push((command, Err(errno)));
+    }
+
+    /// Queue an event to be returned by `NlSocket::next_event`.
+    pub fn push_event(&self, command: Command, attributes: Vec<u8>) {
+        self.state().events.push_back((command, attributes));
+    }
+
+    /// Commands of the requests sent so far, in order.
+    pub fn requests(&self) -> Vec<Command> {
+        self.state().requests.clone()
+    }
+
+    /// Multicast groups subscribed to so far.
+    pub fn groups(&self) -> Vec<MulticastGroup> {
+        self.state().groups.clone()
+    }
+
+    fn state(&self) -> MutexGuard<'_, MockState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Transport for MockSocket {
+    fn send(&self, request: Nl80211Request) -> Result<Responses<'_>> {
+        let command = match &request.nl_payload {
+            NlPayload::Payload(payload) => *payload.cmd(),
+            _ => Command::Unspec,
+        };
+        let mut state = self.state();
+        state.requests.push(command);
+        let response = match state.responses.iter().position(|(c, _)| *c == command) {
+            Some(index) => state.responses.remove(index).1,
+            None => Ok(Vec::new()),
+        };
+
+        match response {
+            Ok(messages) => Ok(Box::new(messages.into_iter().map(move |attributes| {
+                Response::from_payload(message_from_attributes(command, &attributes)?)
+            }))),
+            Err(errno) => {
+                let err = NlError::from_errno(Errno::from_raw(errno)).with_command(command);
+                Ok(Box::new(std::iter::once(Err(err))))
+            }
+        }
+    }
+
+    fn subscribe(&self, group: MulticastGroup) -> Result<()> {
+        self.state().groups.push(group);
+        Ok(())
+    }
+
+    fn next_event(&self) -> Result<Option<Event>> {
+        let Some((command, attributes)) = self.state().events.pop_front() else {
+            return Ok(None);
+        };
+        let message = message_from_attributes(command, &attributes)?;
+        let handle = message.attrs().get_attr_handle();
+        Ok(Some(Event::from_message(command, &handle)?))
+    }
+}
+
+/// Encode a netlink attribute with the given type and payload, including the
+/// header and padding.
+pub fn attribute(attr_type: u16, payload: &[u8]) -> Vec<u8> {
+    let mut buffer = Vec::with_capacity((4 + payload.len()).next_multiple_of(4));
+    encode_attribute(&mut buffer, attr_type, payload);
+    buffer
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::interface::{InterfaceType, MacAddress};
+
+    // Attribute types from nl80211.h.
+    const ATTR_WIPHY: u16 = 1;
+    const ATTR_WIPHY_NAME: u16 = 2;
+    const ATTR_IFINDEX: u16 = 3;
+    const ATTR_IFNAME: u16 = 4;
+    const ATTR_IFTYPE: u16 = 5;
+    const ATTR_MAC: u16 = 6;
+    const ATTR_STA_INFO: u16 = 21;
+    const ATTR_WIPHY_RETRY_SHORT: u16 = 61;
+    const ATTR_WIPHY_RETRY_LONG: u16 = 62;
+    const STA_INFO_INACTIVE_TIME: u16 = 1;
+    const STA_INFO_SIGNAL: u16 = 7;
+
+    #[test]
+    fn test_list_interfaces() {
+        let mock = MockSocket::new();
+        let mut attributes = attribute(ATTR_WIPHY, &1u32.to_ne_bytes());
+        attributes.extend(attribute(ATTR_IFINDEX, &7u32.to_ne_bytes()));
+        attributes.extend(attribute(ATTR_IFNAME, b"wlan0\0"));
+        // NL80211_IFTYPE_STATION
+        attributes.extend(attribute(ATTR_IFTYPE, &2u32.to_ne_bytes()));
+        attributes.extend(attribute(ATTR_MAC, &[2, 0, 0, 0, 0, 1]));
+        mock.respond(Command::GetInterface, vec![attributes]);
+
+        let interfaces = mock.socket().list_interfaces().unwrap();
+        assert_eq!(interfaces.len(), 1);
+        assert_eq!(interfaces[0].wiphy_index, 1);
+        assert_eq!(interfaces[0].interface_index, 7);
+        assert_eq!(interfaces[0].name, "wlan0");
+        assert_eq!(interfaces[0].interface_type, Some(InterfaceType::Station));
+        assert_eq!(interfaces[0].mac, MacAddress::from([2, 0, 0, 0, 0, 1]));
+        assert_eq!(mock.requests(), vec![Command::GetInterface]);
+    }
+
+    #[test]
+    fn test_list_stations() {
+        let mock = MockSocket::new();
+        let mut station_info = attribute(STA_INFO_SIGNAL, &[-52i8 as u8]);
+        station_info.extend(attribute(STA_INFO_INACTIVE_TIME, &250u32.to_ne_bytes()));
+        let mut attributes = attribute(ATTR_IFINDEX, &7u32.to_ne_bytes());
+        attributes.extend(attribute(ATTR_MAC, &[2, 0, 0, 0, 0, 2]));
+        attributes.extend(attribute(ATTR_STA_INFO, &station_info));
+        mock.respond(Command::GetStation, vec![attributes]);
+
+        let stations = mock.socket().list_stations(7).unwrap();
+        assert_eq!(stations.len(), 1);
+        assert_eq!(stations[0].interface_index, 7);
+        assert_eq!(stations[0].mac, MacAddress::from([2, 0, 0, 0, 0, 2]));
+        assert_eq!(stations[0].signal, Some(-52i8 as u8));
+        assert_eq!(stations[0].inactive_time, Some(Duration::from_millis(250)));
+    }
+
+    #[test]
+    fn test_list_stations_lazy() {
+        let mock = MockSocket::new();
+        let mut station_info = attribute(STA_INFO_SIGNAL, &[-52i8 as u8]);
+        station_info.extend(attribute(STA_INFO_INACTIVE_TIME, &250u32.to_ne_bytes()));
+        let mut attributes = attribute(ATTR_IFINDEX, &7u32.to_ne_bytes());
+        attributes.extend(attribute(ATTR_MAC, &[2, 0, 0, 0, 0, 2]));
+        attributes.extend(attribute(ATTR_STA_INFO, &station_info));
+        mock.respond(Command::GetStation, vec![attributes]);
+
+        let stations = mock.socket().list_stations_lazy(7).unwrap();
+        assert_eq!(stations.len(), 1);
+        assert_eq!(stations[0].mac, MacAddress::from([2, 0, 0, 0, 0, 2]));
+        assert_eq!(stations[0].signal(), Some(-52i8 as u8));
+        assert_eq!(stations[0].average_signal(), None);
+        let station = stations[0].station().unwrap();
+        assert_eq!(station.interface_index, 7);
+        assert_eq!(station.mac, MacAddress::from([2, 0, 0, 0, 0, 2]));
+        assert_eq!(station.signal, Some(-52i8 as u8));
+        assert_eq!(station.inactive_time, Some(Duration::from_millis(250)));
+    }
+
+    #[test]
+    fn test_list_physical_devices_merges_messages() {
+        let mock = MockSocket::new();
+        let mut first = attribute(ATTR_WIPHY, &0u32.to_ne_bytes());
+        first.extend(attribute(ATTR_WIPHY_NAME, b"phy0\0"));
+        first.extend(attribute(ATTR_WIPHY_RETRY_SHORT, &[7]));
+        let mut second = attribute(ATTR_WIPHY, &0u32.to_ne_bytes());
+        second.extend(attribute(ATTR_WIPHY_RETRY_LONG, &[4]));
+        let mut other = attribute(ATTR_WIPHY, &1u32.to_ne_bytes());
+        other.extend(attribute(ATTR_WIPHY_NAME, b"phy1\0"));
+        mock.respond(Command::GetWiphy, vec![first, second, other]);
+
+        let mut devices = mock.socket().list_physical_devices().unwrap();
+        devices.sort_by_key(|device| device.wiphy_index);
+        assert_eq!(devices.len(), 2);
+        assert_eq!(devices[0].name, "phy0");
+        assert_eq!(devices[0].retry_short, Some(7));
+        assert_eq!(devices[0].retry_long, Some(4));
+        assert_eq!(devices[1].name, "phy1");
+        assert_eq!(devices[1].retry_short, None);
+        // Without protocol features a single unsplit dump is requested.
+        assert_eq!(
+            mock.requests(),
+            vec![Command::GetProtocolFeatures, Command::GetWiphy]
+        );
+    }
+
+    #[test]
+    fn test_list_physical_devices_without_protocol_features() {
+        let mock = MockSocket::new();
+        // EOPNOTSUPP from kernels without GetProtocolFeatures.
+        mock.fail(Command::GetProtocolFeatures, 95);
+        let mut attributes = attribute(ATTR_WIPHY, &0u32.to_ne_bytes());
+        attributes.extend(attribute(ATTR_WIPHY_NAME, b"phy0\0"));
+        mock.respond(Command::GetWiphy, vec![attributes]);
+
+        let devices = mock.socket().list_physical_devices().unwrap();
+        assert_eq!(devices.len(), 1);
+        assert_eq!(devices[0].name, "phy0");
+    }
+
+    #[test]
+    fn test_list_physical_devices_protocol_features_error() {
+        let mock = MockSocket::new();
+        // EPERM is not a missing command and must not fall back.
+        mock.fail(Command::GetProtocolFeatures, 1);
+
+        let err = mock.socket().list_physical_devices().unwrap_err();
+        assert_eq!(err.command(), Some(Command::GetProtocolFeatures));
+        assert_eq!(mock.requests(), vec![Command::GetProtocolFeatures]);
+    }
+
+    #[test]
+    fn test_list_stations_into_clears_on_error() {
+        let mock = MockSocket::new();
+        let mut attributes = attribute(ATTR_IFINDEX, &7u32.to_ne_bytes());
+        attributes.extend(attribute(ATTR_MAC, &[2, 0, 0, 0, 0, 2]));
+        mock.respond(Command::GetStation, vec![attributes]);
+        // ENODEV after the interface is removed.
+        mock.fail(Command::GetStation, 19);
+
+        let mut stations = Vec::new();
+        mock.socket().list_stations_into(7, &mut stations).unwrap();
+        assert_eq!(stations.len(), 1);
+        mock.socket()
+            .list_stations_into(7, &mut stations)
+            .unwrap_err();
+        assert!(stations.is_empty());
+    }
+
+    #[test]
+    fn test_failed_request() {
+        let mock = MockSocket::new();
+        mock.fail(Command::GetStation, 95);
+
+        let err = mock.socket().list_stations(7).unwrap_err();
+        assert_eq!(err.command(), Some(Command::GetStation));
+    }
+}
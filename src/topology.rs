@@ -0,0 +1,77 @@
+use std::collections::HashMap;
+
+use crate::interface::WirelessInterface;
+use crate::station::WirelessStation;
+use crate::wiphy::PhysicalDevice;
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Snapshot of all physical devices with their interfaces and stations.
+pub struct Topology {
+    /// Physical devices keyed by wiphy index.
+    pub devices: HashMap<u32, DeviceTopology>,
+}
+
+impl Topology {
+    pub(crate) fn new(
+        devices: Vec<PhysicalDevice>,
+        interfaces: Vec<(WirelessInterface, Vec<WirelessStation>)>,
+    ) -> Self {
+        let mut devices: HashMap<u32, DeviceTopology> = devices
+            .into_iter()
+            .map(|device| {
+                let topology = DeviceTopology {
+                    device,
+                    interfaces: Vec::new(),
+                };
+                (topology.device.wiphy_index, topology)
+            })
+            .collect();
+        for (interface, stations) in interfaces {
+            if let Some(device) = devices.get_mut(&interface.wiphy_index) {
+                device.interfaces.push(InterfaceTopology {
+                    interface,
+                    stations,
+                });
+            }
+        }
+        Self { devices }
+    }
+
+    /// Get an interface by its network interface index.
+    pub fn interface(&self, if_index: u32) -> Option<&InterfaceTopology> {
+        self.devices
+            .values()
+            .flat_map(|device| &device.interfaces)
+            .find(|i| i.interface.interface_index == if_index)
+    }
+
+    /// Iterate over stations of all interfaces.
+    pub fn stations(&self) -> impl Iterator<Item = &WirelessStation> {
+        self.devices
+            .values()
+            .flat_map(|device| &device.interfaces)
+            .flat_map(|interface| &interface.stations)
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Physical device and its interfaces.
+pub struct DeviceTopology {
+    /// Physical device information.
+    pub device: PhysicalDevice,
+    /// Interfaces of the device. Wireless devices without a network interface,
+    /// such as P2P devices, are included with an interface index of 0.
+    pub interfaces: Vec<InterfaceTopology>,
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Interface and its associated stations.
+pub struct InterfaceTopology {
+    /// Interface information.
+    pub interface: WirelessInterface,
+    /// Stations associated to the interface.
+    pub stations: Vec<WirelessStation>,
+}
@@ -0,0 +1,19 @@
+use crate::error::Result;
+use crate::event::{Event, MulticastGroup};
+use crate::netlink::{Nl80211Request, Response};
+
+/// Response messages of a request. Error responses are
+/// returned as errors, acknowledgements are skipped.
+pub(crate) type Responses<'a> = Box<dyn Iterator<Item = Result<Response>> + 'a>;
+
+/// Transport used by `NlSocket` to exchange nl80211 messages.
+pub(crate) trait Transport: Send + Sync {
+    /// Send a request and return its responses.
+    fn send(&self, request: Nl80211Request) -> Result<Responses<'_>>;
+
+    /// Subscribe to a nl80211 multicast group.
+    fn subscribe(&self, group: MulticastGroup) -> Result<()>;
+
+    /// Wait for the next event, or return `None` if no more events arrive.
+    fn next_event(&self) -> Result<Option<Event>>;
+}
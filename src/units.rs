@@ -0,0 +1,70 @@
+use std::fmt;
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Power in mBm (100 * dBm).
+pub struct Mbm(pub i32);
+
+impl From<Dbm> for Mbm {
+    fn from(value: Dbm) -> Self {
+        Mbm((value.0 * 100.0).round() as i32)
+    }
+}
+
+impl fmt::Display for Mbm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} mBm", self.0)
+    }
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Power in dBm.
+pub struct Dbm(pub f32);
+
+impl From<Mbm> for Dbm {
+    fn from(value: Mbm) -> Self {
+        Dbm(value.0 as f32 / 100.0)
+    }
+}
+
+impl fmt::Display for Dbm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.2} dBm", self.0)
+    }
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Frequency in MHz.
+pub struct Mhz(pub u32);
+
+impl fmt::Display for Mhz {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} MHz", self.0)
+    }
+}
+
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Frequency in kHz.
+pub struct Khz(pub u32);
+
+impl Khz {
+    /// Convert to MHz, rounding down.
+    pub fn to_mhz(self) -> Mhz {
+        Mhz(self.0 / 1000)
+    }
+}
+
+impl From<Mhz> for Khz {
+    fn from(value: Mhz) -> Self {
+        Khz(value.0 * 1000)
+    }
+}
+
+impl fmt::Display for Khz {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} kHz", self.0)
+    }
+}
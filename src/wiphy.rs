@@ -1,14 +1,33 @@
+use std::fmt;
+
+use bitflags::bitflags;
 use log::debug;
 use neli::attr::Attribute as NeliAttribute;
+use neli::consts::genl::NlAttrType;
 use neli::err::DeError;
+use neli::genl::Nlattr;
+use neli::types::Buffer;
 
 use super::attributes::Attribute;
+use super::attributes::CoalesceCondition as NlCoalesceCondition;
+use super::attributes::DfsState as NlDfsState;
+use super::attributes::InterfaceType as NlInterfaceType;
+use super::attributes::SarType as NlSarType;
 use crate::{
-    attributes::{Attrs, Band, BandAttr, FrequencyAttr},
-    interface::MacAddress,
+    attributes::{
+        Attrs, Band, BandAttr, CoalesceRuleAttr, FrequencyAttr, IftypeAkmAttr, PacketPatternAttr,
+        SarAttr, SarSpecsAttr, WmmRuleAttr,
+    },
+    commands::Command,
+    interface::{InterfaceType, MacAddress},
+    key::{AkmSuite, CipherSuite},
+    reg_domain::{RegulatoryDomain, RegulatoryRule},
+    survey::{FrequencySurvey, SurveyInfo},
+    units::{Khz, Mbm, Mhz},
 };
 
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Physical wireless device information returned from netlink.
 pub struct PhysicalDevice {
     /// Device index.
@@ -24,46 +43,281 @@ pub struct PhysicalDevice {
     pub band_5ghz: Option<WifiBand>,
     /// 6 GHz band.
     pub band_6ghz: Option<WifiBand>,
+    /// 60 GHz band.
+    pub band_60ghz: Option<WifiBand>,
+    /// Sub-1 GHz (S1G) band.
+    pub band_s1ghz: Option<WifiBand>,
+    /// Light communication (LC) band.
+    pub band_lc: Option<WifiBand>,
     /// Indicates if device is self-managing its regulatory information.
     pub self_managed_reg: bool,
     /// Device MAC address (BSSID).
     pub mac: Option<MacAddress>,
+    /// Maximum number of MAC addresses in an access point ACL.
+    pub max_acl_mac_addresses: Option<u32>,
+    /// Supported interface types.
+    pub supported_interface_types: Vec<InterfaceType>,
+    /// Interface types that are purely managed in software, without
+    /// requiring driver or hardware support.
+    pub software_interface_types: Vec<InterfaceType>,
+    /// Commands supported by the device. Commands unknown to this library are
+    /// represented as `Command::UnrecognizedConst`.
+    pub supported_commands: Vec<Command>,
+    /// Short retry limit.
+    pub retry_short: Option<u8>,
+    /// Long retry limit.
+    pub retry_long: Option<u8>,
+    /// Fragmentation threshold in bytes. `u32::MAX` means fragmentation is
+    /// disabled.
+    pub frag_threshold: Option<u32>,
+    /// RTS threshold in bytes. `u32::MAX` means RTS/CTS is disabled.
+    pub rts_threshold: Option<u32>,
+    /// Coverage class as defined by IEEE 802.11-2007 7.3.2.9.
+    pub coverage_class: Option<u8>,
+    /// Transmit queue parameters.
+    pub txq_params: TxqParams,
+    /// SAR (specific absorption rate) capabilities.
+    pub sar_capabilities: Option<SarCapabilities>,
+    /// Device feature flags.
+    pub features: WiphyFeatures,
+    /// Extended device features.
+    pub extended_features: ExtendedFeatures,
+    /// Supported cipher suites.
+    pub cipher_suites: Vec<CipherSuite>,
+    /// Supported AKM suites per interface type, with at most one entry per
+    /// interface type.
+    pub interface_akm_suites: Vec<(InterfaceType, Vec<AkmSuite>)>,
+    /// Attributes not decoded by this library as raw attribute type and
+    /// payload pairs. Only collected with the `raw-attributes` feature.
+    pub raw_attributes: Vec<(u16, Vec<u8>)>,
 }
 
 impl PhysicalDevice {
-    pub(crate) fn merge(&mut self, other: &Self) {
+    /// Check if the device supports the given extended feature.
+    pub fn is_supported(&self, feature: ExtendedFeature) -> bool {
+        self.extended_features.is_supported(feature)
+    }
+
+    /// Check if the device supports the given command.
+    pub fn supports_command(&self, command: Command) -> bool {
+        self.supported_commands.contains(&command)
+    }
+
+    /// Evaluate which modes of operation and channel widths are currently
+    /// allowed on each channel of the device.
+    ///
+    /// Channel flags reported by the device are combined with the rules of
+    /// the given regulatory domain. If the domain contains no rules, only the
+    /// channel flags are taken into account. HT and VHT capabilities limit the
+    /// channel widths only on the 2.4 and 5 GHz bands.
+    pub fn channel_plan(&self, reg_domain: &RegulatoryDomain) -> Vec<ChannelAvailability> {
+        let bands = [
+            (&self.band_2ghz, true),
+            (&self.band_5ghz, true),
+            (&self.band_6ghz, false),
+            (&self.band_60ghz, false),
+            (&self.band_s1ghz, false),
+            (&self.band_lc, false),
+        ];
+        bands
+            .into_iter()
+            .filter_map(|(band, ht_vht)| band.as_ref().map(|band| (band, ht_vht)))
+            .flat_map(|(band, ht_vht)| {
+                band.frequencies.iter().map(move |frequency| {
+                    self.channel_availability(band, ht_vht, frequency, reg_domain)
+                })
+            })
+            .collect()
+    }
+
+    /// Iterate over supported frequencies of all bands.
+    pub fn frequencies(&self) -> impl Iterator<Item = &Frequency> {
+        [
+            &self.band_2ghz,
+            &self.band_5ghz,
+            &self.band_6ghz,
+            &self.band_60ghz,
+            &self.band_s1ghz,
+            &self.band_lc,
+        ]
+        .into_iter()
+        .flatten()
+        .flat_map(|band| band.frequencies.iter())
+    }
+
+    /// Join channel survey results with the supported frequencies of the
+    /// device. Frequencies without a survey entry have no survey values set.
+    pub fn frequency_surveys(&self, surveys: &[SurveyInfo]) -> Vec<FrequencySurvey> {
+        self.frequencies()
+            .map(|frequency| {
+                let survey = surveys
+                    .iter()
+                    .find(|survey| survey.frequency == Some(frequency.frequency));
+                FrequencySurvey {
+                    frequency: frequency.clone(),
+                    noise: survey.and_then(|survey| survey.noise),
+                    active_time: survey.and_then(|survey| survey.active_time),
+                    busy_time: survey.and_then(|survey| survey.busy_time),
+                    in_use: survey.is_some_and(|survey| survey.in_use),
+                }
+            })
+            .collect()
+    }
+
+    fn supports_interface_type(&self, interface_type: InterfaceType) -> bool {
+        self.supported_interface_types.is_empty()
+            || self.supported_interface_types.contains(&interface_type)
+    }
+
+    fn channel_availability(
+        &self,
+        band: &WifiBand,
+        ht_vht: bool,
+        frequency: &Frequency,
+        reg_domain: &RegulatoryDomain,
+    ) -> ChannelAvailability {
+        let rule = reg_domain.rule_for_frequency(frequency.frequency);
+        let permitted = !frequency.disabled && (rule.is_some() || reg_domain.rules.is_empty());
+        let max_bandwidth = |bandwidth: u32| {
+            rule_allows(rule, |rule| {
+                rule.auto_bandwidth || rule.max_bandwidth.0 >= bandwidth
+            })
+        };
+
+        let radiation = permitted
+            && !frequency.no_ir
+            && rule_allows(rule, |rule| !rule.no_ir)
+            && (!frequency.radar_detection
+                || frequency.dfs_state == Some(DfsChannelState::Available));
+        let ht40 = permitted
+            && (!ht_vht
+                || band
+                    .ht_capabilities
+                    .as_ref()
+                    .is_some_and(|ht| ht.channel_width_40mhz))
+            && !(frequency.no_ht40_minus && frequency.no_ht40_plus)
+            && rule_allows(rule, |rule| !(rule.no_ht40_minus && rule.no_ht40_plus))
+            && max_bandwidth(40_000);
+        let vht80 = ht40
+            && (!ht_vht || band.vht_capabilities.is_some())
+            && !frequency.no_80mhz
+            && rule_allows(rule, |rule| !rule.no_80mhz)
+            && max_bandwidth(80_000);
+        let vht160 = vht80
+            && (!ht_vht
+                || band
+                    .vht_capabilities
+                    .as_ref()
+                    .is_some_and(|vht| vht.channel_width_160mhz))
+            && !frequency.no_160mhz
+            && rule_allows(rule, |rule| !rule.no_160mhz)
+            && max_bandwidth(160_000);
+        let eht320 = vht160
+            && !frequency.no_320mhz
+            && !frequency.no_eht
+            && rule_allows(rule, |rule| !rule.no_320mhz && !rule.no_eht)
+            && max_bandwidth(320_000);
+
+        ChannelAvailability {
+            frequency: frequency.frequency,
+            access_point: radiation && self.supports_interface_type(InterfaceType::AccessPoint),
+            adhoc: radiation && self.supports_interface_type(InterfaceType::Adhoc),
+            monitor: permitted && self.supports_interface_type(InterfaceType::Monitor),
+            radar_detection: frequency.radar_detection || rule.is_some_and(|rule| rule.dfs),
+            max_eirp: rule.map(|rule| rule.max_eirp),
+            width_20mhz: permitted,
+            width_40mhz: ht40,
+            width_80mhz: vht80,
+            width_160mhz: vht160,
+            width_320mhz: eht320,
+        }
+    }
+
+    pub(crate) fn merge(&mut self, other: Self) {
         if other.self_managed_reg {
             self.self_managed_reg = true;
         }
         if other.mac.is_some() {
             self.mac = other.mac;
         }
-        if let Some(other_band_2ghz) = &other.band_2ghz {
-            if let Some(self_band_2ghz) = &mut self.band_2ghz {
-                self_band_2ghz
-                    .frequencies
-                    .extend(other_band_2ghz.frequencies.clone());
-            } else {
-                self.band_2ghz = other.band_2ghz.clone();
-            }
+        if other.max_acl_mac_addresses.is_some() {
+            self.max_acl_mac_addresses = other.max_acl_mac_addresses;
         }
-        if let Some(other_band_5ghz) = &other.band_5ghz {
-            if let Some(self_band_5ghz) = &mut self.band_5ghz {
-                self_band_5ghz
-                    .frequencies
-                    .extend(other_band_5ghz.frequencies.clone());
-            } else {
-                self.band_5ghz = other.band_5ghz.clone();
-            }
+        if !other.supported_interface_types.is_empty() {
+            self.supported_interface_types = other.supported_interface_types;
         }
-        if let Some(other_band_6ghz) = &other.band_6ghz {
-            if let Some(self_band_6ghz) = &mut self.band_6ghz {
-                self_band_6ghz
-                    .frequencies
-                    .extend(other_band_6ghz.frequencies.clone());
-            } else {
-                self.band_6ghz = other.band_6ghz.clone();
-            }
+        if !other.software_interface_types.is_empty() {
+            self.software_interface_types = other.software_interface_types;
+        }
+        if other.retry_short.is_some() {
+            self.retry_short = other.retry_short;
+        }
+        if other.retry_long.is_some() {
+            self.retry_long = other.retry_long;
+        }
+        if other.frag_threshold.is_some() {
+            self.frag_threshold = other.frag_threshold;
+        }
+        if other.rts_threshold.is_some() {
+            self.rts_threshold = other.rts_threshold;
+        }
+        if other.coverage_class.is_some() {
+            self.coverage_class = other.coverage_class;
+        }
+        if other.txq_params.limit.is_some() {
+            self.txq_params.limit = other.txq_params.limit;
+        }
+        if other.txq_params.memory_limit.is_some() {
+            self.txq_params.memory_limit = other.txq_params.memory_limit;
+        }
+        if other.txq_params.quantum.is_some() {
+            self.txq_params.quantum = other.txq_params.quantum;
+        }
+        if other.sar_capabilities.is_some() {
+            self.sar_capabilities = other.sar_capabilities;
+        }
+        self.features |= other.features;
+        if !other.extended_features.is_empty() {
+            self.extended_features = other.extended_features;
+        }
+        if !other.supported_commands.is_empty() {
+            self.supported_commands = other.supported_commands;
+        }
+        if !other.cipher_suites.is_empty() {
+            self.cipher_suites = other.cipher_suites;
+        }
+        for (interface_type, akm_suites) in other.interface_akm_suites {
+            set_akm_suites(&mut self.interface_akm_suites, interface_type, akm_suites);
+        }
+        merge_band(&mut self.band_2ghz, other.band_2ghz);
+        merge_band(&mut self.band_5ghz, other.band_5ghz);
+        merge_band(&mut self.band_6ghz, other.band_6ghz);
+        merge_band(&mut self.band_60ghz, other.band_60ghz);
+        merge_band(&mut self.band_s1ghz, other.band_s1ghz);
+        merge_band(&mut self.band_lc, other.band_lc);
+        self.raw_attributes.extend(other.raw_attributes);
+    }
+}
+
+fn set_akm_suites(
+    suites: &mut Vec<(InterfaceType, Vec<AkmSuite>)>,
+    interface_type: InterfaceType,
+    akm_suites: Vec<AkmSuite>,
+) {
+    match suites
+        .iter_mut()
+        .find(|(existing, _)| *existing == interface_type)
+    {
+        Some((_, existing)) => *existing = akm_suites,
+        None => suites.push((interface_type, akm_suites)),
+    }
+}
+
+fn merge_band(band: &mut Option<WifiBand>, other: Option<WifiBand>) {
+    if let Some(other) = other {
+        match band {
+            Some(band) => band.merge(other),
+            None => *band = Some(other),
         }
     }
 }
@@ -74,6 +328,7 @@ impl TryFrom<&Attrs<'_, Attribute>> for PhysicalDevice {
     fn try_from(handle: &Attrs<'_, Attribute>) -> Result<Self, Self::Error> {
         let mut device = PhysicalDevice::default();
         let mut wiphy_bands_attr: Option<Attrs<'_, Band>> = None;
+        let mut iftype_akm_suites_attr: Option<Attrs<'_, u16>> = None;
 
         for attr in handle.iter() {
             match attr.nla_type().nla_type() {
@@ -87,12 +342,67 @@ impl TryFrom<&Attrs<'_, Attribute>> for PhysicalDevice {
                     device.mac = Some(attr.get_payload_as()?);
                 }
                 Attribute::WiphySelfManagedReg => device.self_managed_reg = true,
-                Attribute::WiphyRetryShort
-                | Attribute::WiphyRetryLong
-                | Attribute::WiphyFragThreshold
-                | Attribute::WiphyRtsThreshold
-                | Attribute::WiphyCoverageClass
-                | Attribute::MaxNumScanSsids
+                Attribute::MacAclMax => {
+                    device.max_acl_mac_addresses = Some(attr.get_payload_as()?);
+                }
+                Attribute::SupportedIftypes => {
+                    device.supported_interface_types = parse_interface_types(attr)?;
+                }
+                Attribute::SoftwareIftypes => {
+                    device.software_interface_types = parse_interface_types(attr)?;
+                }
+                Attribute::SupportedCommands => {
+                    let sub_handle: Attrs<'_, u16> = attr.get_attr_handle()?;
+                    for sub_attr in sub_handle.iter() {
+                        let command: u32 = sub_attr.get_payload_as()?;
+                        device.supported_commands.push(Command::from(command as u8));
+                    }
+                }
+                Attribute::FeatureFlags => {
+                    device.features = WiphyFeatures::from_bits_retain(attr.get_payload_as()?);
+                }
+                Attribute::ExtFeatures => {
+                    device.extended_features = ExtendedFeatures {
+                        bitmap: attr.payload().as_ref().to_vec(),
+                    };
+                }
+                Attribute::TxqLimit => {
+                    device.txq_params.limit = Some(attr.get_payload_as()?);
+                }
+                Attribute::TxqMemoryLimit => {
+                    device.txq_params.memory_limit = Some(attr.get_payload_as()?);
+                }
+                Attribute::TxqQuantum => {
+                    device.txq_params.quantum = Some(attr.get_payload_as()?);
+                }
+                Attribute::SarSpec => {
+                    let sub_handle: Attrs<'_, SarAttr> = attr.get_attr_handle()?;
+                    device.sar_capabilities = Some(sub_handle.try_into()?);
+                }
+                Attribute::CipherSuites => {
+                    device.cipher_suites = parse_u32_array(attr.payload().as_ref())
+                        .map(CipherSuite::from)
+                        .collect();
+                }
+                Attribute::IfTypeAkmSuites => {
+                    iftype_akm_suites_attr = Some(attr.get_attr_handle()?);
+                }
+                Attribute::WiphyRetryShort => {
+                    device.retry_short = Some(attr.get_payload_as()?);
+                }
+                Attribute::WiphyRetryLong => {
+                    device.retry_long = Some(attr.get_payload_as()?);
+                }
+                Attribute::WiphyFragThreshold => {
+                    device.frag_threshold = Some(attr.get_payload_as()?);
+                }
+                Attribute::WiphyRtsThreshold => {
+                    device.rts_threshold = Some(attr.get_payload_as()?);
+                }
+                Attribute::WiphyCoverageClass => {
+                    device.coverage_class = Some(attr.get_payload_as()?);
+                }
+                Attribute::MaxNumScanSsids
                 | Attribute::MaxNumSchedScanSsids
                 | Attribute::MaxScanIeLen
                 | Attribute::MaxSchedScanIeLen
@@ -105,40 +415,66 @@ impl TryFrom<&Attrs<'_, Attribute>> for PhysicalDevice {
                 | Attribute::SupportApUapsd
                 | Attribute::TdlsSupport
                 | Attribute::TdlsExternalSetup
-                | Attribute::CipherSuites
                 | Attribute::MaxNumPmkids
                 | Attribute::ControlPortEthertype
                 | Attribute::WiphyAntennaAvailTx
                 | Attribute::WiphyAntennaAvailRx
                 | Attribute::WiphyAntennaTx
                 | Attribute::WiphyAntennaRx
-                | Attribute::SupportedIftypes
-                | Attribute::SupportedCommands
                 | Attribute::MaxRemainOnChannelDuration
                 | Attribute::OffchannelTxOk
                 | Attribute::WowlanTriggersSupported
-                | Attribute::SoftwareIftypes
                 | Attribute::InterfaceCombinations
-                | Attribute::FeatureFlags
                 | Attribute::HtCapabilityMask
                 | Attribute::EmlCapability
                 | Attribute::PeerMeasurements
                 | Attribute::RxFrameTypes
                 | Attribute::TxFrameTypes
-                | Attribute::TxqQuantum
-                | Attribute::TxqMemoryLimit
-                | Attribute::TxqLimit
                 | Attribute::TxqStats
                 | Attribute::NanDual
                 | Attribute::IftypeExtCapa
-                | Attribute::ExtFeatures
                 | Attribute::ExtCapa
                 | Attribute::ExtCapaMask
                 | Attribute::MaxCsaCounters
                 | Attribute::VhtCapabilityMask
-                | Attribute::SarSpec
                 | Attribute::MacAddrs => (), // TODO: Implement all wiphy attributes.
-                unhandled => debug!("Unhandled station attribute 'Attribute::{unhandled:?}'"),
+                unhandled => {
+                    debug!("Unhandled wiphy attribute 'Attribute::{unhandled:?}'");
+                    if cfg!(feature = "raw-attributes") {
+                        device
+                            .raw_attributes
+                            .push((unhandled.into(), attr.payload().as_ref().to_vec()));
+                    }
+                }
+            }
+        }
+        if let Some(sub_handle) = iftype_akm_suites_attr {
+            for sub_attr in sub_handle.iter() {
+                let akm_handle: Attrs<'_, IftypeAkmAttr> = sub_attr.get_attr_handle()?;
+                let mut interface_types = Vec::new();
+                let mut akm_suites: Vec<AkmSuite> = Vec::new();
+                for akm_attr in akm_handle.iter() {
+                    match akm_attr.nla_type().nla_type() {
+                        IftypeAkmAttr::Iftypes => {
+                            interface_types = parse_interface_types(akm_attr)?;
+                        }
+                        IftypeAkmAttr::Suites => {
+                            akm_suites = parse_u32_array(akm_attr.payload().as_ref())
+                                .map(AkmSuite::from)
+                                .collect();
+                        }
+                        unhandled => {
+                            debug!("Unhandled iftype AKM attribute 'IftypeAkmAttr::{unhandled:?}'")
+                        }
+                    }
+                }
+                for interface_type in interface_types {
+                    set_akm_suites(
+                        &mut device.interface_akm_suites,
+                        interface_type,
+                        akm_suites.clone(),
+                    );
+                }
             }
         }
         if let Some(sub_handle) = wiphy_bands_attr {
@@ -156,7 +492,18 @@ impl TryFrom<&Attrs<'_, Attribute>> for PhysicalDevice {
                         let sub_handle: Attrs<'_, BandAttr> = sub_attr.get_attr_handle()?;
                         device.band_6ghz = Some(sub_handle.try_into()?);
                     }
-                    Band::Band60ghz | Band::BandS1ghz | Band::BandLc => (),
+                    Band::Band60ghz => {
+                        let sub_handle: Attrs<'_, BandAttr> = sub_attr.get_attr_handle()?;
+                        device.band_60ghz = Some(sub_handle.try_into()?);
+                    }
+                    Band::BandS1ghz => {
+                        let sub_handle: Attrs<'_, BandAttr> = sub_attr.get_attr_handle()?;
+                        device.band_s1ghz = Some(sub_handle.try_into()?);
+                    }
+                    Band::BandLc => {
+                        let sub_handle: Attrs<'_, BandAttr> = sub_attr.get_attr_handle()?;
+                        device.band_lc = Some(sub_handle.try_into()?);
+                    }
                     unhandled => debug!("Unhandled band 'Band::{unhandled:?}'"),
                 }
             }
@@ -165,11 +512,600 @@ impl TryFrom<&Attrs<'_, Attribute>> for PhysicalDevice {
     }
 }
 
+/// Parse a nested attribute containing interface types as flag attributes.
+fn parse_interface_types<T: NlAttrType>(
+    attr: &Nlattr<T, Buffer>,
+) -> Result<Vec<InterfaceType>, DeError> {
+    let sub_handle: Attrs<'_, u16> = attr.get_attr_handle()?;
+    Ok(sub_handle
+        .iter()
+        .map(|sub_attr| NlInterfaceType::from(*sub_attr.nla_type().nla_type() as u32).into())
+        .collect())
+}
+
+/// Parse an attribute payload containing an array of u32 values.
+fn parse_u32_array(payload: &[u8]) -> impl Iterator<Item = u32> + '_ {
+    payload
+        .chunks_exact(4)
+        .map(|bytes| u32::from_ne_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+bitflags! {
+    /// Device feature flags.
+    ///
+    /// nl80211_feature_flags enum from:
+    /// https://github.com/torvalds/linux/blob/master/include/uapi/linux/nl80211.h
+    #[derive(Clone, Copy, Default, PartialEq, Eq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct WiphyFeatures: u32 {
+        /// Supports socket TX status reporting.
+        const SK_TX_STATUS               = 1<<0;
+        /// Supports IBSS with HT datarates.
+        const HT_IBSS                    = 1<<1;
+        /// Supports configuring the AP client inactivity timer.
+        const INACTIVITY_TIMER           = 1<<2;
+        /// Supports cellular base station regulatory hints.
+        const CELL_BASE_REG_HINTS        = 1<<3;
+        /// P2P Device interfaces need a channel to be configured.
+        const P2P_DEVICE_NEEDS_CHANNEL   = 1<<4;
+        /// Supports SAE authentication in the driver.
+        const SAE                        = 1<<5;
+        /// Supports low priority scan.
+        const LOW_PRIORITY_SCAN          = 1<<6;
+        /// Supports flushing the scan cache on scan.
+        const SCAN_FLUSH                 = 1<<7;
+        /// Supports scanning using an AP interface.
+        const AP_SCAN                    = 1<<8;
+        /// Supports per-interface transmit power setting.
+        const VIF_TXPOWER                = 1<<9;
+        /// Requires userspace to perform OBSS scans.
+        const NEED_OBSS_SCAN             = 1<<10;
+        /// Supports CT window setting in P2P GO mode.
+        const P2P_GO_CTWIN               = 1<<11;
+        /// Supports opportunistic power save in P2P GO mode.
+        const P2P_GO_OPPPS               = 1<<12;
+        /// Advertises channel limits in beacons.
+        const ADVERTISE_CHAN_LIMITS      = 1<<14;
+        /// Supports full AP client state.
+        const FULL_AP_CLIENT_STATE       = 1<<15;
+        /// Supports a userspace mesh peering manager.
+        const USERSPACE_MPM              = 1<<16;
+        /// Supports active monitor interfaces.
+        const ACTIVE_MONITOR             = 1<<17;
+        /// Supports changing the channel width in AP mode.
+        const AP_MODE_CHAN_WIDTH_CHANGE  = 1<<18;
+        /// Adds the DS Parameter Set element in probe requests.
+        const DS_PARAM_SET_IE_IN_PROBES  = 1<<19;
+        /// Adds the WFA TPC Report element in probe requests.
+        const WFA_TPC_IE_IN_PROBES       = 1<<20;
+        /// Supports the Quiet element.
+        const QUIET                      = 1<<21;
+        /// Supports TX power insertion in frames.
+        const TX_POWER_INSERTION         = 1<<22;
+        /// Supports dynamic ACK timeout estimation.
+        const ACKTO_ESTIMATION           = 1<<23;
+        /// Supports static spatial multiplexing power save.
+        const STATIC_SMPS                = 1<<24;
+        /// Supports dynamic spatial multiplexing power save.
+        const DYNAMIC_SMPS               = 1<<25;
+        /// Supports WMM admission control.
+        const SUPPORTS_WMM_ADMISSION     = 1<<26;
+        /// Supports setting the MAC address when creating an interface.
+        const MAC_ON_CREATE              = 1<<27;
+        /// Supports TDLS channel switching.
+        const TDLS_CHANNEL_SWITCH        = 1<<28;
+        /// Supports random MAC address in scans.
+        const SCAN_RANDOM_MAC_ADDR       = 1<<29;
+        /// Supports random MAC address in scheduled scans.
+        const SCHED_SCAN_RANDOM_MAC_ADDR = 1<<30;
+        /// Supports random MAC address in net-detect scans.
+        const ND_RANDOM_MAC_ADDR         = 1<<31;
+    }
+}
+
+impl fmt::Debug for WiphyFeatures {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+bitflags! {
+    /// Protocol features supported by the kernel.
+    ///
+    /// nl80211_protocol_features enum from:
+    /// https://github.com/torvalds/linux/blob/master/include/uapi/linux/nl80211.h
+    #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+    #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+    pub struct ProtocolFeatures: u32 {
+        /// Physical device information can be dumped in several messages.
+        const SPLIT_WIPHY_DUMP = 1<<0;
+    }
+}
+
+/// Extended device feature.
+///
+/// nl80211_ext_feature_index enum from:
+/// https://github.com/torvalds/linux/blob/master/include/uapi/linux/nl80211.h
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ExtendedFeature(pub u32);
+
+impl ExtendedFeature {
+    /// Supports VHT datarates in IBSS.
+    pub const VHT_IBSS: Self = Self(0);
+    /// Supports radio resource measurements.
+    pub const RRM: Self = Self(1);
+    /// Supports MU-MIMO air sniffer in monitor mode.
+    pub const MU_MIMO_AIR_SNIFFER: Self = Self(2);
+    /// Reports the actual scan start time.
+    pub const SCAN_START_TIME: Self = Self(3);
+    /// Reports the TSF of the parent BSS in scan results.
+    pub const BSS_PARENT_TSF: Self = Self(4);
+    /// Supports configuring the scan dwell time.
+    pub const SET_SCAN_DWELL: Self = Self(5);
+    /// Supports legacy beacon rate setting.
+    pub const BEACON_RATE_LEGACY: Self = Self(6);
+    /// Supports HT beacon rate setting.
+    pub const BEACON_RATE_HT: Self = Self(7);
+    /// Supports VHT beacon rate setting.
+    pub const BEACON_RATE_VHT: Self = Self(8);
+    /// Supports FILS shared key authentication in station mode.
+    pub const FILS_STA: Self = Self(9);
+    /// Supports random transmitter address in management frames when not connected.
+    pub const MGMT_TX_RANDOM_TA: Self = Self(10);
+    /// Supports random transmitter address in management frames when connected.
+    pub const MGMT_TX_RANDOM_TA_CONNECTED: Self = Self(11);
+    /// Supports relative RSSI in scheduled scans.
+    pub const SCHED_SCAN_RELATIVE_RSSI: Self = Self(12);
+    /// Supports multiple RSSI thresholds for connection quality monitoring.
+    pub const CQM_RSSI_LIST: Self = Self(13);
+    /// Supports FILS shared key authentication offload.
+    pub const FILS_SK_OFFLOAD: Self = Self(14);
+    /// Supports 4-way handshake offload with PSK in station mode.
+    pub const FOUR_WAY_HANDSHAKE_STA_PSK: Self = Self(15);
+    /// Supports 4-way handshake offload with 802.1X in station mode.
+    pub const FOUR_WAY_HANDSHAKE_STA_1X: Self = Self(16);
+    /// Supports FILS max channel time in scans.
+    pub const FILS_MAX_CHANNEL_TIME: Self = Self(17);
+    /// Supports accepting broadcast probe responses.
+    pub const ACCEPT_BCAST_PROBE_RESP: Self = Self(18);
+    /// Supports sending OCE probe requests at a high TX rate.
+    pub const OCE_PROBE_REQ_HIGH_TX_RATE: Self = Self(19);
+    /// Supports OCE probe request deferral and suppression.
+    pub const OCE_PROBE_REQ_DEFERRAL_SUPPRESSION: Self = Self(20);
+    /// Supports optional management frame protection.
+    pub const MFP_OPTIONAL: Self = Self(21);
+    /// Supports low span scans.
+    pub const LOW_SPAN_SCAN: Self = Self(22);
+    /// Supports low power scans.
+    pub const LOW_POWER_SCAN: Self = Self(23);
+    /// Supports high accuracy scans.
+    pub const HIGH_ACCURACY_SCAN: Self = Self(24);
+    /// Supports DFS offload.
+    pub const DFS_OFFLOAD: Self = Self(25);
+    /// Supports control port frames over nl80211.
+    pub const CONTROL_PORT_OVER_NL80211: Self = Self(26);
+    /// Reports the signal strength of ACK frames.
+    pub const ACK_SIGNAL_SUPPORT: Self = Self(27);
+    /// Supports intermediate software transmit queues.
+    pub const TXQS: Self = Self(28);
+    /// Supports random sequence numbers in probe requests.
+    pub const SCAN_RANDOM_SN: Self = Self(29);
+    /// Supports minimal probe request content.
+    pub const SCAN_MIN_PREQ_CONTENT: Self = Self(30);
+    /// Supports rekeying of the pairwise key with key ID 0.
+    pub const CAN_REPLACE_PTK0: Self = Self(31);
+    /// Supports enabling the FTM responder.
+    pub const ENABLE_FTM_RESPONDER: Self = Self(32);
+    /// Supports airtime fairness scheduling.
+    pub const AIRTIME_FAIRNESS: Self = Self(33);
+    /// Supports PMKSA caching in AP mode.
+    pub const AP_PMKSA_CACHING: Self = Self(34);
+    /// Supports band specific RSSI thresholds in scheduled scans.
+    pub const SCHED_SCAN_BAND_SPECIFIC_RSSI_THOLD: Self = Self(35);
+    /// Supports extended key IDs for pairwise keys.
+    pub const EXT_KEY_ID: Self = Self(36);
+    /// Supports per-station transmit power setting.
+    pub const STA_TX_PWR: Self = Self(37);
+    /// Supports SAE authentication offload in station mode.
+    pub const SAE_OFFLOAD: Self = Self(38);
+    /// Supports VLAN offload.
+    pub const VLAN_OFFLOAD: Self = Self(39);
+    /// Supports airtime queue limits.
+    pub const AQL: Self = Self(40);
+    /// Supports beacon protection.
+    pub const BEACON_PROTECTION: Self = Self(41);
+    /// Supports disabling pre-authentication frames over the control port.
+    pub const CONTROL_PORT_NO_PREAUTH: Self = Self(42);
+    /// Supports protected target wake time.
+    pub const PROTECTED_TWT: Self = Self(43);
+    /// Supports deleting IBSS stations.
+    pub const DEL_IBSS_STA: Self = Self(44);
+    /// Supports multicast management frame registrations.
+    pub const MULTICAST_REGISTRATIONS: Self = Self(45);
+    /// Supports beacon protection in station mode.
+    pub const BEACON_PROTECTION_CLIENT: Self = Self(46);
+    /// Supports scan frequencies in kHz.
+    pub const SCAN_FREQ_KHZ: Self = Self(47);
+    /// Reports TX status of control port frames over nl80211.
+    pub const CONTROL_PORT_OVER_NL80211_TX_STATUS: Self = Self(48);
+    /// Supports operating channel validation.
+    pub const OPERATING_CHANNEL_VALIDATION: Self = Self(49);
+    /// Supports 4-way handshake offload with PSK in AP mode.
+    pub const FOUR_WAY_HANDSHAKE_AP_PSK: Self = Self(50);
+    /// Supports SAE authentication offload in AP mode.
+    pub const SAE_OFFLOAD_AP: Self = Self(51);
+    /// Supports FILS discovery frames.
+    pub const FILS_DISCOVERY: Self = Self(52);
+    /// Supports unsolicited broadcast probe responses.
+    pub const UNSOL_BCAST_PROBE_RESP: Self = Self(53);
+    /// Supports HE beacon rate setting.
+    pub const BEACON_RATE_HE: Self = Self(54);
+    /// Supports secure LTF measurement.
+    pub const SECURE_LTF: Self = Self(55);
+    /// Supports secure round trip time measurement.
+    pub const SECURE_RTT: Self = Self(56);
+    /// Supports protected range negotiation and measurement.
+    pub const PROT_RANGE_NEGO_AND_MEASURE: Self = Self(57);
+    /// Supports BSS color collision detection.
+    pub const BSS_COLOR: Self = Self(58);
+    /// Supports FILS encryption and decryption offload in AP mode.
+    pub const FILS_CRYPTO_OFFLOAD: Self = Self(59);
+    /// Supports background radar detection.
+    pub const RADAR_BACKGROUND: Self = Self(60);
+    /// Supports changing the MAC address while the interface is up.
+    pub const POWERED_ADDR_CHANGE: Self = Self(61);
+    /// Supports preamble puncturing.
+    pub const PUNCT: Self = Self(62);
+    /// Supports secure NAN.
+    pub const SECURE_NAN: Self = Self(63);
+    /// Supports random transmitter address in authentication and deauthentication frames.
+    pub const AUTH_AND_DEAUTH_RANDOM_TA: Self = Self(64);
+    /// Supports OWE offload in station mode.
+    pub const OWE_OFFLOAD: Self = Self(65);
+    /// Supports OWE offload in AP mode.
+    pub const OWE_OFFLOAD_AP: Self = Self(66);
+    /// Supports DFS concurrency.
+    pub const DFS_CONCURRENT: Self = Self(67);
+    /// Supports signaling and payload protected A-MSDUs.
+    pub const SPP_AMSDU_SUPPORT: Self = Self(68);
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Set of extended features supported by a device.
+pub struct ExtendedFeatures {
+    bitmap: Vec<u8>,
+}
+
+impl ExtendedFeatures {
+    /// Check if the given feature is supported.
+    pub fn is_supported(&self, feature: ExtendedFeature) -> bool {
+        let byte = (feature.0 / 8) as usize;
+        let bit = feature.0 % 8;
+        self.bitmap
+            .get(byte)
+            .map(|value| value & (1 << bit) != 0)
+            .unwrap_or(false)
+    }
+
+    fn is_empty(&self) -> bool {
+        self.bitmap.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Device parameters that can be changed with `set_wiphy_params`.
+///
+/// Parameters that are `None` are left unchanged.
+pub struct WiphyParams {
+    /// Short retry limit.
+    pub retry_short: Option<u8>,
+    /// Long retry limit.
+    pub retry_long: Option<u8>,
+    /// Fragmentation threshold in bytes. `u32::MAX` disables fragmentation.
+    pub frag_threshold: Option<u32>,
+    /// RTS threshold in bytes. `u32::MAX` disables RTS/CTS.
+    pub rts_threshold: Option<u32>,
+    /// Coverage class as defined by IEEE 802.11-2007 7.3.2.9. Setting the
+    /// coverage class disables dynamic ACK timeout estimation.
+    pub coverage_class: Option<u8>,
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Transmit queue parameters of a device.
+pub struct TxqParams {
+    /// Maximum number of packets in the transmit queues.
+    pub limit: Option<u32>,
+    /// Maximum memory usage of the transmit queues in bytes.
+    pub memory_limit: Option<u32>,
+    /// Transmit queue scheduler quantum in bytes.
+    pub quantum: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Packet coalescing rule.
+pub struct CoalesceRule {
+    /// Maximum delay in milliseconds a packet is held for coalescing.
+    pub delay: u32,
+    /// Condition when the patterns cause packets to be coalesced.
+    pub condition: CoalesceCondition,
+    /// Packet patterns of the rule.
+    pub patterns: Vec<PacketPattern>,
+}
+
+impl TryFrom<Attrs<'_, CoalesceRuleAttr>> for CoalesceRule {
+    type Error = DeError;
+
+    fn try_from(handle: Attrs<'_, CoalesceRuleAttr>) -> Result<Self, Self::Error> {
+        let mut rule = CoalesceRule {
+            delay: 0,
+            condition: CoalesceCondition::Match,
+            patterns: Vec::new(),
+        };
+        for attr in handle.iter() {
+            match attr.nla_type().nla_type() {
+                CoalesceRuleAttr::Delay => rule.delay = attr.get_payload_as()?,
+                CoalesceRuleAttr::Condition => {
+                    let condition: NlCoalesceCondition = attr.get_payload_as()?;
+                    rule.condition = condition.into();
+                }
+                CoalesceRuleAttr::PktPattern => {
+                    let sub_handle: Attrs<'_, u16> = attr.get_attr_handle()?;
+                    for sub_attr in sub_handle.iter() {
+                        let pattern_handle: Attrs<'_, PacketPatternAttr> =
+                            sub_attr.get_attr_handle()?;
+                        rule.patterns.push(pattern_handle.try_into()?);
+                    }
+                }
+                unhandled => {
+                    debug!("Unhandled coalesce rule attribute 'CoalesceRuleAttr::{unhandled:?}'")
+                }
+            }
+        }
+        Ok(rule)
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Condition of a packet coalescing rule.
+pub enum CoalesceCondition {
+    /// Coalesce packets that match the patterns.
+    Match,
+    /// Coalesce packets that do not match the patterns.
+    NoMatch,
+    /// Kernel returned an unknown condition.
+    Unknown,
+}
+
+impl From<NlCoalesceCondition> for CoalesceCondition {
+    fn from(value: NlCoalesceCondition) -> Self {
+        match value {
+            NlCoalesceCondition::Match => CoalesceCondition::Match,
+            NlCoalesceCondition::NoMatch => CoalesceCondition::NoMatch,
+            NlCoalesceCondition::UnrecognizedConst(_) => CoalesceCondition::Unknown,
+        }
+    }
+}
+
+impl From<CoalesceCondition> for NlCoalesceCondition {
+    fn from(value: CoalesceCondition) -> Self {
+        match value {
+            CoalesceCondition::Match => NlCoalesceCondition::Match,
+            CoalesceCondition::NoMatch => NlCoalesceCondition::NoMatch,
+            CoalesceCondition::Unknown => NlCoalesceCondition::Match,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Packet pattern to match received packets against.
+pub struct PacketPattern {
+    /// Bitmask selecting the pattern bytes to match, one bit per byte starting
+    /// from the least significant bit of the first byte.
+    pub mask: Vec<u8>,
+    /// Bytes to match.
+    pub pattern: Vec<u8>,
+    /// Offset in bytes in the received packet where matching starts.
+    pub offset: u32,
+}
+
+impl TryFrom<Attrs<'_, PacketPatternAttr>> for PacketPattern {
+    type Error = DeError;
+
+    fn try_from(handle: Attrs<'_, PacketPatternAttr>) -> Result<Self, Self::Error> {
+        let mut pattern = PacketPattern::default();
+        for attr in handle.iter() {
+            match attr.nla_type().nla_type() {
+                PacketPatternAttr::Mask => pattern.mask = attr.payload().as_ref().to_vec(),
+                PacketPatternAttr::Pattern => pattern.pattern = attr.payload().as_ref().to_vec(),
+                PacketPatternAttr::Offset => pattern.offset = attr.get_payload_as()?,
+                unhandled => {
+                    debug!("Unhandled packet pattern attribute 'PacketPatternAttr::{unhandled:?}'")
+                }
+            }
+        }
+        Ok(pattern)
+    }
+}
+
+/// Parse coalesce rules from a `GetCoalesce` response.
+pub(crate) fn parse_coalesce_rules(
+    handle: &Attrs<'_, Attribute>,
+) -> Result<Vec<CoalesceRule>, DeError> {
+    let mut rules = Vec::new();
+    for attr in handle.iter() {
+        match attr.nla_type().nla_type() {
+            Attribute::CoalesceRule => {
+                let sub_handle: Attrs<'_, u16> = attr.get_attr_handle()?;
+                for sub_attr in sub_handle.iter() {
+                    let rule_handle: Attrs<'_, CoalesceRuleAttr> = sub_attr.get_attr_handle()?;
+                    rules.push(rule_handle.try_into()?);
+                }
+            }
+            unhandled => debug!("Unhandled coalesce attribute 'Attribute::{unhandled:?}'"),
+        }
+    }
+    Ok(rules)
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// SAR (specific absorption rate) capabilities of a device.
+pub struct SarCapabilities {
+    /// Type of the SAR power limits.
+    pub sar_type: Option<SarType>,
+    /// Frequency ranges that can have separate power limits.
+    pub frequency_ranges: Vec<SarFrequencyRange>,
+}
+
+impl TryFrom<Attrs<'_, SarAttr>> for SarCapabilities {
+    type Error = DeError;
+
+    fn try_from(handle: Attrs<'_, SarAttr>) -> Result<Self, Self::Error> {
+        let mut capabilities = SarCapabilities::default();
+        for attr in handle.iter() {
+            match attr.nla_type().nla_type() {
+                SarAttr::Type => {
+                    let sar_type: NlSarType = attr.get_payload_as()?;
+                    capabilities.sar_type = Some(sar_type.into());
+                }
+                SarAttr::Specs => {
+                    let sub_handle: Attrs<'_, u16> = attr.get_attr_handle()?;
+                    for (index, sub_attr) in sub_handle.iter().enumerate() {
+                        let mut range = SarFrequencyRange {
+                            index: index as u32,
+                            ..Default::default()
+                        };
+                        let range_handle: Attrs<'_, SarSpecsAttr> = sub_attr.get_attr_handle()?;
+                        for range_attr in range_handle.iter() {
+                            match range_attr.nla_type().nla_type() {
+                                SarSpecsAttr::StartFreq => {
+                                    range.start_frequency = Khz(range_attr.get_payload_as()?);
+                                }
+                                SarSpecsAttr::EndFreq => {
+                                    range.end_frequency = Khz(range_attr.get_payload_as()?);
+                                }
+                                unhandled => debug!(
+                                    "Unhandled SAR specs attribute 'SarSpecsAttr::{unhandled:?}'"
+                                ),
+                            }
+                        }
+                        capabilities.frequency_ranges.push(range);
+                    }
+                }
+                unhandled => debug!("Unhandled SAR attribute 'SarAttr::{unhandled:?}'"),
+            }
+        }
+        Ok(capabilities)
+    }
+}
+
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Frequency range with a separately configurable SAR power limit.
+pub struct SarFrequencyRange {
+    /// Index of the range, used with `SarLimit::range_index`.
+    pub index: u32,
+    /// Start frequency.
+    pub start_frequency: Khz,
+    /// End frequency.
+    pub end_frequency: Khz,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Type of SAR power limits.
+pub enum SarType {
+    /// Power limits are specified in units of 0.25 dBm.
+    Power,
+    /// Kernel returned an unknown SAR type.
+    Unknown,
+}
+
+impl From<NlSarType> for SarType {
+    fn from(value: NlSarType) -> Self {
+        match value {
+            NlSarType::Power => SarType::Power,
+            NlSarType::UnrecognizedConst(_) => SarType::Unknown,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// SAR power limit for a frequency range.
+pub struct SarLimit {
+    /// Index of the frequency range (see `SarFrequencyRange::index`).
+    pub range_index: u32,
+    /// Power limit in units of 0.25 dBm.
+    pub power: i32,
+}
+
+/// Check a regulatory rule condition. Missing rule allows everything.
+fn rule_allows(rule: Option<&RegulatoryRule>, check: impl Fn(&RegulatoryRule) -> bool) -> bool {
+    match rule {
+        Some(rule) => check(rule),
+        None => true,
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Availability of a channel in the current regulatory domain, returned by
+/// `PhysicalDevice::channel_plan`.
+pub struct ChannelAvailability {
+    /// Control channel frequency.
+    pub frequency: Mhz,
+    /// Access point operation is allowed.
+    pub access_point: bool,
+    /// IBSS (ad-hoc) operation is allowed.
+    pub adhoc: bool,
+    /// Monitor operation is allowed.
+    pub monitor: bool,
+    /// Radar detection is required before initiating radiation.
+    pub radar_detection: bool,
+    /// Maximum allowed EIRP from the regulatory rule.
+    pub max_eirp: Option<Mbm>,
+    /// 20 MHz operation is allowed.
+    pub width_20mhz: bool,
+    /// 40 MHz operation is allowed.
+    pub width_40mhz: bool,
+    /// 80 MHz operation is allowed.
+    pub width_80mhz: bool,
+    /// 160 MHz operation is allowed.
+    pub width_160mhz: bool,
+    /// 320 MHz operation is allowed.
+    pub width_320mhz: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Wi-Fi band.
 pub struct WifiBand {
     /// Supported frequencies in MHz.
     pub frequencies: Vec<Frequency>,
+    /// HT (802.11n) capabilities.
+    pub ht_capabilities: Option<HtCapabilities>,
+    /// VHT (802.11ac) capabilities.
+    pub vht_capabilities: Option<VhtCapabilities>,
+}
+
+impl WifiBand {
+    fn merge(&mut self, other: Self) {
+        self.frequencies.extend(other.frequencies);
+        if other.ht_capabilities.is_some() {
+            self.ht_capabilities = other.ht_capabilities;
+        }
+        if other.vht_capabilities.is_some() {
+            self.vht_capabilities = other.vht_capabilities;
+        }
+    }
 }
 
 impl TryFrom<Attrs<'_, BandAttr>> for WifiBand {
@@ -177,6 +1113,8 @@ impl TryFrom<Attrs<'_, BandAttr>> for WifiBand {
 
     fn try_from(handle: Attrs<'_, BandAttr>) -> Result<Self, Self::Error> {
         let mut band = WifiBand::default();
+        let mut ht_capabilities: Option<HtCapabilities> = None;
+        let mut vht_capabilities: Option<VhtCapabilities> = None;
         for attr in handle.iter() {
             match attr.nla_type().nla_type() {
                 BandAttr::Frequencies => {
@@ -187,36 +1125,295 @@ impl TryFrom<Attrs<'_, BandAttr>> for WifiBand {
                         band.frequencies.push(freq);
                     }
                 }
+                BandAttr::HtCapabilities => {
+                    let capabilities: u16 = attr.get_payload_as()?;
+                    let ht = ht_capabilities.get_or_insert_with(HtCapabilities::default);
+                    ht.ldpc_coding = capabilities & (1 << 0) != 0;
+                    ht.channel_width_40mhz = capabilities & (1 << 1) != 0;
+                    ht.greenfield = capabilities & (1 << 4) != 0;
+                    ht.short_gi_20mhz = capabilities & (1 << 5) != 0;
+                    ht.short_gi_40mhz = capabilities & (1 << 6) != 0;
+                }
+                BandAttr::HtMcsSet => {
+                    let ht = ht_capabilities.get_or_insert_with(HtCapabilities::default);
+                    // The first 77 bits of the MCS set form the RX MCS bitmask.
+                    ht.supported_mcs = attr
+                        .payload()
+                        .as_ref()
+                        .iter()
+                        .take(10)
+                        .enumerate()
+                        .flat_map(|(byte, value)| {
+                            (0..8)
+                                .filter(move |bit| value & (1 << bit) != 0)
+                                .map(move |bit| (byte * 8 + bit) as u8)
+                        })
+                        .filter(|index| *index <= 76)
+                        .collect();
+                }
+                BandAttr::HtAmpduFactor => {
+                    let factor: u8 = attr.get_payload_as()?;
+                    let ht = ht_capabilities.get_or_insert_with(HtCapabilities::default);
+                    ht.max_ampdu_length = (1 << (13 + factor as u32)) - 1;
+                }
+                BandAttr::HtAmpduDensity => {
+                    let ht = ht_capabilities.get_or_insert_with(HtCapabilities::default);
+                    ht.min_mpdu_start_spacing = attr.get_payload_as()?;
+                }
+                BandAttr::VhtCapabilities => {
+                    let capabilities: u32 = attr.get_payload_as()?;
+                    let vht = vht_capabilities.get_or_insert_with(VhtCapabilities::default);
+                    vht.max_mpdu_length = match capabilities & 0b11 {
+                        0 => 3895,
+                        1 => 7991,
+                        _ => 11454,
+                    };
+                    let channel_widths = (capabilities >> 2) & 0b11;
+                    vht.channel_width_160mhz = channel_widths >= 1;
+                    vht.channel_width_80p80mhz = channel_widths == 2;
+                    vht.ldpc_coding = capabilities & (1 << 4) != 0;
+                    vht.short_gi_80mhz = capabilities & (1 << 5) != 0;
+                    vht.short_gi_160mhz = capabilities & (1 << 6) != 0;
+                    vht.su_beamformer = capabilities & (1 << 11) != 0;
+                    vht.su_beamformee = capabilities & (1 << 12) != 0;
+                    vht.mu_beamformer = capabilities & (1 << 19) != 0;
+                    vht.mu_beamformee = capabilities & (1 << 20) != 0;
+                }
+                BandAttr::VhtMcsSet => {
+                    let payload = attr.payload().as_ref();
+                    if payload.len() >= 8 {
+                        let vht = vht_capabilities.get_or_insert_with(VhtCapabilities::default);
+                        vht.rx_max_mcs =
+                            parse_vht_mcs_map(u16::from_le_bytes([payload[0], payload[1]]));
+                        vht.tx_max_mcs =
+                            parse_vht_mcs_map(u16::from_le_bytes([payload[4], payload[5]]));
+                    }
+                }
                 BandAttr::Bitrates
-                | BandAttr::HtMcsSet
-                | BandAttr::HtCapabilities
-                | BandAttr::HtAmpduFactor
-                | BandAttr::HtAmpduDensity
-                | BandAttr::VhtMcsSet
-                | BandAttr::VhtCapabilities
                 | BandAttr::IftypeData
                 | BandAttr::EdmgChannels
                 | BandAttr::EdmgBwConfig => (), // TODO: Implement all band attributes.
                 unhandled => debug!("Unhandled band attribute 'BandAttr::{unhandled:?}'"),
             }
         }
+        band.ht_capabilities = ht_capabilities;
+        band.vht_capabilities = vht_capabilities;
         Ok(band)
     }
 }
 
 #[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// HT (802.11n) capabilities of a band.
+pub struct HtCapabilities {
+    /// LDPC coding is supported.
+    pub ldpc_coding: bool,
+    /// 40 MHz channel width is supported.
+    pub channel_width_40mhz: bool,
+    /// HT-greenfield format is supported.
+    pub greenfield: bool,
+    /// Short guard interval is supported in 20 MHz channels.
+    pub short_gi_20mhz: bool,
+    /// Short guard interval is supported in 40 MHz channels.
+    pub short_gi_40mhz: bool,
+    /// Supported RX MCS indices.
+    pub supported_mcs: Vec<u8>,
+    /// Maximum A-MPDU length in bytes.
+    pub max_ampdu_length: u32,
+    /// Minimum MPDU start spacing as encoded in the A-MPDU parameters field
+    /// (0 = no restriction, 1 = 1/4 µs, 2 = 1/2 µs, ..., 7 = 16 µs).
+    pub min_mpdu_start_spacing: u8,
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// VHT (802.11ac) capabilities of a band.
+pub struct VhtCapabilities {
+    /// Maximum MPDU length in bytes.
+    pub max_mpdu_length: u32,
+    /// 160 MHz channel width is supported.
+    pub channel_width_160mhz: bool,
+    /// 80+80 MHz channel width is supported.
+    pub channel_width_80p80mhz: bool,
+    /// LDPC coding is supported.
+    pub ldpc_coding: bool,
+    /// Short guard interval is supported in 80 MHz channels.
+    pub short_gi_80mhz: bool,
+    /// Short guard interval is supported in 160 and 80+80 MHz channels.
+    pub short_gi_160mhz: bool,
+    /// Single user beamformer capable.
+    pub su_beamformer: bool,
+    /// Single user beamformee capable.
+    pub su_beamformee: bool,
+    /// Multi user beamformer capable.
+    pub mu_beamformer: bool,
+    /// Multi user beamformee capable.
+    pub mu_beamformee: bool,
+    /// Highest supported RX MCS index for each number of spatial streams,
+    /// starting from one spatial stream.
+    pub rx_max_mcs: Vec<u8>,
+    /// Highest supported TX MCS index for each number of spatial streams,
+    /// starting from one spatial stream.
+    pub tx_max_mcs: Vec<u8>,
+}
+
+/// Parse a VHT MCS map into the highest supported MCS index per spatial
+/// stream. Parsing stops at the first unsupported spatial stream.
+fn parse_vht_mcs_map(map: u16) -> Vec<u8> {
+    (0..8)
+        .map(|nss| (map >> (nss * 2)) & 0b11)
+        .take_while(|support| *support != 3)
+        .map(|support| 7 + support as u8)
+        .collect()
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Frequency information.
 pub struct Frequency {
-    /// Frequency in MHz.
-    pub frequency: u32,
+    /// Frequency.
+    pub frequency: Mhz,
     /// Channel is disabled in current regulatory domain.
     pub disabled: bool,
     /// No mechanisms that initiate radiation are permitted on this channel.
     pub no_ir: bool,
     /// Radar detection is mandatory on this channel in current regulatory domain.
     pub radar_detection: bool,
-    /// Maximum transmission power in mBm (100 * dBm).
-    pub max_tx_power: u32,
+    /// Maximum transmission power.
+    pub max_tx_power: Mbm,
+    /// Frequency offset in kHz.
+    pub offset: u32,
+    /// Current DFS state of the channel.
+    pub dfs_state: Option<DfsChannelState>,
+    /// Time in milliseconds for how long the channel has been in the current
+    /// DFS state.
+    pub dfs_time: Option<u32>,
+    /// DFS CAC time in milliseconds.
+    pub dfs_cac_time: Option<u32>,
+    /// Only indoor use is permitted on this channel.
+    pub indoor_only: bool,
+    /// Initiating radiation is allowed on this channel when concurrently
+    /// connected to a BSS on the same channel or UNII band.
+    pub ir_concurrent: bool,
+    /// HT40- is not possible with this channel as the control channel.
+    pub no_ht40_minus: bool,
+    /// HT40+ is not possible with this channel as the control channel.
+    pub no_ht40_plus: bool,
+    /// 80 MHz channels using this channel are not possible.
+    pub no_80mhz: bool,
+    /// 160 MHz channels using this channel are not possible.
+    pub no_160mhz: bool,
+    /// 320 MHz channels using this channel are not possible.
+    pub no_320mhz: bool,
+    /// HE operation is not allowed on this channel.
+    pub no_he: bool,
+    /// EHT operation is not allowed on this channel.
+    pub no_eht: bool,
+    /// 1 MHz operation is allowed on this channel.
+    pub allow_1mhz: bool,
+    /// 2 MHz operation is allowed on this channel.
+    pub allow_2mhz: bool,
+    /// 4 MHz operation is allowed on this channel.
+    pub allow_4mhz: bool,
+    /// 8 MHz operation is allowed on this channel.
+    pub allow_8mhz: bool,
+    /// 16 MHz operation is allowed on this channel.
+    pub allow_16mhz: bool,
+    /// Regulatory WMM limitations of the channel.
+    pub wmm_rules: Option<WmmRules>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Regulatory WMM limitations per access category.
+pub struct WmmRules {
+    /// Voice access category.
+    pub voice: WmmRule,
+    /// Video access category.
+    pub video: WmmRule,
+    /// Best effort access category.
+    pub best_effort: WmmRule,
+    /// Background access category.
+    pub background: WmmRule,
+}
+
+impl TryFrom<Attrs<'_, u16>> for WmmRules {
+    type Error = DeError;
+
+    fn try_from(handle: Attrs<'_, u16>) -> Result<Self, Self::Error> {
+        let mut rules = WmmRules::default();
+        for attr in handle.iter() {
+            let rule_handle: Attrs<'_, WmmRuleAttr> = attr.get_attr_handle()?;
+            let rule = rule_handle.try_into()?;
+            // Access categories are ordered as in the kernel: VO, VI, BE, BK.
+            match attr.nla_type().nla_type() {
+                0 => rules.voice = rule,
+                1 => rules.video = rule,
+                2 => rules.best_effort = rule,
+                3 => rules.background = rule,
+                unhandled => debug!("Unhandled WMM access category '{unhandled}'"),
+            }
+        }
+        Ok(rules)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Regulatory WMM limitation of an access category.
+pub struct WmmRule {
+    /// Minimum contention window slot.
+    pub cw_min: u16,
+    /// Maximum contention window slot.
+    pub cw_max: u16,
+    /// Arbitration inter frame space.
+    pub aifsn: u8,
+    /// Maximum allowed TX operation time in units of 32 microseconds.
+    pub txop: u16,
+}
+
+impl TryFrom<Attrs<'_, WmmRuleAttr>> for WmmRule {
+    type Error = DeError;
+
+    fn try_from(handle: Attrs<'_, WmmRuleAttr>) -> Result<Self, Self::Error> {
+        let mut rule = WmmRule::default();
+        for attr in handle.iter() {
+            match attr.nla_type().nla_type() {
+                WmmRuleAttr::CwMin => rule.cw_min = attr.get_payload_as()?,
+                WmmRuleAttr::CwMax => rule.cw_max = attr.get_payload_as()?,
+                WmmRuleAttr::Aifsn => rule.aifsn = attr.get_payload_as()?,
+                WmmRuleAttr::Txop => rule.txop = attr.get_payload_as()?,
+                unhandled => debug!("Unhandled WMM rule attribute 'WmmRuleAttr::{unhandled:?}'"),
+            }
+        }
+        Ok(rule)
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// DFS state of a channel.
+pub enum DfsChannelState {
+    /// The channel can be used, but channel availability check (CAC) must be
+    /// performed first.
+    Usable,
+    /// A radar has been detected on the channel.
+    Unavailable,
+    /// The channel has been CAC checked and is available.
+    Available,
+    /// Kernel returned an unknown DFS state.
+    Unknown,
+}
+
+impl From<NlDfsState> for DfsChannelState {
+    fn from(value: NlDfsState) -> Self {
+        match value {
+            NlDfsState::Usable => DfsChannelState::Usable,
+            NlDfsState::Unavailable => DfsChannelState::Unavailable,
+            NlDfsState::Available => DfsChannelState::Available,
+            NlDfsState::UnrecognizedConst(_) => DfsChannelState::Unknown,
+        }
+    }
 }
 
 impl TryFrom<Attrs<'_, FrequencyAttr>> for Frequency {
@@ -227,7 +1424,7 @@ impl TryFrom<Attrs<'_, FrequencyAttr>> for Frequency {
         for attr in handle.iter() {
             match attr.nla_type().nla_type() {
                 FrequencyAttr::Frequency => {
-                    frequency.frequency = attr.get_payload_as()?;
+                    frequency.frequency = Mhz(attr.get_payload_as()?);
                 }
                 FrequencyAttr::Disabled => {
                     frequency.disabled = true;
@@ -239,29 +1436,41 @@ impl TryFrom<Attrs<'_, FrequencyAttr>> for Frequency {
                     frequency.radar_detection = true;
                 }
                 FrequencyAttr::MaxTxPower => {
-                    frequency.max_tx_power = attr.get_payload_as()?;
-                }
-                FrequencyAttr::DfsState
-                | FrequencyAttr::DfdTime
-                | FrequencyAttr::NoHt40Minus
-                | FrequencyAttr::NoHt40Plus
-                | FrequencyAttr::No80Mhz
-                | FrequencyAttr::No160Mhz
-                | FrequencyAttr::DfsCacTime
-                | FrequencyAttr::IndoorOnly
-                | FrequencyAttr::IrConcurrent
-                | FrequencyAttr::No20Mhz
-                | FrequencyAttr::No10Mhz
-                | FrequencyAttr::Wmm
-                | FrequencyAttr::NoHe
-                | FrequencyAttr::Offset
-                | FrequencyAttr::Allow1Mhz
-                | FrequencyAttr::Allow2Mhz
-                | FrequencyAttr::Allow4Mhz
-                | FrequencyAttr::Allow8Mhz
-                | FrequencyAttr::Allow16Mhz
-                | FrequencyAttr::No320Mhz
-                | FrequencyAttr::NoEht => (), // TODO: Implement all frequency attributes.
+                    let power: u32 = attr.get_payload_as()?;
+                    frequency.max_tx_power = Mbm(power as i32);
+                }
+                FrequencyAttr::Offset => {
+                    frequency.offset = attr.get_payload_as()?;
+                }
+                FrequencyAttr::DfsState => {
+                    let state: NlDfsState = attr.get_payload_as()?;
+                    frequency.dfs_state = Some(state.into());
+                }
+                FrequencyAttr::DfdTime => {
+                    frequency.dfs_time = Some(attr.get_payload_as()?);
+                }
+                FrequencyAttr::DfsCacTime => {
+                    frequency.dfs_cac_time = Some(attr.get_payload_as()?);
+                }
+                FrequencyAttr::IndoorOnly => frequency.indoor_only = true,
+                FrequencyAttr::IrConcurrent => frequency.ir_concurrent = true,
+                FrequencyAttr::NoHt40Minus => frequency.no_ht40_minus = true,
+                FrequencyAttr::NoHt40Plus => frequency.no_ht40_plus = true,
+                FrequencyAttr::No80Mhz => frequency.no_80mhz = true,
+                FrequencyAttr::No160Mhz => frequency.no_160mhz = true,
+                FrequencyAttr::No320Mhz => frequency.no_320mhz = true,
+                FrequencyAttr::NoHe => frequency.no_he = true,
+                FrequencyAttr::NoEht => frequency.no_eht = true,
+                FrequencyAttr::Allow1Mhz => frequency.allow_1mhz = true,
+                FrequencyAttr::Allow2Mhz => frequency.allow_2mhz = true,
+                FrequencyAttr::Allow4Mhz => frequency.allow_4mhz = true,
+                FrequencyAttr::Allow8Mhz => frequency.allow_8mhz = true,
+                FrequencyAttr::Allow16Mhz => frequency.allow_16mhz = true,
+                FrequencyAttr::Wmm => {
+                    let sub_handle: Attrs<'_, u16> = attr.get_attr_handle()?;
+                    frequency.wmm_rules = Some(sub_handle.try_into()?);
+                }
+                FrequencyAttr::No20Mhz | FrequencyAttr::No10Mhz => (), // TODO: Implement all frequency attributes.
                 unhandled => {
                     debug!("Unhandled frequency attribute 'FrequencyAttr::{unhandled:?}'",)
                 }
@@ -270,3 +1479,314 @@ impl TryFrom<Attrs<'_, FrequencyAttr>> for Frequency {
         Ok(frequency)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use neli::types::GenlBuffer;
+    use neli::FromBytesWithInput;
+
+    use super::*;
+    use crate::attributes::encode_attribute;
+    use crate::units::Khz;
+
+    fn parse_band(attributes: &[(BandAttr, &[u8])]) -> WifiBand {
+        let mut buffer = Vec::new();
+        for (attr_type, payload) in attributes {
+            encode_attribute(&mut buffer, u16::from(*attr_type), payload);
+        }
+        let attrs = GenlBuffer::<BandAttr, Buffer>::from_bytes_with_input(
+            &mut Cursor::new(&buffer),
+            buffer.len(),
+        )
+        .unwrap();
+        WifiBand::try_from(Attrs::new(attrs)).unwrap()
+    }
+
+    fn ht_capabilities(bits: u16) -> HtCapabilities {
+        parse_band(&[(BandAttr::HtCapabilities, &bits.to_ne_bytes())])
+            .ht_capabilities
+            .unwrap()
+    }
+
+    fn vht_capabilities(bits: u32) -> VhtCapabilities {
+        parse_band(&[(BandAttr::VhtCapabilities, &bits.to_ne_bytes())])
+            .vht_capabilities
+            .unwrap()
+    }
+
+    #[test]
+    fn test_ht_capability_bits() {
+        let ht = ht_capabilities(0);
+        assert!(!ht.ldpc_coding && !ht.channel_width_40mhz && !ht.greenfield);
+        assert!(!ht.short_gi_20mhz && !ht.short_gi_40mhz);
+
+        assert!(ht_capabilities(1 << 0).ldpc_coding);
+        assert!(ht_capabilities(1 << 1).channel_width_40mhz);
+        assert!(ht_capabilities(1 << 4).greenfield);
+        assert!(ht_capabilities(1 << 5).short_gi_20mhz);
+        assert!(ht_capabilities(1 << 6).short_gi_40mhz);
+        // SM power save and other fields do not set any decoded flag.
+        let ht = ht_capabilities(0b1000_1100);
+        assert!(!ht.ldpc_coding && !ht.channel_width_40mhz && !ht.greenfield);
+        assert!(!ht.short_gi_20mhz && !ht.short_gi_40mhz);
+    }
+
+    #[test]
+    fn test_ht_ampdu_and_mcs() {
+        let mut mcs_set = [0u8; 16];
+        mcs_set[0] = 0xff;
+        mcs_set[1] = 0x01;
+        mcs_set[9] = 0x1f;
+        // The RX highest supported data rate is not part of the MCS bitmask.
+        mcs_set[10] = 0xff;
+        let band = parse_band(&[
+            (BandAttr::HtMcsSet, &mcs_set),
+            (BandAttr::HtAmpduFactor, &[3]),
+            (BandAttr::HtAmpduDensity, &[6]),
+        ]);
+        let ht = band.ht_capabilities.unwrap();
+        let mut expected: Vec<u8> = (0..=8).collect();
+        expected.extend(72..=76);
+        assert_eq!(ht.supported_mcs, expected);
+        assert_eq!(ht.max_ampdu_length, 65535);
+        assert_eq!(ht.min_mpdu_start_spacing, 6);
+    }
+
+    #[test]
+    fn test_vht_capability_bits() {
+        assert_eq!(vht_capabilities(0).max_mpdu_length, 3895);
+        assert_eq!(vht_capabilities(1).max_mpdu_length, 7991);
+        assert_eq!(vht_capabilities(2).max_mpdu_length, 11454);
+
+        let vht = vht_capabilities(0);
+        assert!(!vht.channel_width_160mhz && !vht.channel_width_80p80mhz);
+        let vht = vht_capabilities(1 << 2);
+        assert!(vht.channel_width_160mhz && !vht.channel_width_80p80mhz);
+        let vht = vht_capabilities(2 << 2);
+        assert!(vht.channel_width_160mhz && vht.channel_width_80p80mhz);
+
+        assert!(vht_capabilities(1 << 4).ldpc_coding);
+        assert!(vht_capabilities(1 << 5).short_gi_80mhz);
+        assert!(vht_capabilities(1 << 6).short_gi_160mhz);
+        assert!(vht_capabilities(1 << 11).su_beamformer);
+        assert!(vht_capabilities(1 << 12).su_beamformee);
+        assert!(vht_capabilities(1 << 19).mu_beamformer);
+        assert!(vht_capabilities(1 << 20).mu_beamformee);
+        let vht = vht_capabilities(1 << 4);
+        assert!(!vht.short_gi_80mhz && !vht.su_beamformer && !vht.mu_beamformee);
+    }
+
+    #[test]
+    fn test_vht_mcs_map() {
+        // Two spatial streams with MCS 0-9 and MCS 0-7, the rest unsupported.
+        assert_eq!(parse_vht_mcs_map(0xfff2), vec![9, 7]);
+        assert_eq!(parse_vht_mcs_map(0xffff), Vec::<u8>::new());
+        assert_eq!(parse_vht_mcs_map(0x5555), vec![8; 8]);
+
+        let mut mcs_set = [0u8; 8];
+        mcs_set[0..2].copy_from_slice(&0xfffau16.to_le_bytes());
+        mcs_set[4..6].copy_from_slice(&0xfffeu16.to_le_bytes());
+        let band = parse_band(&[(BandAttr::VhtMcsSet, &mcs_set)]);
+        let vht = band.vht_capabilities.unwrap();
+        assert_eq!(vht.rx_max_mcs, vec![9, 9]);
+        assert_eq!(vht.tx_max_mcs, vec![9]);
+    }
+
+    #[test]
+    fn test_extended_features() {
+        let features = ExtendedFeatures {
+            bitmap: vec![0b0000_0001, 0b1000_0001, 0b0000_0000],
+        };
+        assert!(features.is_supported(ExtendedFeature::VHT_IBSS));
+        assert!(!features.is_supported(ExtendedFeature::RRM));
+        assert!(features.is_supported(ExtendedFeature::BEACON_RATE_VHT));
+        assert!(!features.is_supported(ExtendedFeature(9)));
+        assert!(features.is_supported(ExtendedFeature::FOUR_WAY_HANDSHAKE_STA_PSK));
+        assert!(!features.is_supported(ExtendedFeature::FOUR_WAY_HANDSHAKE_STA_1X));
+        // Features beyond the reported bitmap are not supported.
+        assert!(!features.is_supported(ExtendedFeature(24)));
+        assert!(!features.is_supported(ExtendedFeature::SPP_AMSDU_SUPPORT));
+        assert!(!ExtendedFeatures::default().is_supported(ExtendedFeature::VHT_IBSS));
+
+        let device = PhysicalDevice {
+            extended_features: features,
+            ..Default::default()
+        };
+        assert!(device.is_supported(ExtendedFeature::FOUR_WAY_HANDSHAKE_STA_PSK));
+        assert!(!device.is_supported(ExtendedFeature::RRM));
+    }
+
+    fn frequency(frequency: u32) -> Frequency {
+        Frequency {
+            frequency: Mhz(frequency),
+            ..Default::default()
+        }
+    }
+
+    fn band_5ghz(frequencies: Vec<Frequency>) -> WifiBand {
+        WifiBand {
+            frequencies,
+            ht_capabilities: Some(HtCapabilities {
+                channel_width_40mhz: true,
+                ..Default::default()
+            }),
+            vht_capabilities: Some(VhtCapabilities {
+                channel_width_160mhz: true,
+                ..Default::default()
+            }),
+        }
+    }
+
+    fn device(band_5ghz: WifiBand) -> PhysicalDevice {
+        PhysicalDevice {
+            band_5ghz: Some(band_5ghz),
+            ..Default::default()
+        }
+    }
+
+    fn rule(start: u32, end: u32, max_bandwidth: u32) -> RegulatoryRule {
+        RegulatoryRule {
+            freq_range_start: Khz::from(Mhz(start)),
+            freq_range_end: Khz::from(Mhz(end)),
+            max_bandwidth: Khz::from(Mhz(max_bandwidth)),
+            max_eirp: Mbm(2300),
+            ..Default::default()
+        }
+    }
+
+    fn domain(rules: Vec<RegulatoryRule>) -> RegulatoryDomain {
+        RegulatoryDomain {
+            rules,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_channel_plan_without_rules() {
+        let device = device(band_5ghz(vec![frequency(5180)]));
+        let plan = device.channel_plan(&RegulatoryDomain::default());
+        assert_eq!(
+            plan,
+            vec![ChannelAvailability {
+                frequency: Mhz(5180),
+                access_point: true,
+                adhoc: true,
+                monitor: true,
+                radar_detection: false,
+                max_eirp: None,
+                width_20mhz: true,
+                width_40mhz: true,
+                width_80mhz: true,
+                width_160mhz: true,
+                width_320mhz: true,
+            }]
+        );
+    }
+
+    #[test]
+    fn test_channel_plan_capabilities() {
+        let mut band = band_5ghz(vec![frequency(5180)]);
+        band.vht_capabilities = None;
+        let plan = device(band.clone()).channel_plan(&RegulatoryDomain::default());
+        assert!(plan[0].width_40mhz);
+        assert!(!plan[0].width_80mhz && !plan[0].width_160mhz && !plan[0].width_320mhz);
+
+        band.ht_capabilities = None;
+        let plan = device(band.clone()).channel_plan(&RegulatoryDomain::default());
+        assert!(plan[0].width_20mhz && !plan[0].width_40mhz);
+
+        // HT and VHT capabilities do not limit the 6 GHz band.
+        let device = PhysicalDevice {
+            band_6ghz: Some(WifiBand {
+                frequencies: vec![frequency(5955)],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let plan = device.channel_plan(&RegulatoryDomain::default());
+        assert!(plan[0].width_160mhz && plan[0].width_320mhz);
+    }
+
+    #[test]
+    fn test_channel_plan_regulatory_rules() {
+        let device = device(band_5ghz(vec![frequency(5180), frequency(5745)]));
+        let plan = device.channel_plan(&domain(vec![rule(5170, 5250, 80)]));
+        assert_eq!(plan.len(), 2);
+
+        assert_eq!(plan[0].max_eirp, Some(Mbm(2300)));
+        assert!(plan[0].access_point && plan[0].width_80mhz);
+        assert!(!plan[0].width_160mhz && !plan[0].width_320mhz);
+
+        // Channels not covered by any rule are not permitted at all.
+        assert_eq!(plan[1].max_eirp, None);
+        assert!(!plan[1].access_point && !plan[1].adhoc && !plan[1].monitor);
+        assert!(!plan[1].width_20mhz && !plan[1].width_40mhz);
+
+        let plan = device.channel_plan(&domain(vec![RegulatoryRule {
+            no_ir: true,
+            no_80mhz: true,
+            ..rule(5170, 5250, 160)
+        }]));
+        assert!(!plan[0].access_point && !plan[0].adhoc && plan[0].monitor);
+        assert!(plan[0].width_40mhz && !plan[0].width_80mhz && !plan[0].width_160mhz);
+
+        let plan = device.channel_plan(&domain(vec![RegulatoryRule {
+            no_ht40_minus: true,
+            no_ht40_plus: true,
+            ..rule(5170, 5250, 160)
+        }]));
+        assert!(plan[0].width_20mhz && !plan[0].width_40mhz && !plan[0].width_80mhz);
+
+        let plan = device.channel_plan(&domain(vec![RegulatoryRule {
+            auto_bandwidth: true,
+            ..rule(5170, 5250, 20)
+        }]));
+        assert!(plan[0].width_160mhz);
+    }
+
+    #[test]
+    fn test_channel_plan_dfs() {
+        let device = device(band_5ghz(vec![
+            Frequency {
+                radar_detection: true,
+                dfs_state: Some(DfsChannelState::Usable),
+                ..frequency(5260)
+            },
+            Frequency {
+                radar_detection: true,
+                dfs_state: Some(DfsChannelState::Unavailable),
+                ..frequency(5280)
+            },
+            Frequency {
+                radar_detection: true,
+                dfs_state: Some(DfsChannelState::Available),
+                ..frequency(5300)
+            },
+            frequency(5320),
+        ]));
+        let plan = device.channel_plan(&domain(vec![RegulatoryRule {
+            dfs: true,
+            ..rule(5250, 5330, 80)
+        }]));
+
+        // CAC has not been done or a radar was detected.
+        for channel in &plan[0..2] {
+            assert!(channel.radar_detection);
+            assert!(!channel.access_point && !channel.adhoc);
+            assert!(channel.monitor && channel.width_80mhz);
+        }
+        assert!(plan[2].radar_detection && plan[2].access_point);
+        // Radar detection required by the rule only.
+        assert!(plan[3].radar_detection && plan[3].access_point);
+    }
+
+    #[test]
+    fn test_channel_plan_interface_types() {
+        let mut device = device(band_5ghz(vec![frequency(5180)]));
+        device.supported_interface_types = vec![InterfaceType::Station, InterfaceType::Monitor];
+        let plan = device.channel_plan(&RegulatoryDomain::default());
+        assert!(!plan[0].access_point && !plan[0].adhoc && plan[0].monitor);
+    }
+}